@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Aurora animation - Northern lights effect with flowing vertical curtains
+#[derive(Debug)]
 pub(crate) struct Aurora;
 
 impl Animation for Aurora {
@@ -25,6 +26,6 @@ impl Animation for Aurora {
         let base_lightness = 45.0 + field.abs() * 18.0; // brighter on wave ridges
         let lightness = base_lightness + (y * 0.25 + t).sin() * 6.0;
 
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, saturation.clamp(35.0, 100.0), lightness.clamp(30.0, 80.0)))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, saturation.clamp(35.0, 100.0), lightness.clamp(30.0, 80.0)))
     }
 }
@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Breathe animation - Gentle synchronized breathing effect
+#[derive(Debug)]
 pub(crate) struct Breathe;
 
 impl Animation for Breathe {
@@ -9,6 +10,6 @@ impl Animation for Breathe {
         let saturation = 65.0; // Calming, not too vibrant
         // Synchronized breathing: all characters pulse together
         let lightness = 35.0 + 20.0 * (ctx.hue_offset * 0.05).sin();
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, saturation, lightness))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, saturation, lightness))
     }
 }
@@ -1,10 +1,13 @@
 use crate::markdown::text_style::Color;
+use std::fmt;
 
 /// Animation context passed to all animation renderers
 #[derive(Debug, Clone)]
-pub(crate) struct AnimationContext {
+pub(crate) struct AnimationContext<'a> {
     /// Current hue offset (0-360) - drives animation progression
     pub hue_offset: f32,
+    /// Seconds elapsed since the current animation cycle started
+    pub time: f32,
     /// Character index within non-whitespace characters
     pub char_index: usize,
     /// Total non-whitespace characters in the banner
@@ -19,6 +22,10 @@ pub(crate) struct AnimationContext {
     pub total_cols: usize,
     /// The character being rendered
     pub ch: char,
+    /// The color the character has when it isn't animated, if it has one
+    pub base_color: Option<Color>,
+    /// The palette colors are mapped onto
+    pub palette: &'a AnimationPalette,
 }
 
 /// Result from rendering a single character with animation
@@ -62,9 +69,59 @@ impl CharAnimationResult {
 }
 
 /// Trait for animation styles
-pub(crate) trait Animation {
+pub(crate) trait Animation: fmt::Debug {
     /// Render a single character with the animation style
     fn render_char(&self, ctx: &AnimationContext) -> CharAnimationResult;
+
+    /// Render the background behind a whitespace character, if the style draws one
+    fn render_whitespace(&self, _ctx: &AnimationContext) -> Option<Color> {
+        None
+    }
+}
+
+/// The colors an animation's output is mapped onto.
+///
+/// Animations pick their colors in HSL. With no stops they're used as is; otherwise the hue is
+/// a position along a gradient that cycles through the stops, and saturation and lightness are
+/// applied on top of the color at that position.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AnimationPalette {
+    stops: Vec<(u8, u8, u8)>,
+}
+
+impl AnimationPalette {
+    pub(crate) fn new(stops: Vec<(u8, u8, u8)>) -> Self {
+        Self { stops }
+    }
+
+    /// Get the color for the given HSL values.
+    /// H: hue (0-360), S: saturation (0-100), L: lightness (0-100)
+    pub(crate) fn hsl(&self, h: f32, s: f32, l: f32) -> Color {
+        if self.stops.is_empty() {
+            return hsl_to_rgb(h, s, l);
+        }
+        let position = h.rem_euclid(360.0) / 360.0 * self.stops.len() as f32;
+        let index = position.floor() as usize % self.stops.len();
+        let next = self.stops[(index + 1) % self.stops.len()];
+        let color = mix(self.stops[index], next, position.fract());
+
+        // Saturation pulls the color towards a grey of the same brightness.
+        let grey = ((color.0 as u16 + color.1 as u16 + color.2 as u16) / 3) as u8;
+        let color = mix((grey, grey, grey), color, (s / 100.0).clamp(0.0, 1.0));
+
+        // 50% lightness is the color itself, anything else mixes it with black or white.
+        let (r, g, b) = if l < 50.0 {
+            mix((0, 0, 0), color, (l / 50.0).max(0.0))
+        } else {
+            mix(color, (255, 255, 255), ((l - 50.0) / 50.0).min(1.0))
+        };
+        Color::new(r, g, b)
+    }
+}
+
+pub(super) fn mix(from: (u8, u8, u8), to: (u8, u8, u8), ratio: f32) -> (u8, u8, u8) {
+    let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * ratio).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
 }
 
 /// Convert HSL to RGB color
@@ -93,9 +150,31 @@ pub(crate) fn hsl_to_rgb(h: f32, s: f32, l: f32) -> Color {
     )
 }
 
-/// Generate a rainbow color for a given position
-/// total: total number of positions, index: current position (0-based)
-pub(crate) fn rainbow_color(index: usize, total: usize) -> Color {
-    let hue = (index as f32 / total as f32) * 360.0;
-    hsl_to_rgb(hue, 100.0, 50.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, 100.0, 50.0)]
+    #[case(200.0, 65.0, 35.0)]
+    #[case(359.0, 20.0, 85.0)]
+    fn no_stops(#[case] h: f32, #[case] s: f32, #[case] l: f32) {
+        assert_eq!(AnimationPalette::default().hsl(h, s, l), hsl_to_rgb(h, s, l));
+    }
+
+    #[rstest]
+    #[case::first_stop(0.0, 100.0, 50.0, Color::new(255, 0, 0))]
+    #[case::second_stop(180.0, 100.0, 50.0, Color::new(0, 0, 255))]
+    #[case::between_stops(90.0, 100.0, 50.0, Color::new(128, 0, 128))]
+    #[case::wraps_around(360.0, 100.0, 50.0, Color::new(255, 0, 0))]
+    #[case::dark(0.0, 100.0, 25.0, Color::new(128, 0, 0))]
+    #[case::light(180.0, 100.0, 75.0, Color::new(128, 128, 255))]
+    #[case::black(0.0, 100.0, 0.0, Color::new(0, 0, 0))]
+    #[case::white(0.0, 100.0, 100.0, Color::new(255, 255, 255))]
+    #[case::desaturated(0.0, 0.0, 50.0, Color::new(85, 85, 85))]
+    fn stops(#[case] h: f32, #[case] s: f32, #[case] l: f32, #[case] expected: Color) {
+        let palette = AnimationPalette::new(vec![(255, 0, 0), (0, 0, 255)]);
+        assert_eq!(palette.hsl(h, s, l), expected);
+    }
 }
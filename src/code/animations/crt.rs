@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// CRT animation - Retro CRT effect with scanlines, phosphor triads, and rolling highlight
+#[derive(Debug)]
 pub(crate) struct Crt;
 
 impl Animation for Crt {
@@ -21,21 +22,21 @@ impl Animation for Crt {
         };
 
         // Horizontal scanlines: every other row is slightly darker
-        let scanline = if ctx.row_index % 2 == 0 { -8.0 } else { 0.0 };
+        let scanline = if ctx.row_index.is_multiple_of(2) { -8.0 } else { 0.0 };
 
         // Rolling bright bar moving down (vertical retrace)
         let bar_pos = (t * 0.35) % 360.0; // 0..360
         // Map 0..360 to rows cyclically
-        let bar_row = (bar_pos / 360.0) * ctx.total_rows as f32;
+        let bar_row = (bar_pos / 360.0) * (ctx.total_rows as f32).max(1.0);
         let dist = (y - bar_row).abs();
         let bar_boost = (1.0 - (dist / 2.5).min(1.0)) * 18.0; // strong near the bar
 
         // Subtle noise flicker
-        let noise = ((x * 12.9898 + y * 78.233 + t).sin() * 43758.5453).fract() * 4.0 - 2.0;
+        let noise = ((x * 12.9898 + y * 78.233 + t).sin() * 43_758.547).fract() * 4.0 - 2.0;
 
         let saturation = 75.0;
         let lightness = 42.0 + scanline + bar_boost + noise;
 
-        CharAnimationResult::with_color(hsl_to_rgb(hue, saturation, lightness.clamp(25.0, 80.0)))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue, saturation, lightness.clamp(25.0, 80.0)))
     }
 }
@@ -0,0 +1,305 @@
+use super::{
+    common::{Animation, AnimationContext, CharAnimationResult, mix},
+    expression::{Expression, noise},
+};
+use crate::{
+    markdown::text_style::{Color, UndefinedPaletteColorError},
+    theme::{ColorPalette, raw::RawColor},
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A banner animation described in YAML rather than in code.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AnimationSpec {
+    /// The color of every character.
+    #[serde(with = "serde_yaml::with::singleton_map")]
+    pub(crate) color: ColorSpec,
+
+    /// The color behind every character, including whitespace.
+    #[serde(default, with = "serde_yaml::with::singleton_map")]
+    pub(crate) background: Option<ColorSpec>,
+
+    /// The characters that get replaced while animating.
+    #[serde(default)]
+    pub(crate) glyphs: Option<GlyphSpec>,
+}
+
+/// How to compute a character's color.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub(crate) enum ColorSpec {
+    /// A color in HSL, which is mapped onto the theme's banner gradient like built in animations.
+    Hsl {
+        /// The hue, in degrees.
+        hue: Expression,
+
+        /// The saturation, between 0 and 100.
+        #[serde(default)]
+        saturation: Option<Expression>,
+
+        /// The lightness, between 0 and 100.
+        #[serde(default)]
+        lightness: Option<Expression>,
+    },
+
+    /// A position in a gradient that goes through a list of colors.
+    Gradient {
+        /// The gradient's colors.
+        stops: Vec<RawColor>,
+
+        /// The position in the gradient, between 0 (the first color) and 1 (the last one).
+        position: Expression,
+    },
+}
+
+/// How to replace characters.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct GlyphSpec {
+    /// Characters are replaced whenever this is positive.
+    pub(crate) when: Expression,
+
+    /// The replacements for specific characters.
+    #[serde(default)]
+    pub(crate) table: BTreeMap<char, String>,
+
+    /// The replacements for characters that aren't in the table.
+    #[serde(default)]
+    pub(crate) characters: Option<String>,
+
+    /// The index of the replacement to use. Defaults to a pseudo random one.
+    #[serde(default)]
+    pub(crate) pick: Option<Expression>,
+}
+
+/// An animation built from an [AnimationSpec].
+#[derive(Debug)]
+pub(crate) struct CustomAnimation {
+    color: ColorFunction,
+    background: Option<ColorFunction>,
+    glyphs: Option<Glyphs>,
+}
+
+impl CustomAnimation {
+    pub(crate) fn new(spec: &AnimationSpec, palette: &ColorPalette) -> Result<Self, CustomAnimationError> {
+        let AnimationSpec { color, background, glyphs } = spec;
+        let color = ColorFunction::new(color, palette)?;
+        let background = background.as_ref().map(|background| ColorFunction::new(background, palette)).transpose()?;
+        let glyphs = glyphs.as_ref().map(Glyphs::new).transpose()?;
+        Ok(Self { color, background, glyphs })
+    }
+}
+
+impl Animation for CustomAnimation {
+    fn render_char(&self, ctx: &AnimationContext) -> CharAnimationResult {
+        let result = CharAnimationResult {
+            color: self.color.evaluate(ctx),
+            bg_color: self.render_whitespace(ctx),
+            replacement_char: None,
+        };
+        match &self.glyphs {
+            Some(glyphs) => CharAnimationResult { replacement_char: glyphs.replacement(ctx), ..result },
+            None => result,
+        }
+    }
+
+    fn render_whitespace(&self, ctx: &AnimationContext) -> Option<Color> {
+        self.background.as_ref().map(|background| background.evaluate(ctx))
+    }
+}
+
+#[derive(Debug)]
+enum ColorFunction {
+    Hsl { hue: Expression, saturation: Expression, lightness: Expression },
+    Gradient { stops: Vec<(u8, u8, u8)>, position: Expression },
+}
+
+impl ColorFunction {
+    fn new(spec: &ColorSpec, palette: &ColorPalette) -> Result<Self, CustomAnimationError> {
+        match spec {
+            ColorSpec::Hsl { hue, saturation, lightness } => Ok(Self::Hsl {
+                hue: hue.clone(),
+                saturation: saturation.clone().unwrap_or_else(|| Expression::constant(100.0)),
+                lightness: lightness.clone().unwrap_or_else(|| Expression::constant(50.0)),
+            }),
+            ColorSpec::Gradient { stops, position } => {
+                if stops.is_empty() {
+                    return Err(CustomAnimationError::EmptyGradient);
+                }
+                let mut colors = Vec::new();
+                for stop in stops {
+                    let color = stop.resolve(palette)?.and_then(|color| color.as_rgb());
+                    colors.push(color.ok_or(CustomAnimationError::GradientColor(stop.to_string()))?);
+                }
+                Ok(Self::Gradient { stops: colors, position: position.clone() })
+            }
+        }
+    }
+
+    fn evaluate(&self, ctx: &AnimationContext) -> Color {
+        match self {
+            Self::Hsl { hue, saturation, lightness } => {
+                let hue = hue.evaluate(ctx).rem_euclid(360.0);
+                let saturation = saturation.evaluate(ctx).clamp(0.0, 100.0);
+                let lightness = lightness.evaluate(ctx).clamp(0.0, 100.0);
+                ctx.palette.hsl(hue, saturation, lightness)
+            }
+            Self::Gradient { stops, position } => {
+                let position = position.evaluate(ctx).clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+                let index = position.floor() as usize;
+                let next = stops[(index + 1).min(stops.len() - 1)];
+                let (r, g, b) = mix(stops[index], next, position.fract());
+                Color::new(r, g, b)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Glyphs {
+    when: Expression,
+    table: BTreeMap<char, Vec<char>>,
+    characters: Vec<char>,
+    pick: Option<Expression>,
+}
+
+impl Glyphs {
+    fn new(spec: &GlyphSpec) -> Result<Self, CustomAnimationError> {
+        let GlyphSpec { when, table, characters, pick } = spec;
+        let table: BTreeMap<_, Vec<_>> =
+            table.iter().map(|(c, replacements)| (*c, replacements.chars().collect())).collect();
+        let characters: Vec<_> = characters.iter().flat_map(|characters| characters.chars()).collect();
+        if table.values().any(Vec::is_empty) || (table.is_empty() && characters.is_empty()) {
+            return Err(CustomAnimationError::EmptyGlyphs);
+        }
+        Ok(Self { when: when.clone(), table, characters, pick: pick.clone() })
+    }
+
+    fn replacement(&self, ctx: &AnimationContext) -> Option<char> {
+        if self.when.evaluate(ctx) <= 0.0 {
+            return None;
+        }
+        let replacements = self.table.get(&ctx.ch).unwrap_or(&self.characters);
+        if replacements.is_empty() {
+            return None;
+        }
+        let index = match &self.pick {
+            Some(pick) => pick.evaluate(ctx).floor(),
+            None => (noise(ctx.char_index as f32 + ctx.hue_offset) * replacements.len() as f32).floor(),
+        };
+        Some(replacements[(index as i64).rem_euclid(replacements.len() as i64) as usize])
+    }
+}
+
+/// An error building a custom animation.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CustomAnimationError {
+    #[error(transparent)]
+    Palette(#[from] UndefinedPaletteColorError),
+
+    #[error("gradient color '{0}' is not an RGB color")]
+    GradientColor(String),
+
+    #[error("gradients need at least one color")]
+    EmptyGradient,
+
+    #[error("glyph replacements can't be empty")]
+    EmptyGlyphs,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::animations::AnimationPalette;
+    use rstest::rstest;
+
+    fn context(palette: &AnimationPalette, hue_offset: f32, ch: char) -> AnimationContext<'_> {
+        AnimationContext {
+            hue_offset,
+            time: 0.0,
+            char_index: 2,
+            total_chars: 4,
+            row_index: 0,
+            total_rows: 1,
+            col_index: 2,
+            total_cols: 4,
+            ch,
+            base_color: None,
+            palette,
+        }
+    }
+
+    fn build(spec: &str) -> Result<CustomAnimation, CustomAnimationError> {
+        let spec: AnimationSpec = serde_yaml::from_str(spec).expect("invalid spec");
+        CustomAnimation::new(&spec, &ColorPalette::default())
+    }
+
+    #[test]
+    fn hsl() {
+        let animation = build("color: { hsl: { hue: 'hue_offset + 120', lightness: 25 } }").unwrap();
+        let palette = AnimationPalette::default();
+        let result = animation.render_char(&context(&palette, 0.0, '#'));
+        assert_eq!(result.color, super::super::common::hsl_to_rgb(120.0, 100.0, 25.0));
+        assert_eq!(result.bg_color, None);
+        assert_eq!(result.replacement_char, None);
+    }
+
+    #[rstest]
+    #[case::start(0.0, Color::new(0, 0, 0))]
+    #[case::middle(180.0, Color::new(255, 0, 0))]
+    #[case::between(90.0, Color::new(128, 0, 0))]
+    #[case::end(360.0, Color::new(255, 255, 255))]
+    fn gradient(#[case] hue_offset: f32, #[case] expected: Color) {
+        let animation = build("color: { gradient: { stops: [000000, ff0000, ffffff], position: progress } }").unwrap();
+        let palette = AnimationPalette::default();
+        assert_eq!(animation.render_char(&context(&palette, hue_offset, '#')).color, expected);
+    }
+
+    #[test]
+    fn background() {
+        let animation = build(
+            "
+color: { hsl: { hue: 0 } }
+background: { gradient: { stops: [0000ff], position: 0 } }
+",
+        )
+        .unwrap();
+        let palette = AnimationPalette::default();
+        let ctx = context(&palette, 0.0, ' ');
+        assert_eq!(animation.render_whitespace(&ctx), Some(Color::new(0, 0, 255)));
+        assert_eq!(animation.render_char(&ctx).bg_color, Some(Color::new(0, 0, 255)));
+    }
+
+    #[rstest]
+    #[case::table('#', Some('▓'))]
+    #[case::characters('x', Some('1'))]
+    #[case::disabled('!', None)]
+    fn glyphs(#[case] ch: char, #[case] expected: Option<char>) {
+        let animation = build(
+            "
+color: { hsl: { hue: 0 } }
+glyphs:
+  when: 'hue_offset < 180'
+  table: { '#': '░▓' }
+  characters: '01'
+  pick: char_index + 1
+",
+        )
+        .unwrap();
+        let palette = AnimationPalette::default();
+        let hue_offset = if ch == '!' { 270.0 } else { 90.0 };
+        assert_eq!(animation.render_char(&context(&palette, hue_offset, ch)).replacement_char, expected);
+    }
+
+    #[rstest]
+    #[case::empty_gradient("color: { gradient: { stops: [], position: 0 } }")]
+    #[case::named_color("color: { gradient: { stops: [red], position: 0 } }")]
+    #[case::undefined_palette_color("color: { gradient: { stops: ['palette:foo'], position: 0 } }")]
+    #[case::no_glyphs("{ color: { hsl: { hue: 0 } }, glyphs: { when: 1 } }")]
+    #[case::empty_glyphs("{ color: { hsl: { hue: 0 } }, glyphs: { when: 1, table: { a: '' } } }")]
+    fn invalid(#[case] spec: &str) {
+        assert!(build(spec).is_err());
+    }
+}
@@ -0,0 +1,620 @@
+use super::common::AnimationContext;
+use serde::{Deserialize, Deserializer, de::Visitor};
+use std::{fmt, str::FromStr};
+
+/// An arithmetic expression evaluated for every character in a banner.
+///
+/// Expressions support numbers, the variables in [Variable], the functions in [Function], the
+/// `+ - * / % ^` operators, comparisons (which evaluate to 1 or 0) and parentheses.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Expression(Node);
+
+impl Expression {
+    pub(crate) fn constant(value: f32) -> Self {
+        Self(Node::Number(value))
+    }
+
+    pub(crate) fn evaluate(&self, ctx: &AnimationContext) -> f32 {
+        self.0.evaluate(ctx)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseExpressionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens, position: 0, length: input.chars().count() };
+        let node = parser.parse_comparison()?;
+        match parser.tokens.get(parser.position) {
+            Some((column, token)) => {
+                Err(ParseExpressionError::new(*column, ExpressionError::UnexpectedToken(token.to_string())))
+            }
+            None => Ok(Self(node)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ExpressionVisitor;
+
+        impl Visitor<'_> for ExpressionVisitor {
+            type Value = Expression;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a number or an expression")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(Expression::constant(value as f32))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(Expression::constant(value as f32))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(Expression::constant(value as f32))
+            }
+        }
+
+        deserializer.deserialize_any(ExpressionVisitor)
+    }
+}
+
+/// A value taken from the character being rendered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Variable {
+    /// The animation's progress through its cycle, from 0 to 360.
+    HueOffset,
+
+    /// The animation's progress through its cycle, from 0 to 1.
+    Progress,
+
+    /// The seconds elapsed since the cycle started.
+    Time,
+
+    /// The index of the character among non-whitespace characters.
+    CharIndex,
+
+    /// The number of non-whitespace characters.
+    TotalChars,
+
+    /// The row the character is in.
+    RowIndex,
+
+    /// The number of rows.
+    TotalRows,
+
+    /// The column the character is in.
+    ColIndex,
+
+    /// The number of columns in the character's row.
+    TotalCols,
+
+    /// The constant π.
+    Pi,
+}
+
+impl Variable {
+    fn from_name(name: &str) -> Option<Self> {
+        let variable = match name {
+            "hue_offset" => Self::HueOffset,
+            "progress" => Self::Progress,
+            "time" => Self::Time,
+            "char_index" => Self::CharIndex,
+            "total_chars" => Self::TotalChars,
+            "row_index" => Self::RowIndex,
+            "total_rows" => Self::TotalRows,
+            "col_index" => Self::ColIndex,
+            "total_cols" => Self::TotalCols,
+            "pi" => Self::Pi,
+            _ => return None,
+        };
+        Some(variable)
+    }
+
+    fn value(&self, ctx: &AnimationContext) -> f32 {
+        match self {
+            Self::HueOffset => ctx.hue_offset,
+            Self::Progress => ctx.hue_offset / 360.0,
+            Self::Time => ctx.time,
+            Self::CharIndex => ctx.char_index as f32,
+            Self::TotalChars => ctx.total_chars as f32,
+            Self::RowIndex => ctx.row_index as f32,
+            Self::TotalRows => ctx.total_rows as f32,
+            Self::ColIndex => ctx.col_index as f32,
+            Self::TotalCols => ctx.total_cols as f32,
+            Self::Pi => std::f32::consts::PI,
+        }
+    }
+}
+
+/// A function that can be called in an expression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Abs,
+    Floor,
+    Ceil,
+    Round,
+    Fract,
+    Sqrt,
+    /// A pseudo random number in the `[0, 1)` range that's stable for the same input.
+    Noise,
+    Min,
+    Max,
+    Clamp,
+    /// Linear interpolation between the first two arguments by the third one.
+    Mix,
+    /// The second argument if the first one is positive, otherwise the third one.
+    If,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        let function = match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "abs" => Self::Abs,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "round" => Self::Round,
+            "fract" => Self::Fract,
+            "sqrt" => Self::Sqrt,
+            "noise" => Self::Noise,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "clamp" => Self::Clamp,
+            "mix" => Self::Mix,
+            "if" => Self::If,
+            _ => return None,
+        };
+        Some(function)
+    }
+
+    fn arguments(&self) -> usize {
+        match self {
+            Self::Min | Self::Max => 2,
+            Self::Clamp | Self::Mix | Self::If => 3,
+            _ => 1,
+        }
+    }
+
+    fn apply(&self, args: &[f32]) -> f32 {
+        match self {
+            Self::Sin => args[0].sin(),
+            Self::Cos => args[0].cos(),
+            Self::Tan => args[0].tan(),
+            Self::Abs => args[0].abs(),
+            Self::Floor => args[0].floor(),
+            Self::Ceil => args[0].ceil(),
+            Self::Round => args[0].round(),
+            Self::Fract => args[0].rem_euclid(1.0),
+            Self::Sqrt => args[0].max(0.0).sqrt(),
+            Self::Noise => noise(args[0]),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            // Not using f32::clamp as it panics if the bounds are reversed.
+            Self::Clamp => args[0].max(args[1]).min(args[2]),
+            Self::Mix => args[0] + (args[1] - args[0]) * args[2],
+            Self::If => {
+                if args[0] > 0.0 {
+                    args[1]
+                } else {
+                    args[2]
+                }
+            }
+        }
+    }
+}
+
+/// A pseudo random number in the `[0, 1)` range that's stable for the same input.
+pub(crate) fn noise(value: f32) -> f32 {
+    ((value * 12.9898).sin() * 43_758.547).rem_euclid(1.0)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+impl BinaryOperator {
+    fn apply(&self, left: f32, right: f32) -> f32 {
+        let truth = |value: bool| if value { 1.0 } else { 0.0 };
+        match self {
+            Self::Add => left + right,
+            Self::Subtract => left - right,
+            Self::Multiply => left * right,
+            // Keep results finite so colors don't end up as NaN when dividing by a zero count.
+            Self::Divide if right == 0.0 => 0.0,
+            Self::Divide => left / right,
+            Self::Remainder if right == 0.0 => 0.0,
+            Self::Remainder => left.rem_euclid(right),
+            Self::Power => left.powf(right),
+            Self::Less => truth(left < right),
+            Self::LessEqual => truth(left <= right),
+            Self::Greater => truth(left > right),
+            Self::GreaterEqual => truth(left >= right),
+            Self::Equal => truth(left == right),
+            Self::NotEqual => truth(left != right),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Number(f32),
+    Variable(Variable),
+    Negate(Box<Node>),
+    Binary(BinaryOperator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+impl Node {
+    fn evaluate(&self, ctx: &AnimationContext) -> f32 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(variable) => variable.value(ctx),
+            Self::Negate(node) => -node.evaluate(ctx),
+            Self::Binary(operator, left, right) => operator.apply(left.evaluate(ctx), right.evaluate(ctx)),
+            Self::Call(function, args) => {
+                let args: Vec<_> = args.iter().map(|arg| arg.evaluate(ctx)).collect();
+                function.apply(&args)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Operator(&'static str),
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Identifier(name) => write!(f, "{name}"),
+            Self::Operator(operator) => write!(f, "{operator}"),
+            Self::OpenParen => write!(f, "("),
+            Self::CloseParen => write!(f, ")"),
+            Self::Comma => write!(f, ","),
+        }
+    }
+}
+
+const OPERATORS: &[&str] = &["<=", ">=", "==", "!=", "+", "-", "*", "/", "%", "^", "<", ">"];
+
+/// Split an expression into tokens, each along with the 1-based column it starts at.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ParseExpressionError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let column = index + 1;
+        let c = chars[index];
+        let token = match c {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            ',' => Token::Comma,
+            c if c.is_ascii_digit() || c == '.' => {
+                let end = chars[index..]
+                    .iter()
+                    .position(|c| !c.is_ascii_digit() && *c != '.')
+                    .map_or(chars.len(), |p| index + p);
+                let number: String = chars[index..end].iter().collect();
+                let value = number
+                    .parse()
+                    .map_err(|_| ParseExpressionError::new(column, ExpressionError::InvalidNumber(number.clone())))?;
+                index = end;
+                tokens.push((column, Token::Number(value)));
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let end = chars[index..]
+                    .iter()
+                    .position(|c| !c.is_ascii_alphanumeric() && *c != '_')
+                    .map_or(chars.len(), |p| index + p);
+                let name: String = chars[index..end].iter().collect();
+                index = end;
+                tokens.push((column, Token::Identifier(name)));
+                continue;
+            }
+            _ => {
+                let rest: String = chars[index..chars.len().min(index + 2)].iter().collect();
+                let operator = OPERATORS
+                    .iter()
+                    .find(|operator| rest.starts_with(**operator))
+                    .ok_or_else(|| ParseExpressionError::new(column, ExpressionError::UnexpectedCharacter(c)))?;
+                index += operator.len();
+                tokens.push((column, Token::Operator(operator)));
+                continue;
+            }
+        };
+        index += 1;
+        tokens.push((column, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    length: usize,
+}
+
+impl Parser {
+    fn parse_comparison(&mut self) -> Result<Node, ParseExpressionError> {
+        let left = self.parse_additive()?;
+        let operator = match self.peek_operator() {
+            Some("<") => BinaryOperator::Less,
+            Some("<=") => BinaryOperator::LessEqual,
+            Some(">") => BinaryOperator::Greater,
+            Some(">=") => BinaryOperator::GreaterEqual,
+            Some("==") => BinaryOperator::Equal,
+            Some("!=") => BinaryOperator::NotEqual,
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        let right = self.parse_additive()?;
+        Ok(Node::Binary(operator, left.into(), right.into()))
+    }
+
+    fn parse_additive(&mut self) -> Result<Node, ParseExpressionError> {
+        let mut node = self.parse_term()?;
+        loop {
+            let operator = match self.peek_operator() {
+                Some("+") => BinaryOperator::Add,
+                Some("-") => BinaryOperator::Subtract,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            node = Node::Binary(operator, node.into(), self.parse_term()?.into());
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Node, ParseExpressionError> {
+        let mut node = self.parse_unary()?;
+        loop {
+            let operator = match self.peek_operator() {
+                Some("*") => BinaryOperator::Multiply,
+                Some("/") => BinaryOperator::Divide,
+                Some("%") => BinaryOperator::Remainder,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            node = Node::Binary(operator, node.into(), self.parse_unary()?.into());
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Node, ParseExpressionError> {
+        if self.peek_operator() == Some("-") {
+            self.position += 1;
+            return Ok(Node::Negate(self.parse_unary()?.into()));
+        }
+        self.parse_power()
+    }
+
+    fn parse_power(&mut self) -> Result<Node, ParseExpressionError> {
+        let base = self.parse_primary()?;
+        if self.peek_operator() != Some("^") {
+            return Ok(base);
+        }
+        self.position += 1;
+        // Right associative, and binds tighter than a negation on its left: -2^2 is -4.
+        let exponent = self.parse_unary()?;
+        Ok(Node::Binary(BinaryOperator::Power, base.into(), exponent.into()))
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, ParseExpressionError> {
+        let (column, token) = self.next()?;
+        match token {
+            Token::Number(value) => Ok(Node::Number(value)),
+            Token::OpenParen => {
+                let node = self.parse_comparison()?;
+                self.expect(Token::CloseParen)?;
+                Ok(node)
+            }
+            Token::Identifier(name) if self.peek() == Some(&Token::OpenParen) => {
+                self.position += 1;
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| ParseExpressionError::new(column, ExpressionError::UnknownFunction(name.clone())))?;
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::CloseParen) {
+                    args.push(self.parse_comparison()?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.position += 1;
+                        args.push(self.parse_comparison()?);
+                    }
+                }
+                self.expect(Token::CloseParen)?;
+                if args.len() != function.arguments() {
+                    let error =
+                        ExpressionError::ArgumentCount { name, expected: function.arguments(), actual: args.len() };
+                    return Err(ParseExpressionError::new(column, error));
+                }
+                Ok(Node::Call(function, args))
+            }
+            Token::Identifier(name) => Variable::from_name(&name)
+                .map(Node::Variable)
+                .ok_or_else(|| ParseExpressionError::new(column, ExpressionError::UnknownVariable(name))),
+            token => Err(ParseExpressionError::new(column, ExpressionError::UnexpectedToken(token.to_string()))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn peek_operator(&self) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Operator(operator)) => Some(operator),
+            _ => None,
+        }
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), ParseExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| ParseExpressionError::new(self.length + 1, ExpressionError::UnexpectedEnd))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseExpressionError> {
+        let (column, token) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(ParseExpressionError::new(column, ExpressionError::UnexpectedToken(token.to_string())))
+        }
+    }
+}
+
+/// An error parsing an expression.
+#[derive(Debug, thiserror::Error, PartialEq)]
+#[error("{error} at column {column}")]
+pub(crate) struct ParseExpressionError {
+    column: usize,
+    error: ExpressionError,
+}
+
+impl ParseExpressionError {
+    fn new(column: usize, error: ExpressionError) -> Self {
+        Self { column, error }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub(crate) enum ExpressionError {
+    #[error("unexpected character '{0}'")]
+    UnexpectedCharacter(char),
+
+    #[error("unexpected '{0}'")]
+    UnexpectedToken(String),
+
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+
+    #[error("invalid number '{0}'")]
+    InvalidNumber(String),
+
+    #[error("unknown variable '{0}'")]
+    UnknownVariable(String),
+
+    #[error("unknown function '{0}'")]
+    UnknownFunction(String),
+
+    #[error("function '{name}' takes {expected} arguments, got {actual}")]
+    ArgumentCount { name: String, expected: usize, actual: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::animations::AnimationPalette;
+    use rstest::rstest;
+
+    fn evaluate(input: &str) -> f32 {
+        let palette = AnimationPalette::default();
+        let ctx = AnimationContext {
+            hue_offset: 180.0,
+            time: 1.5,
+            char_index: 3,
+            total_chars: 10,
+            row_index: 1,
+            total_rows: 4,
+            col_index: 5,
+            total_cols: 20,
+            ch: '#',
+            base_color: None,
+            palette: &palette,
+        };
+        let expression: Expression = input.parse().expect("invalid expression");
+        expression.evaluate(&ctx)
+    }
+
+    #[rstest]
+    #[case::number("42", 42.0)]
+    #[case::precedence("1 + 2 * 3", 7.0)]
+    #[case::parentheses("(1 + 2) * 3", 9.0)]
+    #[case::left_associative("10 - 4 - 3", 3.0)]
+    #[case::negate("-2 * 3", -6.0)]
+    #[case::power("2 ^ 3 ^ 2", 512.0)]
+    #[case::negated_power("-2 ^ 2", -4.0)]
+    #[case::remainder("-1 % 4", 3.0)]
+    #[case::divide_by_zero("1 / 0", 0.0)]
+    #[case::comparison("1 + 1 > 1", 1.0)]
+    #[case::variables("hue_offset + char_index * row_index", 183.0)]
+    #[case::progress("progress * total_cols", 10.0)]
+    #[case::time("time", 1.5)]
+    #[case::functions("max(1, min(5, 3)) + clamp(10, 0, 2)", 5.0)]
+    #[case::mix("mix(10, 20, 0.25)", 12.5)]
+    #[case::condition("if(col_index < 3, 1, 2)", 2.0)]
+    #[case::fract("fract(-0.25)", 0.75)]
+    fn evaluation(#[case] input: &str, #[case] expected: f32) {
+        assert_eq!(evaluate(input), expected);
+    }
+
+    #[rstest]
+    #[case::empty("", 1, ExpressionError::UnexpectedEnd)]
+    #[case::trailing_operator("1 +", 4, ExpressionError::UnexpectedEnd)]
+    #[case::unknown_variable("1 + foo", 5, ExpressionError::UnknownVariable("foo".into()))]
+    #[case::unknown_function("bar(1)", 1, ExpressionError::UnknownFunction("bar".into()))]
+    #[case::character("1 $ 2", 3, ExpressionError::UnexpectedCharacter('$'))]
+    #[case::unclosed("(1 + 2", 7, ExpressionError::UnexpectedEnd)]
+    #[case::leftover("1 2", 3, ExpressionError::UnexpectedToken("2".into()))]
+    #[case::number("1.2.3", 1, ExpressionError::InvalidNumber("1.2.3".into()))]
+    #[case::arguments(
+        "min(1)",
+        1,
+        ExpressionError::ArgumentCount { name: "min".into(), expected: 2, actual: 1 }
+    )]
+    fn invalid(#[case] input: &str, #[case] column: usize, #[case] error: ExpressionError) {
+        let result = input.parse::<Expression>();
+        assert_eq!(result, Err(ParseExpressionError::new(column, error)));
+    }
+
+    #[test]
+    fn deserialize_numbers() {
+        let expressions: Vec<Expression> = serde_yaml::from_str("[1, 2.5, '-3']").expect("invalid expressions");
+        assert_eq!(expressions, &[Expression::constant(1.0), Expression::constant(2.5), "-3".parse().unwrap()]);
+    }
+}
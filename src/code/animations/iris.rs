@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Iris animation - Lightness pulse expanding from center outward
+#[derive(Debug)]
 pub(crate) struct Iris;
 
 impl Animation for Iris {
@@ -12,6 +13,6 @@ impl Animation for Iris {
         let l = if dist <= radius { 60.0 } else { 35.0 };
         // Fixed hue rainbow mapping by index for variety
         let hue = (pos / ctx.total_chars as f32) * 360.0;
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, 100.0, l))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, 100.0, l))
     }
 }
@@ -1,7 +1,8 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 use super::matrix_chars;
 
 /// Matrix animation - Matrix-style digital rain with authentic green shades
+#[derive(Debug)]
 pub(crate) struct Matrix;
 
 impl Animation for Matrix {
@@ -9,6 +10,7 @@ impl Animation for Matrix {
         let hue = 120.0; // Green base
 
         // Create pseudo-random variation per character for authentic look
+        #[allow(clippy::approx_constant)]
         let char_seed = (ctx.char_index as f32 * 7.919 + ctx.row_index as f32 * 3.141).sin() * 100.0;
         let char_variation = char_seed.fract();
 
@@ -63,7 +65,7 @@ impl Animation for Matrix {
             Some(matrix_chars::get_char(char_seed + ctx.hue_offset * 0.5))
         };
 
-        let color = hsl_to_rgb(hue, saturation, lightness);
+        let color = ctx.palette.hsl(hue, saturation, lightness);
         if let Some(ch) = replacement {
             CharAnimationResult::with_replacement(color, ch)
         } else {
@@ -1,6 +1,9 @@
 mod common;
+mod custom;
+mod expression;
 mod glitch_chars;
 mod matrix_chars;
+mod text;
 
 // Individual animation modules
 mod aurora;
@@ -21,31 +24,35 @@ mod sepia;
 mod typewriter;
 mod wave;
 
-pub(crate) use common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb, rainbow_color};
-pub(crate) use glitch_chars::get_glitched_char;
-pub(crate) use matrix_chars::get_char as get_matrix_char;
+pub(crate) use common::{Animation, AnimationContext, AnimationPalette};
+pub(crate) use custom::{AnimationSpec, CustomAnimation};
+pub(crate) use text::AnimatedText;
 
 use crate::code::snippet::BannerAnimationStyle;
+use std::sync::Arc;
 
-/// Get the animation implementation for a given style
-pub(crate) fn get_animation(style: BannerAnimationStyle) -> Box<dyn Animation> {
-    match style {
-        BannerAnimationStyle::Rainbow => Box::new(rainbow::Rainbow),
-        BannerAnimationStyle::Flash => Box::new(flash::Flash),
-        BannerAnimationStyle::Wave => Box::new(wave::Wave),
-        BannerAnimationStyle::Iris => Box::new(iris::Iris),
-        BannerAnimationStyle::Plasma => Box::new(plasma::Plasma),
-        BannerAnimationStyle::Scanner => Box::new(scanner::Scanner),
-        BannerAnimationStyle::Matrix => Box::new(matrix::Matrix),
-        BannerAnimationStyle::Neon => Box::new(neon::Neon),
-        BannerAnimationStyle::Kaleidoscope => Box::new(kaleidoscope::Kaleidoscope),
-        BannerAnimationStyle::Sepia => Box::new(sepia::Sepia),
-        BannerAnimationStyle::Prism => Box::new(prism::Prism),
-        BannerAnimationStyle::Glitch => Box::new(glitch::Glitch),
-        BannerAnimationStyle::Breathe => Box::new(breathe::Breathe),
-        BannerAnimationStyle::Fire => Box::new(fire::Fire),
-        BannerAnimationStyle::Aurora => Box::new(aurora::Aurora),
-        BannerAnimationStyle::Crt => Box::new(crt::Crt),
-        BannerAnimationStyle::Typewriter => Box::new(typewriter::Typewriter),
-    }
+/// Get the animation implementation for a built in style. Custom styles are defined by the
+/// presentation so they can't be resolved here.
+pub(crate) fn get_animation(style: &BannerAnimationStyle) -> Option<Arc<dyn Animation>> {
+    let animation: Arc<dyn Animation> = match style {
+        BannerAnimationStyle::Rainbow => Arc::new(rainbow::Rainbow),
+        BannerAnimationStyle::Flash => Arc::new(flash::Flash),
+        BannerAnimationStyle::Wave => Arc::new(wave::Wave),
+        BannerAnimationStyle::Iris => Arc::new(iris::Iris),
+        BannerAnimationStyle::Plasma => Arc::new(plasma::Plasma),
+        BannerAnimationStyle::Scanner => Arc::new(scanner::Scanner),
+        BannerAnimationStyle::Matrix => Arc::new(matrix::Matrix),
+        BannerAnimationStyle::Neon => Arc::new(neon::Neon),
+        BannerAnimationStyle::Kaleidoscope => Arc::new(kaleidoscope::Kaleidoscope),
+        BannerAnimationStyle::Sepia => Arc::new(sepia::Sepia),
+        BannerAnimationStyle::Prism => Arc::new(prism::Prism),
+        BannerAnimationStyle::Glitch => Arc::new(glitch::Glitch),
+        BannerAnimationStyle::Breathe => Arc::new(breathe::Breathe),
+        BannerAnimationStyle::Fire => Arc::new(fire::Fire),
+        BannerAnimationStyle::Aurora => Arc::new(aurora::Aurora),
+        BannerAnimationStyle::Crt => Arc::new(crt::Crt),
+        BannerAnimationStyle::Typewriter => Arc::new(typewriter::Typewriter),
+        BannerAnimationStyle::Custom(_) => return None,
+    };
+    Some(animation)
 }
@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Neon animation - Bright neon sign colors cycling through classic neon palette
+#[derive(Debug)]
 pub(crate) struct Neon;
 
 impl Animation for Neon {
@@ -23,6 +24,6 @@ impl Animation for Neon {
         let pulse = (ctx.hue_offset * 0.1).sin() * 5.0;
         let lightness = base_lightness + pulse;
 
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, 100.0, lightness))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, 100.0, lightness))
     }
 }
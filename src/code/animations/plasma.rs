@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Plasma animation - Psychedelic plasma effect with overlapping sine waves
+#[derive(Debug)]
 pub(crate) struct Plasma;
 
 impl Animation for Plasma {
@@ -15,6 +16,6 @@ impl Animation for Plasma {
 
         // Average the waves and map from -1..1 to 0..360
         let hue = ((wave1 + wave2 + wave3) / 3.0 + 1.0) * 180.0;
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, 100.0, 50.0))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, 100.0, 50.0))
     }
 }
@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Prism animation - Spectrum split and refraction effect like light through a prism
+#[derive(Debug)]
 pub(crate) struct Prism;
 
 impl Animation for Prism {
@@ -18,6 +19,6 @@ impl Animation for Prism {
         let dist_from_center = (beam_pos - beam_center).abs();
         let lightness = 55.0 - (dist_from_center / beam_center) * 10.0;
 
-        CharAnimationResult::with_color(hsl_to_rgb(hue % 360.0, 100.0, lightness))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue % 360.0, 100.0, lightness))
     }
 }
@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Scanner animation - Horizontal scan line effect like KITT from Knight Rider
+#[derive(Debug)]
 pub(crate) struct Scanner;
 
 impl Animation for Scanner {
@@ -9,6 +10,6 @@ impl Animation for Scanner {
         let dist = (ctx.char_index as f32 - scan_pos).abs();
         let lightness = (70.0 - (dist * 8.0)).max(30.0);
         // Classic red scanner
-        CharAnimationResult::with_color(hsl_to_rgb(0.0, 100.0, lightness))
+        CharAnimationResult::with_color(ctx.palette.hsl(0.0, 100.0, lightness))
     }
 }
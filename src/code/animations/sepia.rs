@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Sepia animation - Subdued vintage monochrome sepia tone with gentle wave
+#[derive(Debug)]
 pub(crate) struct Sepia;
 
 impl Animation for Sepia {
@@ -8,6 +9,6 @@ impl Animation for Sepia {
         let hue = 30.0; // Warm brown/sepia tone
         let saturation = 45.0; // Desaturated vintage look
         let lightness = 35.0 + 15.0 * (ctx.char_index as f32 * 0.15 + ctx.hue_offset * 0.05).sin();
-        CharAnimationResult::with_color(hsl_to_rgb(hue, saturation, lightness))
+        CharAnimationResult::with_color(ctx.palette.hsl(hue, saturation, lightness))
     }
 }
@@ -0,0 +1,234 @@
+use super::{Animation, AnimationContext, AnimationPalette};
+use crate::{
+    code::banner::{AnimationState, RainbowAnimationPollable, animation_frame_times, animation_timeline_kind},
+    markdown::{elements::Text, text::WeightedLine, text_style::Color},
+    render::{
+        operation::{
+            AsRenderOperations, BlockLine, Pollable, RenderAsync, RenderOperation, RenderTimeline, TimelineKind,
+        },
+        properties::WindowSize,
+    },
+};
+use std::{
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Animates the text in ordinary slide content, like a heading, a paragraph or a list item.
+///
+/// The animation plays once when the content is shown. Once it's done, the content is rendered
+/// the way it would have been without it.
+#[derive(Clone, Debug)]
+pub(crate) struct AnimatedText {
+    operations: Vec<RenderOperation>,
+    animation: Arc<dyn Animation>,
+    palette: AnimationPalette,
+    default_color: Option<Color>,
+    duration: Duration,
+    state: Arc<Mutex<AnimationState>>,
+}
+
+impl AnimatedText {
+    /// Construct a new animated text.
+    ///
+    /// The default color is used as the color of any text that doesn't set one explicitly.
+    pub(crate) fn new(
+        operations: Vec<RenderOperation>,
+        animation: Arc<dyn Animation>,
+        palette: AnimationPalette,
+        default_color: Option<Color>,
+        duration_millis: u64,
+    ) -> Self {
+        Self {
+            operations,
+            animation,
+            palette,
+            default_color,
+            duration: Duration::from_millis(duration_millis.max(1)),
+            state: Default::default(),
+        }
+    }
+
+    /// Render the content as if the animation had already played.
+    pub(crate) fn freeze(&self) {
+        self.state.lock().unwrap().freeze();
+    }
+
+    fn line(operation: &RenderOperation) -> Option<&WeightedLine> {
+        match operation {
+            RenderOperation::RenderText { line, .. } => Some(line),
+            RenderOperation::RenderBlockLine(BlockLine { text, .. }) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn with_line(operation: &RenderOperation, line: WeightedLine) -> RenderOperation {
+        match operation {
+            RenderOperation::RenderText { alignment, .. } => {
+                RenderOperation::RenderText { line, alignment: *alignment }
+            }
+            RenderOperation::RenderBlockLine(block) => {
+                RenderOperation::RenderBlockLine(BlockLine { text: line, ..block.clone() })
+            }
+            other => other.clone(),
+        }
+    }
+
+    fn render_with_offset(&self, hue_offset: f32) -> Vec<RenderOperation> {
+        let lines = self.operations.iter().filter_map(Self::line);
+        let total_rows = lines.clone().count();
+        let total_chars = lines
+            .flat_map(WeightedLine::iter_texts)
+            .flat_map(|text| text.text().content.chars())
+            .filter(|c| !c.is_whitespace())
+            .count();
+        let time = hue_offset / 360.0 * self.duration.as_secs_f32();
+
+        let mut operations = Vec::new();
+        let mut char_index = 0;
+        let mut row_index = 0;
+        for operation in &self.operations {
+            let Some(line) = Self::line(operation) else {
+                operations.push(operation.clone());
+                continue;
+            };
+            let total_cols = line.iter_texts().map(|text| text.text().content.chars().count()).sum();
+            let mut texts = Vec::new();
+            let mut col_index = 0;
+            for text in line.iter_texts() {
+                let text = text.text();
+                for ch in text.content.chars() {
+                    let ctx = AnimationContext {
+                        hue_offset,
+                        time,
+                        char_index,
+                        total_chars,
+                        row_index,
+                        total_rows,
+                        col_index,
+                        total_cols,
+                        ch,
+                        base_color: text.style.colors.foreground.or(self.default_color),
+                        palette: &self.palette,
+                    };
+                    let mut style = text.style;
+                    let mut display_char = ch;
+                    if ch.is_whitespace() {
+                        if let Some(color) = self.animation.render_whitespace(&ctx) {
+                            style = style.bg_color(color);
+                        }
+                    } else {
+                        let result = self.animation.render_char(&ctx);
+                        style = style.fg_color(result.color);
+                        if let Some(color) = result.bg_color {
+                            style = style.bg_color(color);
+                        }
+                        display_char = result.replacement_char.unwrap_or(ch);
+                        char_index += 1;
+                    }
+                    col_index += 1;
+                    texts.push(Text::new(display_char, style));
+                }
+            }
+            operations.push(Self::with_line(operation, texts.into()));
+            row_index += 1;
+        }
+        operations
+    }
+}
+
+impl AsRenderOperations for AnimatedText {
+    fn as_render_operations(&self, _: &WindowSize) -> Vec<RenderOperation> {
+        let state = self.state.lock().unwrap();
+        if state.completed { self.operations.clone() } else { self.render_with_offset(state.hue_offset) }
+    }
+}
+
+impl RenderAsync for AnimatedText {
+    fn pollable(&self) -> Box<dyn Pollable> {
+        Box::new(RainbowAnimationPollable::new(self.state.clone(), false, self.duration))
+    }
+
+    fn timeline(&self) -> Option<Rc<dyn RenderTimeline>> {
+        Some(Rc::new(self.clone()))
+    }
+}
+
+impl RenderTimeline for AnimatedText {
+    fn frame_times(&self) -> Vec<f64> {
+        animation_frame_times(self.duration, false)
+    }
+
+    fn seek_to(&self, time: f64) {
+        self.state.lock().unwrap().seek(time, self.duration, false);
+    }
+
+    fn kind(&self) -> TimelineKind {
+        animation_timeline_kind(self.duration, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        code::{animations::get_animation, snippet::BannerAnimationStyle},
+        markdown::text_style::TextStyle,
+        theme::Alignment,
+    };
+
+    fn render(animation: &AnimatedText, hue_offset: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        for operation in animation.render_with_offset(hue_offset) {
+            if let Some(line) = AnimatedText::line(&operation) {
+                lines.push(line.iter_texts().map(|text| text.text().content.as_str()).collect());
+            }
+        }
+        lines
+    }
+
+    fn typewriter(style: TextStyle) -> AnimatedText {
+        let operations = vec![
+            RenderOperation::RenderText {
+                line: vec![Text::new("ab cd", style)].into(),
+                alignment: Alignment::default(),
+            },
+            RenderOperation::RenderLineBreak,
+        ];
+        let animation = get_animation(&BannerAnimationStyle::Typewriter).unwrap();
+        AnimatedText::new(operations, animation, Default::default(), Some(Color::new(1, 2, 3)), 1000)
+    }
+
+    #[test]
+    fn typewriter_reveal() {
+        let animation = typewriter(TextStyle::default());
+        assert_eq!(render(&animation, 0.0), &["▌    "]);
+        assert_eq!(render(&animation, 160.0), &["ab ▌ "]);
+        assert_eq!(render(&animation, 360.0), &["ab cd"]);
+    }
+
+    #[test]
+    fn keeps_text_colors() {
+        let colored = Color::new(4, 5, 6);
+        let animation = typewriter(TextStyle::default().fg_color(colored));
+        let operations = animation.render_with_offset(360.0);
+        let RenderOperation::RenderText { line, .. } = &operations[0] else { panic!("not text") };
+        let colors: Vec<_> = line.iter_texts().map(|text| text.text().style.colors.foreground).collect();
+        assert_eq!(colors, &[Some(colored)]);
+
+        let animation = typewriter(TextStyle::default());
+        let operations = animation.render_with_offset(360.0);
+        let RenderOperation::RenderText { line, .. } = &operations[0] else { panic!("not text") };
+        let colors: Vec<_> = line.iter_texts().map(|text| text.text().style.colors.foreground).collect();
+        assert_eq!(colors, &[Some(Color::new(1, 2, 3)), None, Some(Color::new(1, 2, 3))]);
+    }
+
+    #[test]
+    fn original_once_completed() {
+        let animation = typewriter(TextStyle::default());
+        animation.state.lock().unwrap().completed = true;
+        let operations = animation.as_render_operations(&WindowSize { rows: 1, columns: 1, height: 1, width: 1 });
+        assert!(matches!(&operations[0], RenderOperation::RenderText { line, .. } if line.width() == 5));
+    }
+}
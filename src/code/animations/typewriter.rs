@@ -1,8 +1,17 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
+use crate::markdown::text_style::Color;
 
 /// Typewriter animation - Gradual left-to-right reveal effect
+#[derive(Debug)]
 pub(crate) struct Typewriter;
 
+impl Typewriter {
+    /// Revealed characters keep their own color, if they have one.
+    fn ink(ctx: &AnimationContext) -> Color {
+        ctx.base_color.unwrap_or_else(|| ctx.palette.hsl(40.0, 20.0, 85.0))
+    }
+}
+
 impl Animation for Typewriter {
     fn render_char(&self, ctx: &AnimationContext) -> CharAnimationResult {
         // Use a longer duration to ensure we have time to reveal all characters
@@ -13,8 +22,7 @@ impl Animation for Typewriter {
 
         if ctx.hue_offset > total_duration {
             // Animation fully complete: all text revealed, no caret
-            let color = hsl_to_rgb(40.0, 20.0, 85.0);
-            return CharAnimationResult::with_color(color);
+            return CharAnimationResult::with_color(Self::ink(ctx));
         }
 
         let progress = (ctx.hue_offset / typing_duration).clamp(0.0, 1.0);
@@ -23,20 +31,18 @@ impl Animation for Typewriter {
 
         if ctx.char_index < reveal_count {
             // Already revealed: warm white ink
-            let color = hsl_to_rgb(40.0, 20.0, 85.0);
-            CharAnimationResult::with_color(color)
+            CharAnimationResult::with_color(Self::ink(ctx))
         } else if ctx.char_index == reveal_count && reveal_count < ctx.total_chars {
             // Currently being typed: show with caret (only if not at end)
-            let color = hsl_to_rgb(200.0, 85.0, 65.0);
+            let color = ctx.palette.hsl(200.0, 85.0, 65.0);
             // Draw a block caret instead of the ASCII glyph to emphasize typing
             CharAnimationResult::with_replacement(color, '▌')
         } else if reveal_count >= ctx.total_chars {
             // All characters revealed but still in settling time
-            let color = hsl_to_rgb(40.0, 20.0, 85.0);
-            CharAnimationResult::with_color(color)
+            CharAnimationResult::with_color(Self::ink(ctx))
         } else {
             // Not yet revealed: render as space (no ink)
-            let color = hsl_to_rgb(0.0, 0.0, 0.0);
+            let color = ctx.palette.hsl(0.0, 0.0, 0.0);
             CharAnimationResult::with_replacement(color, ' ')
         }
     }
@@ -1,6 +1,7 @@
-use super::common::{Animation, AnimationContext, CharAnimationResult, hsl_to_rgb};
+use super::common::{Animation, AnimationContext, CharAnimationResult};
 
 /// Wave animation - Hue oscillates along a sine wave with character position
+#[derive(Debug)]
 pub(crate) struct Wave;
 
 impl Animation for Wave {
@@ -10,6 +11,6 @@ impl Animation for Wave {
         let freq = 0.35; // chars per cycle
         let phase = ctx.hue_offset.to_radians();
         let hue = base + amplitude * ((ctx.char_index as f32 * freq + phase).sin());
-        CharAnimationResult::with_color(hsl_to_rgb(((hue % 360.0) + 360.0) % 360.0, 100.0, 50.0))
+        CharAnimationResult::with_color(ctx.palette.hsl(((hue % 360.0) + 360.0) % 360.0, 100.0, 50.0))
     }
 }
@@ -0,0 +1,1072 @@
+use crate::code::snippet::PosterFrame;
+use crate::markdown::elements::Text;
+use crate::markdown::text::{WeightedLine, WeightedText};
+use crate::render::operation::{
+    AsRenderOperations, BlockLine, PlaybackCommand, PlaybackControl, Pollable, PollableState, RenderAsync,
+    RenderAsyncStartPolicy, RenderOperation, RenderTimeline, TimelineKind,
+};
+use crate::render::properties::WindowSize;
+use crate::theme::AsciinemaStyle;
+use playback::ScreenPlayback;
+use serde::Deserialize;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+mod playback;
+pub(crate) mod record;
+mod screen;
+
+/// A single frame event in an asciinema recording
+#[derive(Debug, Clone, Deserialize)]
+struct CastEvent {
+    /// Timestamp in seconds
+    #[serde(rename = "0")]
+    time: f64,
+    /// Event type (usually "o" for output)
+    #[serde(rename = "1")]
+    event_type: String,
+    /// Terminal output data
+    #[serde(rename = "2")]
+    data: String,
+}
+
+impl CastEvent {
+    fn is_output(&self) -> bool {
+        self.event_type == "o"
+    }
+
+    fn is_marker(&self) -> bool {
+        self.event_type == "m"
+    }
+}
+
+/// Asciinema cast file header
+#[derive(Debug, Deserialize)]
+struct CastHeader {
+    version: u32,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    timestamp: Option<u64>,
+    #[serde(default)]
+    idle_time_limit: Option<f64>,
+    /// The terminal information, only present in version 3 recordings
+    #[serde(default)]
+    term: Option<CastTerminal>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+/// The terminal a version 3 recording was made in
+#[derive(Debug, Deserialize)]
+struct CastTerminal {
+    cols: u32,
+    rows: u32,
+}
+
+/// A parsed asciinema recording
+#[derive(Debug, Clone)]
+pub(crate) struct AsciinemaRecording {
+    /// Recording events
+    events: Vec<CastEvent>,
+    /// Terminal width from header
+    width: u32,
+    /// Terminal height from header
+    height: u32,
+    /// The maximum time between events, as specified in the header
+    idle_time_limit: Option<f64>,
+    /// The title of the recording, as specified in the header
+    title: Option<String>,
+}
+
+/// Errors that can occur when parsing asciinema recordings
+#[derive(thiserror::Error, Debug)]
+pub enum AsciinemaError {
+    #[error("failed to parse cast file: {0}")]
+    ParseError(String),
+
+    #[error("invalid cast file format: {0}")]
+    InvalidFormat(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl AsciinemaRecording {
+    /// Parse an asciinema cast file from its contents
+    ///
+    /// Both version 2 recordings, where event times are absolute, and version 3 recordings, where
+    /// they're relative to the previous event, are supported.
+    pub(crate) fn from_cast(content: &str) -> Result<Self, AsciinemaError> {
+        // Version 3 allows comment lines anywhere in the file
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+
+        // First line should be the header
+        let (_, header_line) = lines.next()
+            .ok_or_else(|| AsciinemaError::InvalidFormat("empty cast file".to_string()))?;
+
+        let header: CastHeader = serde_json::from_str(header_line)
+            .map_err(|e| AsciinemaError::ParseError(format!("invalid header: {}", e)))?;
+
+        let relative_times = match header.version {
+            2 => false,
+            3 => true,
+            version => {
+                return Err(AsciinemaError::InvalidFormat(format!("unsupported cast version: {version}")));
+            }
+        };
+
+        let (width, height) = match &header.term {
+            Some(term) => (term.cols, term.rows),
+            None => (header.width.unwrap_or(80), header.height.unwrap_or(24)),
+        };
+
+        // Parse events
+        let mut events = Vec::new();
+        let mut time = 0.0;
+        for (idx, line) in lines {
+            let mut event: CastEvent = serde_json::from_str(line)
+                .map_err(|e| AsciinemaError::ParseError(format!("line {}: {}", idx + 1, e)))?;
+            if relative_times {
+                time += event.time;
+                event.time = time;
+            }
+
+            // Only process output and marker events
+            if event.is_output() || event.is_marker() {
+                events.push(event);
+            }
+        }
+
+        Ok(Self {
+            events,
+            width,
+            height,
+            idle_time_limit: header.idle_time_limit,
+            title: header.title,
+        })
+    }
+
+    /// The title of the recording, if any.
+    pub(crate) fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The idle time limit the recording was made with, if any.
+    pub(crate) fn idle_time_limit(&self) -> Option<f64> {
+        self.idle_time_limit
+    }
+
+    /// Cap the time between consecutive events to the given number of seconds.
+    pub(crate) fn limit_idle_time(&mut self, limit: f64) {
+        let mut previous_time = 0.0;
+        let mut shift = 0.0;
+        for event in &mut self.events {
+            let gap = event.time - previous_time;
+            previous_time = event.time;
+            shift += (gap - limit).max(0.0);
+            event.time -= shift;
+        }
+    }
+
+    /// Only keep the part of the recording between the given timestamps.
+    ///
+    /// Output that happens before the start of the range is kept but applied at the very beginning
+    /// so the screen looks the way it did at that point.
+    pub(crate) fn trim(&mut self, start: f64, end: Option<f64>) {
+        let end = end.unwrap_or(f64::INFINITY);
+        self.events.retain(|event| event.time <= end && (event.is_output() || event.time > start));
+        for event in &mut self.events {
+            event.time = (event.time - start).max(0.0);
+        }
+    }
+
+    /// The timestamps of the markers in this recording.
+    pub(crate) fn markers(&self) -> Vec<f64> {
+        self.events.iter().filter(|event| event.is_marker() && event.time > 0.0).map(|event| event.time).collect()
+    }
+
+    /// Get the total duration of the recording in seconds
+    pub(crate) fn duration(&self) -> f64 {
+        self.events.last().map(|e| e.time).unwrap_or(0.0)
+    }
+
+    /// Get the terminal width
+    pub(crate) fn width(&self) -> u32 {
+        self.width
+    }
+}
+
+/// The speeds the playback can be moved through using the speed controls.
+const SPEED_STEPS: &[f64] = &[0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 2.0, 3.0, 4.0];
+
+/// The length of the windows output is grouped into when a recording is exported as a sequence of
+/// frames. Each window results in a single frame.
+const EXPORT_FRAME_INTERVAL_SECONDS: f64 = 0.1;
+
+/// The options that control how a recording is played back.
+#[derive(Clone, Debug)]
+pub(crate) struct PlaybackOptions {
+    /// Whether to loop the recording
+    pub(crate) loop_playback: bool,
+    /// Speed multiplier (1.0 = normal speed)
+    pub(crate) speed: f64,
+    /// Start policy for playback
+    pub(crate) start_policy: RenderAsyncStartPolicy,
+    /// Whether to start paused, waiting for the presentation to move forward
+    pub(crate) wait: bool,
+    /// Whether to show the playback progress under the frame
+    pub(crate) show_progress: bool,
+}
+
+/// Asciinema player that renders recording frames
+#[derive(Clone, Debug)]
+pub(crate) struct AsciinemaPlayer {
+    /// The recording to play
+    recording: Arc<AsciinemaRecording>,
+    /// The style of the frame around the recording
+    style: AsciinemaStyle,
+    /// Font size
+    font_size: u8,
+    /// Playback state
+    state: Arc<Mutex<PlaybackState>>,
+    /// Whether to loop the animation
+    loop_playback: bool,
+    /// Whether to show the playback progress under the frame
+    show_progress: bool,
+    /// Start policy for playback
+    start_policy: RenderAsyncStartPolicy,
+    /// Whether playback waits for the presentation to move forward before starting
+    wait: bool,
+    /// The points in time where playback stops until the presentation moves forward
+    markers: Vec<f64>,
+}
+
+/// Keeps track of the position within a recording while it's being played.
+#[derive(Debug)]
+struct PlaybackClock {
+    /// The recording time at the point the clock was last anchored.
+    offset: f64,
+    /// The instant the clock was last anchored at, if it's running.
+    anchor: Option<Instant>,
+    /// Speed multiplier (1.0 = normal speed)
+    speed: f64,
+}
+
+impl PlaybackClock {
+    fn new(speed: f64) -> Self {
+        Self { offset: 0.0, anchor: None, speed }
+    }
+
+    fn time(&self) -> f64 {
+        match self.anchor {
+            Some(anchor) => self.offset + anchor.elapsed().as_secs_f64() * self.speed,
+            None => self.offset,
+        }
+    }
+
+    fn start(&mut self) {
+        if self.anchor.is_none() {
+            self.anchor = Some(Instant::now());
+        }
+    }
+
+    fn stop(&mut self) {
+        self.offset = self.time();
+        self.anchor = None;
+    }
+
+    fn set_time(&mut self, time: f64) {
+        self.offset = time;
+        if self.anchor.is_some() {
+            self.anchor = Some(Instant::now());
+        }
+    }
+
+    fn set_speed(&mut self, speed: f64) {
+        self.set_time(self.time());
+        self.speed = speed;
+    }
+}
+
+#[derive(Debug)]
+struct PlaybackState {
+    /// The clock that drives the playback
+    clock: PlaybackClock,
+    /// Current playback time in seconds
+    current_time: f64,
+    /// The screen contents at the current playback time
+    playback: ScreenPlayback,
+    /// Whether the playback has been polled at least once
+    started: bool,
+    /// Whether the playback is paused, either because it's waiting to be started or explicitly
+    paused: bool,
+    /// Whether playback has completed
+    completed: bool,
+    /// The time at which playback will pause next
+    stop_at: Option<f64>,
+}
+
+impl PlaybackState {
+    /// Move the playback to the given time, returning whether the screen contents changed.
+    fn set_time(&mut self, time: f64) -> bool {
+        self.current_time = time;
+        self.playback.seek(time)
+    }
+
+    /// Move both the clock and the playback to the given time.
+    fn jump(&mut self, time: f64) {
+        self.clock.set_time(time);
+        self.set_time(time);
+    }
+
+    fn resume(&mut self) {
+        self.paused = false;
+        if self.started {
+            self.clock.start();
+        }
+    }
+
+    fn pause_at(&mut self, time: f64) {
+        self.paused = true;
+        self.clock.stop();
+        self.jump(time);
+    }
+}
+
+impl AsciinemaPlayer {
+    pub(crate) fn new(
+        recording: AsciinemaRecording,
+        style: AsciinemaStyle,
+        font_size: u8,
+        options: PlaybackOptions,
+    ) -> Self {
+        let markers = recording.markers();
+        let recording = Arc::new(recording);
+        let mut playback = ScreenPlayback::new(recording.clone());
+        playback.seek(0.0);
+        Self {
+            recording: recording.clone(),
+            style,
+            font_size,
+            state: Arc::new(Mutex::new(PlaybackState {
+                // Minimum speed to avoid playback never advancing
+                clock: PlaybackClock::new(options.speed.max(0.1)),
+                current_time: 0.0,
+                playback,
+                started: false,
+                paused: options.wait,
+                completed: false,
+                stop_at: markers.first().copied(),
+            })),
+            loop_playback: options.loop_playback,
+            show_progress: options.show_progress,
+            start_policy: options.start_policy,
+            wait: options.wait,
+            markers,
+        }
+    }
+
+    /// The number of times the presentation needs to move forward to play the entire recording.
+    pub(crate) fn steps(&self) -> usize {
+        self.markers.len() + self.wait as usize
+    }
+
+    /// Play the part of the recording that the given step, starting at 1, reveals.
+    fn play_step(&self, step: usize) {
+        let segment = self.step_segment(step - 1);
+        let mut state = self.state.lock().unwrap();
+        let start = self.segment_start(segment);
+        if state.current_time < start {
+            state.jump(start);
+        }
+        state.stop_at = self.markers.get(segment).copied();
+        state.completed = false;
+        state.resume();
+    }
+
+    /// Pause the recording at the point it would be at right after the given step was played.
+    fn rewind_to_step(&self, step: usize) {
+        let segment = self.step_segment(step);
+        let mut state = self.state.lock().unwrap();
+        state.pause_at(self.segment_start(segment));
+        state.stop_at = self.markers.get(segment).copied();
+        state.completed = false;
+    }
+
+    /// Go back to the beginning of the recording, as if it had never been played.
+    fn restart(&self) {
+        let mut state = self.state.lock().unwrap();
+        if self.wait {
+            state.pause_at(0.0);
+        } else {
+            state.jump(0.0);
+            state.resume();
+        }
+        state.stop_at = self.markers.first().copied();
+        state.completed = false;
+    }
+
+    /// Freeze the recording at the given frame, for when it can't be played back.
+    pub(crate) fn freeze(&self, poster: PosterFrame) {
+        let duration = self.recording.duration();
+        let time = match poster {
+            PosterFrame::Time(time) => time.min(duration),
+            PosterFrame::End => duration,
+        };
+        let mut state = self.state.lock().unwrap();
+        state.pause_at(time);
+        state.stop_at = None;
+        state.started = true;
+        state.completed = true;
+    }
+
+    /// Skip to the end of the recording.
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.clock.stop();
+        state.jump(self.recording.duration());
+        state.stop_at = None;
+        state.completed = true;
+    }
+
+    /// The segment, delimited by markers, that plays after the given step.
+    fn step_segment(&self, step: usize) -> usize {
+        // Recordings that don't wait to be started play their first segment right away.
+        step + !self.wait as usize
+    }
+
+    fn segment_start(&self, segment: usize) -> f64 {
+        match segment {
+            0 => 0.0,
+            segment => self.markers[segment - 1],
+        }
+    }
+
+    fn render_frame(&self, state: &PlaybackState, window_size: &WindowSize) -> Vec<RenderOperation> {
+        let border_style = self.style.border_style.size(self.font_size);
+        let content_style = self.style.style.size(self.font_size);
+        let screen = state.playback.screen();
+        let padding = self.style.padding;
+        let horizontal_padding = " ".repeat(padding.horizontal as usize);
+
+        // Everything around the recording itself, plus some minimal margin, has to fit in the window
+        let border_width = if self.style.border.is_some() { 2 } else { 0 };
+        let chrome_width = border_width + padding.horizontal as usize * 2;
+        let available_width = (window_size.columns / self.font_size as u16) as usize;
+        let available_width = available_width.saturating_sub(chrome_width + 2);
+        let frame_width = (self.recording.width() as usize).min(available_width);
+        let inner_width = frame_width + padding.horizontal as usize * 2;
+        let total_width = inner_width + border_width;
+
+        let mut operations = Vec::new();
+        if let Some(border) = &self.style.border {
+            let line = Self::border_line(border.top_left, border.horizontal, border.top_right, inner_width);
+            self.push_frame_line(&mut operations, total_width, vec![Text::new(line, border_style)]);
+        }
+        if let (Some(title_style), Some(title)) = (&self.style.title_bar_style, self.recording.title()) {
+            let title: String = format!(" {title}").chars().take(inner_width).collect();
+            let title = Text::new(format!("{title:inner_width$}"), title_style.size(self.font_size));
+            self.push_bordered_line(&mut operations, total_width, vec![title]);
+            if let Some(border) = &self.style.border {
+                let line =
+                    Self::border_line(border.left_junction, border.horizontal, border.right_junction, inner_width);
+                self.push_frame_line(&mut operations, total_width, vec![Text::new(line, border_style)]);
+            }
+        }
+
+        let blank_line = || vec![Text::new(" ".repeat(inner_width), content_style)];
+        for _ in 0..padding.vertical {
+            self.push_bordered_line(&mut operations, total_width, blank_line());
+        }
+        for row in 0..screen.height() {
+            let mut texts = vec![Text::new(horizontal_padding.clone(), content_style)];
+            for mut text in screen.row_texts(row, frame_width) {
+                // The theme's colors are used wherever the recording doesn't set its own.
+                text.style = text.style.merged(&self.style.style).size(self.font_size);
+                texts.push(text);
+            }
+            texts.push(Text::new(horizontal_padding.clone(), content_style));
+            self.push_bordered_line(&mut operations, total_width, texts);
+        }
+        for _ in 0..padding.vertical {
+            self.push_bordered_line(&mut operations, total_width, blank_line());
+        }
+
+        if let Some(border) = &self.style.border {
+            let line = Self::border_line(border.bottom_left, border.horizontal, border.bottom_right, inner_width);
+            self.push_frame_line(&mut operations, total_width, vec![Text::new(line, border_style)]);
+        }
+
+        if self.show_progress {
+            let progress = ProgressLine {
+                time: state.current_time,
+                duration: self.recording.duration(),
+                speed: state.clock.speed,
+                paused: state.paused,
+            };
+            let progress = Text::new(progress.render(total_width), border_style);
+            self.push_frame_line(&mut operations, total_width, vec![progress]);
+        }
+        operations
+    }
+
+    fn border_line(left: char, middle: char, right: char, width: usize) -> String {
+        let middle = middle.to_string().repeat(width);
+        format!("{left}{middle}{right}")
+    }
+
+    /// Push a line surrounded by the sides of the border, if there's one.
+    fn push_bordered_line(&self, operations: &mut Vec<RenderOperation>, width: usize, mut texts: Vec<Text>) {
+        if let Some(border) = &self.style.border {
+            let side = Text::new(border.vertical, self.style.border_style.size(self.font_size));
+            texts.insert(0, side.clone());
+            texts.push(side);
+        }
+        self.push_frame_line(operations, width, texts);
+    }
+
+    fn push_frame_line(&self, operations: &mut Vec<RenderOperation>, width: usize, texts: Vec<Text>) {
+        operations.push(RenderOperation::RenderBlockLine(BlockLine {
+            prefix: WeightedText::from(""),
+            right_padding_length: 0,
+            repeat_prefix_on_wrap: false,
+            text: WeightedLine::from(texts),
+            block_length: width as u16,
+            alignment: self.style.alignment,
+            block_color: None,
+        }));
+        operations.push(RenderOperation::RenderLineBreak);
+    }
+}
+
+impl AsRenderOperations for AsciinemaPlayer {
+    fn as_render_operations(&self, window_size: &WindowSize) -> Vec<RenderOperation> {
+        let state = self.state.lock().unwrap();
+        self.render_frame(&state, window_size)
+    }
+}
+
+impl RenderAsync for AsciinemaPlayer {
+    fn pollable(&self) -> Box<dyn Pollable> {
+        Box::new(AsciinemaPlaybackPollable {
+            state: self.state.clone(),
+            duration: self.recording.duration(),
+            loop_playback: self.loop_playback,
+            show_progress: self.show_progress,
+            last_second: None,
+        })
+    }
+
+    fn start_policy(&self) -> RenderAsyncStartPolicy {
+        self.start_policy
+    }
+
+    fn control_playback(&self, command: PlaybackCommand) -> PlaybackControl {
+        let mut state = self.state.lock().unwrap();
+        let duration = self.recording.duration();
+        match command {
+            PlaybackCommand::TogglePause if state.completed => {
+                // Toggling a finished recording plays it again from the start.
+                state.completed = false;
+                state.jump(0.0);
+                state.resume();
+                return PlaybackControl::Restart;
+            }
+            PlaybackCommand::TogglePause if state.paused => state.resume(),
+            PlaybackCommand::TogglePause => {
+                let time = state.clock.time();
+                state.pause_at(time);
+            }
+            PlaybackCommand::Seek(delta) => {
+                let time = (state.clock.time() + delta).clamp(0.0, duration);
+                state.jump(time);
+                // Seeking past the next marker means it's been skipped.
+                if state.stop_at.is_some_and(|stop_at| time >= stop_at) {
+                    state.stop_at = None;
+                }
+                if state.completed && time < duration {
+                    state.completed = false;
+                    if !state.paused {
+                        state.clock.start();
+                    }
+                    return PlaybackControl::Restart;
+                }
+            }
+            PlaybackCommand::SpeedUp => {
+                let speed = state.clock.speed;
+                let speed = SPEED_STEPS.iter().copied().find(|step| *step > speed).unwrap_or(speed);
+                state.clock.set_speed(speed);
+            }
+            PlaybackCommand::SlowDown => {
+                let speed = state.clock.speed;
+                let speed = SPEED_STEPS.iter().copied().rev().find(|step| *step < speed).unwrap_or(speed);
+                state.clock.set_speed(speed);
+            }
+        };
+        PlaybackControl::Applied
+    }
+
+    fn timeline(&self) -> Option<Rc<dyn RenderTimeline>> {
+        Some(Rc::new(self.clone()))
+    }
+}
+
+impl RenderTimeline for AsciinemaPlayer {
+    fn frame_times(&self) -> Vec<f64> {
+        let duration = self.recording.duration();
+        let mut times = vec![0.0];
+        let mut bucket_start = None;
+        for event in self.recording.events.iter().filter(|event| event.is_output()) {
+            match bucket_start {
+                // Output shortly after the start of a bucket moves its frame forward so the frame
+                // shows all of it. The first frame always stays at the start of the recording.
+                Some(start) if event.time - start < EXPORT_FRAME_INTERVAL_SECONDS && times.len() > 1 => {
+                    if let Some(last) = times.last_mut() {
+                        *last = event.time;
+                    }
+                }
+                _ => {
+                    bucket_start = Some(event.time);
+                    if times.last().is_some_and(|last| *last < event.time) {
+                        times.push(event.time);
+                    }
+                }
+            }
+        }
+        if self.show_progress {
+            times.extend((1..=duration as u64).map(|second| second as f64));
+            times.sort_by(f64::total_cmp);
+            times.dedup();
+        }
+        // Output that was merged into the last frame would otherwise never show up.
+        if times.last().is_some_and(|last| *last < duration) {
+            times.push(duration);
+        }
+        times
+    }
+
+    fn seek_to(&self, time: f64) {
+        let mut state = self.state.lock().unwrap();
+        state.clock.stop();
+        state.jump(time.clamp(0.0, self.recording.duration()));
+        // The frame is rendered as if the recording was being played.
+        state.paused = false;
+    }
+
+    fn kind(&self) -> TimelineKind {
+        TimelineKind::Recording
+    }
+}
+
+struct AsciinemaPlaybackPollable {
+    state: Arc<Mutex<PlaybackState>>,
+    duration: f64,
+    loop_playback: bool,
+    show_progress: bool,
+    last_second: Option<u64>,
+}
+
+impl Pollable for AsciinemaPlaybackPollable {
+    fn poll(&mut self) -> PollableState {
+        let mut state = self.state.lock().unwrap();
+        if !state.started {
+            state.started = true;
+            if !state.paused {
+                state.clock.start();
+            }
+            state.set_time(0.0);
+            return PollableState::Modified;
+        }
+        if state.completed {
+            return PollableState::Done;
+        }
+
+        let playback_time = state.clock.time();
+        if let Some(stop_at) = state.stop_at {
+            if playback_time >= stop_at {
+                state.pause_at(stop_at);
+                state.stop_at = None;
+                return PollableState::Modified;
+            }
+        }
+        if playback_time >= self.duration {
+            if self.loop_playback && self.duration > 0.0 {
+                // Loop: reset to beginning
+                state.jump(0.0);
+                return PollableState::Modified;
+            } else {
+                // Complete: freeze on last frame
+                state.clock.stop();
+                state.jump(self.duration);
+                state.completed = true;
+                return PollableState::Done;
+            }
+        }
+
+        // Only re-render when new output was applied to the screen or the progress needs updating
+        let screen_changed = state.set_time(playback_time);
+        let second = playback_time as u64;
+        let progress_changed = self.show_progress && self.last_second != Some(second);
+        self.last_second = Some(second);
+        match screen_changed || progress_changed {
+            true => PollableState::Modified,
+            false => PollableState::Unmodified,
+        }
+    }
+}
+
+/// The line that shows how far into a recording the playback is.
+struct ProgressLine {
+    time: f64,
+    duration: f64,
+    speed: f64,
+    paused: bool,
+}
+
+impl ProgressLine {
+    fn render(&self, width: usize) -> String {
+        let status = if self.paused { "‖" } else { "▶" };
+        let prefix = format!("{status} {} / {} ", Self::format_time(self.time), Self::format_time(self.duration));
+        let suffix = format!(" {}x", Self::format_speed(self.speed));
+        let bar_width = width.saturating_sub(prefix.chars().count() + suffix.chars().count());
+        let progress = if self.duration > 0.0 { (self.time / self.duration).clamp(0.0, 1.0) } else { 1.0 };
+        let filled = (bar_width as f64 * progress).round() as usize;
+        let bar = format!("{}{}", "━".repeat(filled), "─".repeat(bar_width - filled));
+        format!("{prefix}{bar}{suffix}")
+    }
+
+    fn format_time(seconds: f64) -> String {
+        let seconds = seconds.max(0.0) as u64;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+
+    fn format_speed(speed: f64) -> String {
+        let speed = format!("{speed:.2}");
+        speed.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Steps through a recording as the presentation moves forward.
+///
+/// Recordings that wait to be started take one step to start playing, and every marker in them
+/// takes another one to play past it.
+#[derive(Debug)]
+pub(crate) struct AsciinemaStepMutator {
+    player: std::rc::Rc<AsciinemaPlayer>,
+    current: std::cell::Cell<usize>,
+}
+
+impl AsciinemaStepMutator {
+    pub(crate) fn new(player: std::rc::Rc<AsciinemaPlayer>) -> Self {
+        Self { player, current: std::cell::Cell::new(0) }
+    }
+}
+
+impl crate::presentation::ChunkMutator for AsciinemaStepMutator {
+    fn mutate_next(&self) -> bool {
+        let current = self.current.get();
+        if current == self.player.steps() {
+            return false;
+        }
+        self.current.set(current + 1);
+        self.player.play_step(current + 1);
+        true
+    }
+
+    fn mutate_previous(&self) -> bool {
+        let current = self.current.get();
+        if current == 0 {
+            return false;
+        }
+        self.current.set(current - 1);
+        self.player.rewind_to_step(current - 1);
+        true
+    }
+
+    fn reset_mutations(&self) {
+        self.current.set(0);
+        self.player.restart();
+    }
+
+    fn apply_all_mutations(&self) {
+        self.current.set(self.player.steps());
+        self.player.finish();
+    }
+
+    fn mutations(&self) -> (usize, usize) {
+        (self.current.get(), self.player.steps())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn paused_player() -> AsciinemaPlayer {
+        let cast = r#"{"version": 2, "width": 10, "height": 2}
+[0.0, "o", "a"]
+[10.0, "o", "b"]
+[20.0, "o", "c"]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        let options = PlaybackOptions {
+            loop_playback: false,
+            speed: 1.0,
+            start_policy: RenderAsyncStartPolicy::OnDemand,
+            wait: true,
+            show_progress: true,
+        };
+        AsciinemaPlayer::new(recording, Default::default(), 1, options)
+    }
+
+    fn time(player: &AsciinemaPlayer) -> f64 {
+        player.state.lock().unwrap().current_time
+    }
+
+    #[test]
+    fn test_parse_simple_cast() {
+        let cast_content = r#"{"version": 2, "width": 80, "height": 24}
+[0.0, "o", "Hello"]
+[1.0, "o", " World"]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast_content).expect("failed to parse");
+        assert_eq!(recording.width, 80);
+        assert_eq!(recording.height, 24);
+        assert_eq!(recording.events.len(), 2);
+        assert_eq!(recording.duration(), 1.0);
+    }
+
+    #[test]
+    fn seek() {
+        let player = paused_player();
+        assert_eq!(player.control_playback(PlaybackCommand::Seek(12.5)), PlaybackControl::Applied);
+        assert_eq!(time(&player), 12.5);
+
+        player.control_playback(PlaybackCommand::Seek(-20.0));
+        assert_eq!(time(&player), 0.0);
+
+        player.control_playback(PlaybackCommand::Seek(100.0));
+        assert_eq!(time(&player), 20.0);
+    }
+
+    #[test]
+    fn toggle_pause() {
+        let player = paused_player();
+        player.control_playback(PlaybackCommand::TogglePause);
+        assert!(!player.state.lock().unwrap().paused);
+
+        player.control_playback(PlaybackCommand::TogglePause);
+        assert!(player.state.lock().unwrap().paused);
+    }
+
+    #[test]
+    fn restart_when_completed() {
+        let player = paused_player();
+        let mut pollable = player.pollable();
+        player.play_step(1);
+        player.control_playback(PlaybackCommand::Seek(100.0));
+        assert_eq!(pollable.poll(), PollableState::Modified);
+        assert_eq!(pollable.poll(), PollableState::Done);
+
+        assert_eq!(player.control_playback(PlaybackCommand::Seek(-5.0)), PlaybackControl::Restart);
+        assert_eq!(time(&player), 15.0);
+        assert_eq!(player.control_playback(PlaybackCommand::Seek(100.0)), PlaybackControl::Applied);
+    }
+
+    #[test]
+    fn speed_steps() {
+        let player = paused_player();
+        let speed = || player.state.lock().unwrap().clock.speed;
+        player.control_playback(PlaybackCommand::SpeedUp);
+        assert_eq!(speed(), 1.25);
+        for _ in 0..20 {
+            player.control_playback(PlaybackCommand::SpeedUp);
+        }
+        assert_eq!(speed(), 4.0);
+        for _ in 0..20 {
+            player.control_playback(PlaybackCommand::SlowDown);
+        }
+        assert_eq!(speed(), 0.25);
+    }
+
+    #[rstest]
+    #[case::start(0.0, 1.0, false, "▶ 0:00 / 1:30 ───────────── 1x")]
+    #[case::half(45.0, 1.0, false, "▶ 0:45 / 1:30 ━━━━━━━────── 1x")]
+    #[case::paused(90.0, 1.0, true, "‖ 1:30 / 1:30 ━━━━━━━━━━━━━ 1x")]
+    #[case::fractional_speed(0.0, 1.5, false, "▶ 0:00 / 1:30 ─────────── 1.5x")]
+    fn progress_line(#[case] time: f64, #[case] speed: f64, #[case] paused: bool, #[case] expected: &str) {
+        let line = ProgressLine { time, duration: 90.0, speed, paused };
+        assert_eq!(line.render(30), expected);
+    }
+
+    fn event_times(recording: &AsciinemaRecording) -> Vec<(f64, &str)> {
+        recording.events.iter().map(|event| (event.time, event.event_type.as_str())).collect()
+    }
+
+    #[test]
+    fn markers_kept() {
+        let cast = r#"{"version": 2, "width": 80, "height": 24, "idle_time_limit": 2.5}
+[0.5, "o", "a"]
+[1.0, "m", "first"]
+[1.5, "i", "typed"]
+[2.0, "o", "b"]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        assert_eq!(event_times(&recording), &[(0.5, "o"), (1.0, "m"), (2.0, "o")]);
+        assert_eq!(recording.markers(), &[1.0]);
+        assert_eq!(recording.idle_time_limit(), Some(2.5));
+    }
+
+    #[test]
+    fn idle_time_limit() {
+        let cast = r#"{"version": 2, "width": 80, "height": 24}
+[5.0, "o", "a"]
+[6.0, "o", "b"]
+[16.0, "m", ""]
+[20.0, "o", "c"]
+"#;
+        let mut recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        recording.limit_idle_time(2.0);
+        assert_eq!(event_times(&recording), &[(2.0, "o"), (3.0, "o"), (5.0, "m"), (7.0, "o")]);
+    }
+
+    #[test]
+    fn trim() {
+        let cast = r#"{"version": 2, "width": 80, "height": 24}
+[1.0, "o", "a"]
+[2.0, "m", ""]
+[3.0, "o", "b"]
+[4.0, "m", ""]
+[5.0, "o", "c"]
+[7.0, "o", "d"]
+"#;
+        let mut recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        recording.trim(2.5, Some(6.0));
+        assert_eq!(event_times(&recording), &[(0.0, "o"), (0.5, "o"), (1.5, "m"), (2.5, "o")]);
+        assert_eq!(recording.duration(), 2.5);
+    }
+
+    #[test]
+    fn step_through_markers() {
+        use crate::presentation::ChunkMutator;
+
+        let cast = r#"{"version": 2, "width": 10, "height": 2}
+[1.0, "o", "a"]
+[2.0, "m", ""]
+[3.0, "o", "b"]
+[4.0, "m", ""]
+[5.0, "o", "c"]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        let options = PlaybackOptions {
+            loop_playback: false,
+            speed: 1.0,
+            start_policy: RenderAsyncStartPolicy::OnDemand,
+            wait: true,
+            show_progress: false,
+        };
+        let player = std::rc::Rc::new(AsciinemaPlayer::new(recording, Default::default(), 1, options));
+        let mutator = AsciinemaStepMutator::new(player.clone());
+        let mut pollable = player.pollable();
+        pollable.poll();
+        assert_eq!(mutator.mutations(), (0, 3));
+        let stop_at = || player.state.lock().unwrap().stop_at;
+        assert_eq!(stop_at(), Some(2.0));
+
+        // Pretend the first segment played until the marker.
+        assert!(mutator.mutate_next());
+        player.state.lock().unwrap().clock.set_time(2.5);
+        assert_eq!(pollable.poll(), PollableState::Modified);
+        assert_eq!(time(&player), 2.0);
+        assert!(player.state.lock().unwrap().paused);
+
+        // Moving forward before reaching a marker skips ahead to it.
+        assert!(mutator.mutate_next());
+        assert!(mutator.mutate_next());
+        assert_eq!(time(&player), 4.0);
+        assert_eq!(stop_at(), None);
+        assert!(!mutator.mutate_next());
+
+        // Going back pauses where the step that was undone started.
+        assert!(mutator.mutate_previous());
+        assert_eq!(time(&player), 4.0);
+        assert!(mutator.mutate_previous());
+        assert_eq!(time(&player), 2.0);
+        assert_eq!(stop_at(), Some(4.0));
+
+        mutator.apply_all_mutations();
+        assert_eq!(time(&player), 5.0);
+        mutator.reset_mutations();
+        assert_eq!(time(&player), 0.0);
+        assert_eq!(mutator.mutations(), (0, 3));
+    }
+
+    #[rstest]
+    #[case::time(PosterFrame::Time(12.0), 12.0)]
+    #[case::past_end(PosterFrame::Time(100.0), 20.0)]
+    #[case::end(PosterFrame::End, 20.0)]
+    fn freeze(#[case] poster: PosterFrame, #[case] expected: f64) {
+        let player = paused_player();
+        player.freeze(poster);
+        assert_eq!(time(&player), expected);
+
+        // Frozen recordings are done right away so exporting doesn't wait on them
+        assert_eq!(player.pollable().poll(), PollableState::Done);
+        assert_eq!(time(&player), expected);
+    }
+
+    #[test]
+    fn frame_times() {
+        let cast = r#"{"version": 2, "width": 10, "height": 2}
+[0.0, "o", "a"]
+[0.05, "o", "a"]
+[0.5, "o", "b"]
+[0.55, "o", "c"]
+[0.58, "o", "d"]
+[2.5, "m", ""]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        let options = PlaybackOptions {
+            loop_playback: false,
+            speed: 1.0,
+            start_policy: RenderAsyncStartPolicy::Automatic,
+            wait: false,
+            show_progress: false,
+        };
+        let player = AsciinemaPlayer::new(recording, Default::default(), 1, options);
+        assert_eq!(player.frame_times(), &[0.0, 0.05, 0.58, 2.5]);
+
+        player.seek_to(0.52);
+        assert_eq!(time(&player), 0.52);
+    }
+
+    #[test]
+    fn parse_v3() {
+        let cast = r#"{"version": 3, "term": {"cols": 100, "rows": 30, "type": "xterm-256color"}}
+# a comment
+[0.5, "o", "a"]
+[1.5, "m", ""]
+[0.25, "o", "b"]
+[0.25, "x", "0"]
+"#;
+        let recording = AsciinemaRecording::from_cast(cast).expect("failed to parse");
+        assert_eq!(recording.width, 100);
+        assert_eq!(recording.height, 30);
+        assert_eq!(event_times(&recording), &[(0.5, "o"), (2.0, "m"), (2.25, "o")]);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let result = AsciinemaRecording::from_cast(r#"{"version": 1, "width": 80, "height": 24}"#);
+        assert!(matches!(result, Err(AsciinemaError::InvalidFormat(_))));
+    }
+}
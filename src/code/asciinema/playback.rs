@@ -0,0 +1,176 @@
+use super::{AsciinemaRecording, screen::TerminalScreen};
+use std::sync::Arc;
+use vte::Parser;
+
+/// How often, in recording time, a snapshot of the screen is kept around for seeking.
+const KEYFRAME_INTERVAL_SECONDS: f64 = 10.0;
+
+/// A snapshot of the screen after applying a number of events.
+#[derive(Debug)]
+struct Keyframe {
+    next_event: usize,
+    screen: TerminalScreen,
+}
+
+/// The live screen state for a recording.
+///
+/// This only applies the events that happened since the last time the playback was advanced, and
+/// keeps periodic snapshots of the screen so jumping backwards doesn't require replaying the
+/// recording from the start.
+pub(crate) struct ScreenPlayback {
+    recording: Arc<AsciinemaRecording>,
+    screen: TerminalScreen,
+    parser: Parser,
+    next_event: usize,
+    keyframes: Vec<Keyframe>,
+}
+
+impl ScreenPlayback {
+    pub(crate) fn new(recording: Arc<AsciinemaRecording>) -> Self {
+        let screen = TerminalScreen::new(recording.width as usize, recording.height as usize);
+        let keyframes = vec![Keyframe { next_event: 0, screen: screen.clone() }];
+        Self { recording, screen, parser: Parser::new(), next_event: 0, keyframes }
+    }
+
+    /// The screen as of the last time the playback was moved.
+    pub(crate) fn screen(&self) -> &TerminalScreen {
+        &self.screen
+    }
+
+    /// Move the playback to the given timestamp, in seconds.
+    ///
+    /// Returns `true` if the screen changed as a result of this.
+    pub(crate) fn seek(&mut self, timestamp: f64) -> bool {
+        let target_event = self.recording.events.partition_point(|event| event.time <= timestamp);
+        if target_event == self.next_event {
+            return false;
+        }
+        if target_event < self.next_event {
+            // Keyframes are sorted by event so the first one is always usable.
+            let index = self.keyframes.partition_point(|keyframe| keyframe.next_event <= target_event) - 1;
+            let keyframe = &self.keyframes[index];
+            self.screen = keyframe.screen.clone();
+            self.next_event = keyframe.next_event;
+            self.parser = Parser::new();
+        }
+        self.apply_events(target_event);
+        true
+    }
+
+    fn apply_events(&mut self, target_event: usize) {
+        while self.next_event < target_event {
+            let event = &self.recording.events[self.next_event];
+            self.next_event += 1;
+            if !event.is_output() {
+                continue;
+            }
+            self.parser.advance(&mut self.screen, event.data.as_bytes());
+
+            let last_keyframe = self.keyframes.last().expect("no keyframes");
+            let last_keyframe_time = match last_keyframe.next_event {
+                0 => 0.0,
+                next_event => self.recording.events[next_event - 1].time,
+            };
+            if self.next_event > last_keyframe.next_event
+                && event.time - last_keyframe_time >= KEYFRAME_INTERVAL_SECONDS
+                && ends_outside_sequence(&event.data)
+            {
+                self.keyframes.push(Keyframe { next_event: self.next_event, screen: self.screen.clone() });
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ScreenPlayback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScreenPlayback")
+            .field("next_event", &self.next_event)
+            .field("keyframes", &self.keyframes.len())
+            .finish()
+    }
+}
+
+/// Check whether the given output doesn't end in the middle of an escape sequence.
+///
+/// The parser's state can't be snapshotted along with the screen, so keyframes are only taken at
+/// points where a fresh parser would interpret the upcoming output the same way.
+fn ends_outside_sequence(data: &str) -> bool {
+    let Some(position) = data.rfind('\x1b') else {
+        return true;
+    };
+    let sequence = &data.as_bytes()[position + 1..];
+    match sequence {
+        [] => false,
+        [b'[', rest @ ..] => rest.iter().any(|b| (0x40..=0x7e).contains(b)),
+        [b']', rest @ ..] => rest.contains(&0x07),
+        [b'(' | b')' | b'#' | b'%'] => false,
+        // `ESC \` terminates an OSC/DCS string.
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn recording(events: &[(f64, &str)]) -> Arc<AsciinemaRecording> {
+        let mut cast = String::from(r#"{"version": 2, "width": 10, "height": 3}"#);
+        for (time, data) in events {
+            cast.push('\n');
+            cast.push_str(&serde_json::to_string(&(time, "o", data)).unwrap());
+        }
+        Arc::new(AsciinemaRecording::from_cast(&cast).expect("invalid cast"))
+    }
+
+    fn contents(screen: &TerminalScreen) -> Vec<String> {
+        (0..screen.height())
+            .map(|row| screen.row_texts(row, 10).into_iter().map(|text| text.content).collect())
+            .collect()
+    }
+
+    fn snapshot(recording: &Arc<AsciinemaRecording>, timestamp: f64) -> Vec<String> {
+        let mut playback = ScreenPlayback::new(recording.clone());
+        playback.seek(timestamp);
+        contents(playback.screen())
+    }
+
+    #[test]
+    fn incremental() {
+        let recording = recording(&[(0.0, "a"), (1.0, "b"), (2.0, "\x1b[31"), (2.5, "mc")]);
+        let mut playback = ScreenPlayback::new(recording.clone());
+        for timestamp in [0.5, 1.5, 2.2, 3.0] {
+            assert!(playback.seek(timestamp));
+            assert_eq!(contents(playback.screen()), snapshot(&recording, timestamp));
+        }
+        assert!(!playback.seek(10.0));
+        assert_eq!(contents(playback.screen())[0], "abc       ");
+    }
+
+    #[test]
+    fn rewind() {
+        let events: Vec<_> = (0..100).map(|index| (index as f64, "x")).collect();
+        let recording = recording(&events);
+        let mut playback = ScreenPlayback::new(recording.clone());
+        playback.seek(99.0);
+        assert!(playback.keyframes.len() > 1);
+
+        for timestamp in [55.5, 3.0, 0.0, 75.0, 12.0] {
+            playback.seek(timestamp);
+            assert_eq!(contents(playback.screen()), snapshot(&recording, timestamp), "at {timestamp}");
+        }
+    }
+
+    #[rstest]
+    #[case::plain("hello", true)]
+    #[case::complete_csi("\x1b[31mhi", true)]
+    #[case::partial_csi("hi\x1b[31", false)]
+    #[case::lone_escape("hi\x1b", false)]
+    #[case::complete_osc("\x1b]0;title\x07", true)]
+    #[case::partial_osc("\x1b]0;tit", false)]
+    #[case::charset("\x1b(", false)]
+    #[case::simple_escape("\x1b7", true)]
+    fn sequence_boundaries(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(ends_outside_sequence(input), expected);
+    }
+}
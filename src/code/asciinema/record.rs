@@ -0,0 +1,259 @@
+use std::{
+    io::{self, Write},
+    process::{Command, ExitStatus},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Writes the output of a terminal session into an asciicast v2 file as it's produced.
+pub(crate) struct CastWriter<W: Write> {
+    writer: W,
+    start: Instant,
+    /// Output that ends in the middle of a UTF-8 character, waiting for the rest of it.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> CastWriter<W> {
+    /// Construct a new writer, writing the header for a terminal of the given size right away.
+    pub(crate) fn new(mut writer: W, width: u16, height: u16) -> io::Result<Self> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or_default();
+        let header = serde_json::json!({ "version": 2, "width": width, "height": height, "timestamp": timestamp });
+        writeln!(writer, "{header}")?;
+        Ok(Self { writer, start: Instant::now(), pending: Vec::new() })
+    }
+
+    /// Write an output event containing the given data, timestamped at the current time.
+    pub(crate) fn output(&mut self, data: &[u8]) -> io::Result<()> {
+        let time = self.start.elapsed().as_secs_f64();
+        self.output_at(time, data)
+    }
+
+    /// Flush any pending output and get back the underlying writer.
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        if !self.pending.is_empty() {
+            let data = String::from_utf8_lossy(&self.pending).into_owned();
+            self.write_event(self.start.elapsed().as_secs_f64(), &data)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn output_at(&mut self, time: f64, data: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(data);
+        // Keep the start of a character around until the rest of it shows up.
+        let consumed = match std::str::from_utf8(&self.pending) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            _ => self.pending.len(),
+        };
+        let data = String::from_utf8_lossy(&self.pending[..consumed]).into_owned();
+        self.pending.drain(..consumed);
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.write_event(time, &data)
+    }
+
+    fn write_event(&mut self, time: f64, data: &str) -> io::Result<()> {
+        // Microsecond precision is what asciinema itself uses.
+        let time = (time * 1_000_000.0).round() / 1_000_000.0;
+        let event = serde_json::to_string(&(time, "o", data))?;
+        writeln!(self.writer, "{event}")
+    }
+}
+
+/// Run a command attached to a pseudo terminal of the given size, recording everything it writes.
+///
+/// The output is also written into `echo` and anything read from stdin is forwarded to the
+/// command, so it can be used interactively while it's being recorded.
+#[cfg(unix)]
+pub(crate) fn record_command<W: Write, E: Write>(
+    mut command: Command,
+    writer: &mut CastWriter<W>,
+    mut echo: E,
+    columns: u16,
+    rows: u16,
+) -> io::Result<ExitStatus> {
+    use std::{
+        io::Read,
+        os::unix::process::CommandExt,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        thread,
+    };
+
+    let pty = unix::Pty::open(columns, rows)?;
+    let terminal = pty.open_terminal()?;
+    command.stdin(terminal.try_clone()?).stdout(terminal.try_clone()?).stderr(terminal);
+    // SAFETY: only async signal safe functions are called in between fork and exec.
+    unsafe {
+        command.pre_exec(|| {
+            // Start a new session so the pseudo terminal becomes the controlling terminal.
+            if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = command.spawn()?;
+    // Close our ends of the terminal so reading fails once the command exits.
+    drop(command);
+
+    let mut master = pty.master;
+    let done = Arc::new(AtomicBool::new(false));
+    let input = thread::spawn({
+        let master = master.try_clone()?;
+        let done = done.clone();
+        move || unix::forward_input(master, &done)
+    });
+
+    let mut buffer = [0; 4096];
+    let result = loop {
+        match master.read(&mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(count) => {
+                let data = &buffer[..count];
+                if let Err(e) = echo.write_all(data).and_then(|_| echo.flush()).and_then(|_| writer.output(data)) {
+                    break Err(e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            // Linux fails with EIO once there's no process on the other side of the terminal.
+            Err(_) => break Ok(()),
+        }
+    };
+    if result.is_err() {
+        let _ = child.kill();
+    }
+    let status = child.wait();
+    done.store(true, Ordering::Relaxed);
+    let _ = input.join();
+    result?;
+    status
+}
+
+#[cfg(not(unix))]
+pub(crate) fn record_command<W: Write, E: Write>(
+    _command: Command,
+    _writer: &mut CastWriter<W>,
+    _echo: E,
+    _columns: u16,
+    _rows: u16,
+) -> io::Result<ExitStatus> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "recording is only supported on unix"))
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::{
+        ffi::{CStr, CString, OsStr},
+        fs::{File, OpenOptions},
+        io::{self, Write},
+        os::unix::{
+            ffi::OsStrExt,
+            fs::OpenOptionsExt,
+            io::{AsRawFd, FromRawFd},
+        },
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    /// How long to wait for input before checking whether the command is done.
+    const INPUT_POLL_MILLIS: i32 = 50;
+
+    /// A pseudo terminal.
+    pub(super) struct Pty {
+        pub(super) master: File,
+        terminal_path: CString,
+    }
+
+    impl Pty {
+        pub(super) fn open(columns: u16, rows: u16) -> io::Result<Self> {
+            // SAFETY: the descriptor is owned by the file right after being created, and the name
+            // returned by `ptsname` is copied before anything else can modify it.
+            unsafe {
+                let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+                if fd == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                let master = File::from_raw_fd(fd);
+                if libc::grantpt(fd) == -1 || libc::unlockpt(fd) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                let name = libc::ptsname(fd);
+                if name.is_null() {
+                    return Err(io::Error::last_os_error());
+                }
+                let terminal_path = CStr::from_ptr(name).to_owned();
+                let size = libc::winsize { ws_row: rows, ws_col: columns, ws_xpixel: 0, ws_ypixel: 0 };
+                if libc::ioctl(fd, libc::TIOCSWINSZ, &size) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(Self { master, terminal_path })
+            }
+        }
+
+        /// Open the terminal side of this pseudo terminal.
+        pub(super) fn open_terminal(&self) -> io::Result<File> {
+            let path = OsStr::from_bytes(self.terminal_path.as_bytes());
+            OpenOptions::new().read(true).write(true).custom_flags(libc::O_NOCTTY).open(path)
+        }
+    }
+
+    /// Forward anything read from stdin into the given terminal until `done` is set.
+    pub(super) fn forward_input(mut master: File, done: &AtomicBool) {
+        let fd = io::stdin().as_raw_fd();
+        let mut buffer = [0_u8; 1024];
+        while !done.load(Ordering::Relaxed) {
+            let mut poll_fd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+            // SAFETY: the pollfd outlives the call.
+            let ready = unsafe { libc::poll(&mut poll_fd, 1, INPUT_POLL_MILLIS) };
+            if ready == -1 && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+                return;
+            }
+            if ready <= 0 {
+                continue;
+            }
+            // Read straight from the descriptor as stdin's buffering would hold on to input.
+            // SAFETY: the buffer is valid for its entire length.
+            let count = unsafe { libc::read(fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+            if count <= 0 || master.write_all(&buffer[..count as usize]).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::code::asciinema::AsciinemaRecording;
+
+    #[test]
+    fn split_characters() {
+        let mut writer = CastWriter::new(Vec::new(), 10, 2).expect("failed to create writer");
+        let bytes = "héllo".as_bytes();
+        writer.output_at(0.5, &bytes[..2]).unwrap();
+        writer.output_at(1.0, &bytes[2..]).unwrap();
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let recording = AsciinemaRecording::from_cast(&output).expect("invalid cast");
+        let events: Vec<_> = recording.events.iter().map(|event| (event.time, event.data.as_str())).collect();
+        assert_eq!(events, &[(0.5, "h"), (1.0, "éllo")]);
+        assert_eq!(recording.width(), 10);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn record() {
+        let mut writer = CastWriter::new(Vec::new(), 20, 5).expect("failed to create writer");
+        let mut command = Command::new("sh");
+        command.args(["-c", "stty size; [ -t 1 ] && echo terminal"]);
+        let status = record_command(command, &mut writer, io::sink(), 20, 5).expect("recording failed");
+        assert!(status.success());
+
+        let output = String::from_utf8(writer.finish().unwrap()).unwrap();
+        let recording = AsciinemaRecording::from_cast(&output).expect("invalid cast");
+        let data: String = recording.events.iter().map(|event| event.data.as_str()).collect();
+        assert_eq!(data, "5 20\r\nterminal\r\n");
+    }
+}
@@ -0,0 +1,754 @@
+use crate::markdown::{
+    elements::Text,
+    text_style::{Color, TextStyle},
+};
+use std::mem;
+use unicode_width::UnicodeWidthChar;
+use vte::{Params, ParamsIter, Perform};
+
+const TAB_WIDTH: usize = 8;
+
+/// The graphic rendition used when printing characters.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Pen {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+    italics: bool,
+    underlined: bool,
+    strikethrough: bool,
+    reverse: bool,
+}
+
+impl Pen {
+    fn style(&self) -> TextStyle {
+        let (foreground, background) = match self.reverse {
+            true => (Some(self.background.unwrap_or(Color::Black)), Some(self.foreground.unwrap_or(Color::White))),
+            false => (self.foreground, self.background),
+        };
+        let mut style = TextStyle::default();
+        if let Some(color) = foreground {
+            style = style.fg_color(color);
+        }
+        if let Some(color) = background {
+            style = style.bg_color(color);
+        }
+        if self.bold {
+            style = style.bold();
+        }
+        if self.italics {
+            style = style.italics();
+        }
+        if self.underlined {
+            style = style.underlined();
+        }
+        if self.strikethrough {
+            style = style.strikethrough();
+        }
+        style
+    }
+
+    /// The style used for cells that are erased while this pen is active.
+    fn erase_style(&self) -> TextStyle {
+        match self.background {
+            Some(color) if !self.reverse => TextStyle::default().bg_color(color),
+            _ => TextStyle::default(),
+        }
+    }
+
+    fn apply_sgr(&mut self, params: &Params) {
+        if params.is_empty() {
+            *self = Default::default();
+            return;
+        }
+        let mut iter = params.iter();
+        while let Some(param) = iter.next() {
+            let code = param.first().copied().unwrap_or(0);
+            match code {
+                0 => *self = Default::default(),
+                1 => self.bold = true,
+                3 => self.italics = true,
+                4 => self.underlined = true,
+                7 => self.reverse = true,
+                9 => self.strikethrough = true,
+                22 => self.bold = false,
+                23 => self.italics = false,
+                24 => self.underlined = false,
+                27 => self.reverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.foreground = indexed_color(code - 30),
+                38 => self.foreground = extended_color(param, &mut iter).or(self.foreground),
+                39 => self.foreground = None,
+                40..=47 => self.background = indexed_color(code - 40),
+                48 => self.background = extended_color(param, &mut iter).or(self.background),
+                49 => self.background = None,
+                90..=97 => self.foreground = indexed_color(code - 90 + 8),
+                100..=107 => self.background = indexed_color(code - 100 + 8),
+                _ => (),
+            };
+        }
+    }
+}
+
+/// Parse a `38`/`48` color, either in its `38;5;n` or its `38:5:n` form.
+fn extended_color(param: &[u16], iter: &mut ParamsIter) -> Option<Color> {
+    let mut values: Vec<u16> = param[1..].to_vec();
+    if values.is_empty() {
+        let kind = *iter.next()?.first()?;
+        values.push(kind);
+        let count = if kind == 2 { 3 } else { 1 };
+        for _ in 0..count {
+            values.push(*iter.next()?.first()?);
+        }
+    }
+    match values.as_slice() {
+        [5, index] => indexed_color(*index),
+        // Colon separated truecolors can include a color space id before the components.
+        [2, r, g, b] | [2, _, r, g, b, ..] => {
+            Some(Color::new((*r).try_into().ok()?, (*g).try_into().ok()?, (*b).try_into().ok()?))
+        }
+        _ => None,
+    }
+}
+
+/// Get the color for an index in the xterm 256 color palette.
+fn indexed_color(index: u16) -> Option<Color> {
+    let color = match index {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        7 => Color::Grey,
+        8 => Color::DarkGrey,
+        9 => Color::Red,
+        10 => Color::Green,
+        11 => Color::Yellow,
+        12 => Color::Blue,
+        13 => Color::Magenta,
+        14 => Color::Cyan,
+        15 => Color::White,
+        16..=231 => {
+            let levels = [0, 95, 135, 175, 215, 255];
+            let value = (index - 16) as usize;
+            Color::new(levels[value / 36], levels[(value / 6) % 6], levels[value % 6])
+        }
+        232..=255 => {
+            let level = (8 + (index - 232) * 10) as u8;
+            Color::new(level, level, level)
+        }
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Translate a character when the DEC special graphics character set is selected.
+fn line_drawing_char(c: char) -> char {
+    match c {
+        '`' => '◆',
+        'a' => '▒',
+        'f' => '°',
+        'g' => '±',
+        'j' => '┘',
+        'k' => '┐',
+        'l' => '┌',
+        'm' => '└',
+        'n' => '┼',
+        'o' => '⎺',
+        'p' => '⎻',
+        'q' => '─',
+        'r' => '⎼',
+        's' => '⎽',
+        't' => '├',
+        'u' => '┤',
+        'v' => '┴',
+        'w' => '┬',
+        'x' => '│',
+        'y' => '≤',
+        'z' => '≥',
+        '{' => 'π',
+        '|' => '≠',
+        '}' => '£',
+        '~' => '·',
+        _ => c,
+    }
+}
+
+/// A single cell in the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    character: char,
+    style: TextStyle,
+    /// The number of columns this cell's character takes up. Cells that are covered by the
+    /// second half of a wide character have a width of 0.
+    width: u8,
+}
+
+impl Cell {
+    fn blank(style: TextStyle) -> Self {
+        Self { character: ' ', style, width: 1 }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::blank(Default::default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Cursor {
+    row: usize,
+    column: usize,
+}
+
+#[derive(Clone, Debug)]
+struct SavedCursor {
+    cursor: Cursor,
+    pen: Pen,
+    line_drawing: bool,
+}
+
+/// An emulated VT100/xterm screen.
+///
+/// This interprets the output of a program as a terminal would, keeping track of the contents and
+/// style of every cell so they can be rendered later on.
+#[derive(Clone, Debug)]
+pub(crate) struct TerminalScreen {
+    rows: Vec<Vec<Cell>>,
+    primary_rows: Option<Vec<Vec<Cell>>>,
+    width: usize,
+    height: usize,
+    cursor: Cursor,
+    pending_wrap: bool,
+    pen: Pen,
+    scroll_top: usize,
+    scroll_bottom: usize,
+    saved_cursor: Option<SavedCursor>,
+    autowrap: bool,
+    line_drawing: bool,
+    last_char: Option<char>,
+}
+
+impl TerminalScreen {
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            rows: vec![vec![Cell::default(); width]; height],
+            primary_rows: None,
+            width,
+            height,
+            cursor: Default::default(),
+            pending_wrap: false,
+            pen: Default::default(),
+            scroll_top: 0,
+            scroll_bottom: height - 1,
+            saved_cursor: None,
+            autowrap: true,
+            line_drawing: false,
+            last_char: None,
+        }
+    }
+
+    /// The number of rows in this screen.
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the contents of a row, truncated to at most `columns` columns.
+    ///
+    /// Consecutive cells that share the same style are merged into a single [Text].
+    pub(crate) fn row_texts(&self, row: usize, columns: usize) -> Vec<Text> {
+        let Some(cells) = self.rows.get(row) else {
+            return Vec::new();
+        };
+        let mut texts: Vec<Text> = Vec::new();
+        let mut used_columns = 0;
+        for cell in cells {
+            if cell.width == 0 {
+                continue;
+            }
+            used_columns += cell.width as usize;
+            // A wide character that doesn't fit gets replaced by a space.
+            let character = if used_columns > columns {
+                if used_columns - cell.width as usize >= columns {
+                    break;
+                }
+                ' '
+            } else {
+                cell.character
+            };
+            match texts.last_mut() {
+                Some(text) if text.style == cell.style => text.content.push(character),
+                _ => texts.push(Text::new(character.to_string(), cell.style)),
+            };
+        }
+        texts
+    }
+
+    fn blank_cell(&self) -> Cell {
+        Cell::blank(self.pen.erase_style())
+    }
+
+    fn blank_row(&self) -> Vec<Cell> {
+        vec![self.blank_cell(); self.width]
+    }
+
+    fn put_char(&mut self, c: char) {
+        let c = if self.line_drawing { line_drawing_char(c) } else { c };
+        let width = match c.width() {
+            Some(0) | None => return,
+            Some(width) => width.min(2),
+        };
+        if self.pending_wrap || self.cursor.column + width > self.width {
+            if self.autowrap {
+                self.cursor.column = 0;
+                self.line_feed();
+            } else {
+                self.cursor.column = self.width - width;
+            }
+        }
+        self.pending_wrap = false;
+        if width > self.width {
+            return;
+        }
+
+        let style = self.pen.style();
+        let Cursor { row, column } = self.cursor;
+        self.clear_wide_char_at(row, column);
+        self.rows[row][column] = Cell { character: c, style, width: width as u8 };
+        if width == 2 {
+            self.clear_wide_char_at(row, column + 1);
+            self.rows[row][column + 1] = Cell { character: ' ', style, width: 0 };
+        }
+        self.last_char = Some(c);
+
+        if column + width >= self.width {
+            self.cursor.column = self.width - 1;
+            self.pending_wrap = true;
+        } else {
+            self.cursor.column = column + width;
+        }
+    }
+
+    /// Make sure overwriting the cell at the given position doesn't leave half of a wide character.
+    fn clear_wide_char_at(&mut self, row: usize, column: usize) {
+        let blank = self.blank_cell();
+        let cells = &mut self.rows[row];
+        match cells[column].width {
+            0 if column > 0 => cells[column - 1] = blank,
+            2 if column + 1 < cells.len() => cells[column + 1] = blank,
+            _ => (),
+        };
+    }
+
+    fn line_feed(&mut self) {
+        if self.cursor.row == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.cursor.row + 1 < self.height {
+            self.cursor.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        if self.cursor.row == self.scroll_top {
+            self.scroll_down(1);
+        } else {
+            self.cursor.row = self.cursor.row.saturating_sub(1);
+        }
+    }
+
+    /// Scroll the contents of the scroll region up, adding blank lines at the bottom.
+    fn scroll_up(&mut self, amount: usize) {
+        let region_size = self.scroll_bottom - self.scroll_top + 1;
+        let amount = amount.min(region_size);
+        self.rows.drain(self.scroll_top..self.scroll_top + amount);
+        let position = self.scroll_bottom + 1 - amount;
+        for _ in 0..amount {
+            self.rows.insert(position, self.blank_row());
+        }
+    }
+
+    /// Scroll the contents of the scroll region down, adding blank lines at the top.
+    fn scroll_down(&mut self, amount: usize) {
+        let region_size = self.scroll_bottom - self.scroll_top + 1;
+        let amount = amount.min(region_size);
+        self.rows.drain(self.scroll_bottom + 1 - amount..=self.scroll_bottom);
+        for _ in 0..amount {
+            self.rows.insert(self.scroll_top, self.blank_row());
+        }
+    }
+
+    fn insert_lines(&mut self, amount: usize) {
+        if !(self.scroll_top..=self.scroll_bottom).contains(&self.cursor.row) {
+            return;
+        }
+        let top = mem::replace(&mut self.scroll_top, self.cursor.row);
+        self.scroll_down(amount);
+        self.scroll_top = top;
+        self.cursor.column = 0;
+    }
+
+    fn delete_lines(&mut self, amount: usize) {
+        if !(self.scroll_top..=self.scroll_bottom).contains(&self.cursor.row) {
+            return;
+        }
+        let top = mem::replace(&mut self.scroll_top, self.cursor.row);
+        self.scroll_up(amount);
+        self.scroll_top = top;
+        self.cursor.column = 0;
+    }
+
+    fn erase_cells(&mut self, row: usize, columns: impl Iterator<Item = usize>) {
+        let blank = self.blank_cell();
+        for column in columns {
+            self.clear_wide_char_at(row, column);
+            self.rows[row][column] = blank;
+        }
+    }
+
+    fn erase_in_line(&mut self, mode: u16) {
+        let Cursor { row, column } = self.cursor;
+        match mode {
+            0 => self.erase_cells(row, column..self.width),
+            1 => self.erase_cells(row, 0..=column),
+            2 => self.erase_cells(row, 0..self.width),
+            _ => (),
+        };
+    }
+
+    fn erase_in_display(&mut self, mode: u16) {
+        let Cursor { row, .. } = self.cursor;
+        match mode {
+            0 => {
+                self.erase_in_line(0);
+                for row in row + 1..self.height {
+                    self.erase_cells(row, 0..self.width);
+                }
+            }
+            1 => {
+                self.erase_in_line(1);
+                for row in 0..row {
+                    self.erase_cells(row, 0..self.width);
+                }
+            }
+            2 | 3 => {
+                for row in 0..self.height {
+                    self.erase_cells(row, 0..self.width);
+                }
+            }
+            _ => (),
+        };
+    }
+
+    fn insert_chars(&mut self, amount: usize) {
+        let blank = self.blank_cell();
+        let Cursor { row, column } = self.cursor;
+        let amount = amount.min(self.width - column);
+        let cells = &mut self.rows[row];
+        cells.truncate(self.width - amount);
+        cells.splice(column..column, (0..amount).map(|_| blank));
+    }
+
+    fn delete_chars(&mut self, amount: usize) {
+        let blank = self.blank_cell();
+        let Cursor { row, column } = self.cursor;
+        let amount = amount.min(self.width - column);
+        let cells = &mut self.rows[row];
+        cells.drain(column..column + amount);
+        cells.extend((0..amount).map(|_| blank));
+    }
+
+    fn move_to(&mut self, row: usize, column: usize) {
+        self.cursor = Cursor { row: row.min(self.height - 1), column: column.min(self.width - 1) };
+        self.pending_wrap = false;
+    }
+
+    fn move_down(&mut self, amount: usize) {
+        // Movement stops at the scroll region's bottom margin if we're within it.
+        let limit = if self.cursor.row <= self.scroll_bottom { self.scroll_bottom } else { self.height - 1 };
+        self.move_to((self.cursor.row + amount).min(limit), self.cursor.column);
+    }
+
+    fn move_up(&mut self, amount: usize) {
+        let limit = if self.cursor.row >= self.scroll_top { self.scroll_top } else { 0 };
+        self.move_to(self.cursor.row.saturating_sub(amount).max(limit), self.cursor.column);
+    }
+
+    fn tab(&mut self) {
+        let next = (self.cursor.column / TAB_WIDTH + 1) * TAB_WIDTH;
+        self.cursor.column = next.min(self.width - 1);
+    }
+
+    fn save_cursor(&mut self) {
+        self.saved_cursor = Some(SavedCursor { cursor: self.cursor, pen: self.pen, line_drawing: self.line_drawing });
+    }
+
+    fn restore_cursor(&mut self) {
+        let saved = self.saved_cursor.clone().unwrap_or(SavedCursor {
+            cursor: Default::default(),
+            pen: Default::default(),
+            line_drawing: false,
+        });
+        self.pen = saved.pen;
+        self.line_drawing = saved.line_drawing;
+        self.move_to(saved.cursor.row, saved.cursor.column);
+    }
+
+    fn set_scroll_region(&mut self, top: usize, bottom: usize) {
+        let bottom = if bottom == 0 { self.height } else { bottom.min(self.height) };
+        let top = top.max(1);
+        if top < bottom {
+            self.scroll_top = top - 1;
+            self.scroll_bottom = bottom - 1;
+            self.move_to(0, 0);
+        }
+    }
+
+    fn enter_alternate_screen(&mut self) {
+        if self.primary_rows.is_none() {
+            let rows = vec![vec![Cell::default(); self.width]; self.height];
+            self.primary_rows = Some(mem::replace(&mut self.rows, rows));
+        }
+    }
+
+    fn exit_alternate_screen(&mut self) {
+        if let Some(rows) = self.primary_rows.take() {
+            self.rows = rows;
+        }
+    }
+
+    fn set_private_mode(&mut self, mode: u16, enabled: bool) {
+        match mode {
+            7 => self.autowrap = enabled,
+            47 | 1047 | 1049 => {
+                if enabled {
+                    if mode == 1049 {
+                        self.save_cursor();
+                    }
+                    self.enter_alternate_screen();
+                } else {
+                    self.exit_alternate_screen();
+                    if mode == 1049 {
+                        self.restore_cursor();
+                    }
+                }
+            }
+            _ => (),
+        };
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.width, self.height);
+    }
+}
+
+impl Perform for TerminalScreen {
+    fn print(&mut self, c: char) {
+        self.put_char(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' | 0x0b | 0x0c => self.line_feed(),
+            b'\r' => {
+                self.cursor.column = 0;
+                self.pending_wrap = false;
+            }
+            0x08 => {
+                self.cursor.column = self.cursor.column.saturating_sub(1);
+                self.pending_wrap = false;
+            }
+            b'\t' => self.tab(),
+            _ => (),
+        };
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, action: char) {
+        let values: Vec<u16> = params.iter().map(|param| param.first().copied().unwrap_or(0)).collect();
+        let param = |index: usize, default: u16| -> usize {
+            match values.get(index) {
+                Some(0) | None => default as usize,
+                Some(value) => *value as usize,
+            }
+        };
+        if intermediates == b"?" {
+            match action {
+                'h' => values.iter().for_each(|mode| self.set_private_mode(*mode, true)),
+                'l' => values.iter().for_each(|mode| self.set_private_mode(*mode, false)),
+                _ => (),
+            };
+            return;
+        }
+        if !intermediates.is_empty() {
+            return;
+        }
+        let Cursor { row, column } = self.cursor;
+        match action {
+            'A' => self.move_up(param(0, 1)),
+            'B' | 'e' => self.move_down(param(0, 1)),
+            'C' | 'a' => self.move_to(row, column + param(0, 1)),
+            'D' => self.move_to(row, column.saturating_sub(param(0, 1))),
+            'E' => {
+                self.move_down(param(0, 1));
+                self.cursor.column = 0;
+            }
+            'F' => {
+                self.move_up(param(0, 1));
+                self.cursor.column = 0;
+            }
+            'G' | '`' => self.move_to(row, param(0, 1) - 1),
+            'H' | 'f' => self.move_to(param(0, 1) - 1, param(1, 1) - 1),
+            'd' => self.move_to(param(0, 1) - 1, column),
+            'J' => self.erase_in_display(values.first().copied().unwrap_or(0)),
+            'K' => self.erase_in_line(values.first().copied().unwrap_or(0)),
+            'L' => self.insert_lines(param(0, 1)),
+            'M' => self.delete_lines(param(0, 1)),
+            '@' => self.insert_chars(param(0, 1)),
+            'P' => self.delete_chars(param(0, 1)),
+            'X' => {
+                let end = (column + param(0, 1)).min(self.width);
+                self.erase_cells(row, column..end);
+            }
+            'S' => self.scroll_up(param(0, 1)),
+            'T' => self.scroll_down(param(0, 1)),
+            'b' => {
+                if let Some(c) = self.last_char {
+                    for _ in 0..param(0, 1).min(self.width * self.height) {
+                        self.put_char(c);
+                    }
+                }
+            }
+            'r' => self.set_scroll_region(param(0, 1), values.get(1).copied().unwrap_or(0) as usize),
+            's' => self.save_cursor(),
+            'u' => self.restore_cursor(),
+            'm' => self.pen.apply_sgr(params),
+            _ => (),
+        };
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        match (intermediates, byte) {
+            ([], b'7') => self.save_cursor(),
+            ([], b'8') => self.restore_cursor(),
+            ([], b'D') => self.line_feed(),
+            ([], b'E') => {
+                self.cursor.column = 0;
+                self.line_feed();
+            }
+            ([], b'M') => self.reverse_index(),
+            ([], b'c') => self.reset(),
+            ([b'('], b'0') => self.line_drawing = true,
+            ([b'('], _) => self.line_drawing = false,
+            _ => (),
+        };
+    }
+
+    // OSC sequences (window titles, hyperlinks, etc) don't affect the screen contents.
+    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use vte::Parser;
+
+    fn run(width: usize, height: usize, input: &str) -> TerminalScreen {
+        let mut screen = TerminalScreen::new(width, height);
+        let mut parser = Parser::new();
+        parser.advance(&mut screen, input.as_bytes());
+        screen
+    }
+
+    fn contents(screen: &TerminalScreen) -> Vec<String> {
+        (0..screen.height())
+            .map(|row| screen.row_texts(row, screen.width).into_iter().map(|text| text.content).collect())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::text("hi", &["hi   ", "     "])]
+    #[case::line_break("hi\r\nbye", &["hi   ", "bye  "])]
+    #[case::wrap("abcdefg", &["abcde", "fg   "])]
+    #[case::exact_width("abcde", &["abcde", "     "])]
+    #[case::scroll("a\r\nb\r\nc", &["b    ", "c    "])]
+    #[case::cursor_position("\x1b[2;3Hx\x1b[1;1Hy", &["y    ", "  x  "])]
+    #[case::cursor_movement("ab\x1b[Bc\x1b[2Dd\x1b[Ae", &["abe  ", " dc  "])]
+    #[case::column("abc\x1b[2Gx", &["axc  ", "     "])]
+    #[case::erase_line_right("abcde\r\x1b[2C\x1b[K", &["ab   ", "     "])]
+    #[case::erase_line_left("abcde\r\x1b[2C\x1b[1K", &["   de", "     "])]
+    #[case::erase_display("ab\r\ncd\x1b[2J", &["     ", "     "])]
+    #[case::erase_display_below("ab\r\ncd\x1b[1;2H\x1b[J", &["a    ", "     "])]
+    #[case::backspace("ab\x08c", &["ac   ", "     "])]
+    #[case::tab("a\tb", &["a   b", "     "])]
+    #[case::delete_chars("abcde\r\x1b[P", &["bcde ", "     "])]
+    #[case::insert_chars("abc\r\x1b[2@", &["  abc", "     "])]
+    #[case::erase_chars("abcde\r\x1b[2X", &["  cde", "     "])]
+    #[case::insert_line("ab\r\ncd\x1b[1;1H\x1b[L", &["     ", "ab   "])]
+    #[case::delete_line("ab\r\ncd\x1b[1;1H\x1b[M", &["cd   ", "     "])]
+    #[case::reverse_index("ab\x1bM", &["     ", "ab   "])]
+    #[case::save_restore("a\x1b7\x1b[2;4Hb\x1b8c", &["ac   ", "   b "])]
+    #[case::line_drawing("\x1b(0lqk\x1b(Bx", &["┌─┐x ", "     "])]
+    #[case::wide_chars("日本語", &["日本 ", "語   "])]
+    #[case::repeat("a\x1b[3b", &["aaaa ", "     "])]
+    #[case::alternate_screen("ab\x1b[?1049hcd\x1b[?1049le", &["abe  ", "     "])]
+    #[case::no_autowrap("\x1b[?7labcdefg", &["abcdg", "     "])]
+    fn screen_contents(#[case] input: &str, #[case] expected: &[&str]) {
+        let screen = run(5, 2, input);
+        assert_eq!(contents(&screen), expected);
+    }
+
+    #[test]
+    fn scroll_region() {
+        let screen = run(3, 4, "a\r\nb\r\nc\r\nd\x1b[2;3r\x1b[3;1H\ne");
+        assert_eq!(contents(&screen), &["a  ", "c  ", "e  ", "d  "]);
+    }
+
+    #[rstest]
+    #[case::bold("\x1b[1mx", TextStyle::default().bold())]
+    #[case::reset("\x1b[1;3m\x1b[0mx", TextStyle::default())]
+    #[case::reset_empty("\x1b[1m\x1b[mx", TextStyle::default())]
+    #[case::remove_bold("\x1b[1;3m\x1b[22mx", TextStyle::default().italics())]
+    #[case::standard_foreground("\x1b[31mx", TextStyle::default().fg_color(Color::DarkRed))]
+    #[case::bright_foreground("\x1b[91mx", TextStyle::default().fg_color(Color::Red))]
+    #[case::standard_background("\x1b[44mx", TextStyle::default().bg_color(Color::DarkBlue))]
+    #[case::bright_background("\x1b[104mx", TextStyle::default().bg_color(Color::Blue))]
+    #[case::palette_foreground("\x1b[38;5;196mx", TextStyle::default().fg_color(Color::new(255, 0, 0)))]
+    #[case::palette_grayscale("\x1b[38;5;232mx", TextStyle::default().fg_color(Color::new(8, 8, 8)))]
+    #[case::rgb_foreground("\x1b[38;2;1;2;3mx", TextStyle::default().fg_color(Color::new(1, 2, 3)))]
+    #[case::rgb_colons("\x1b[48:2::1:2:3mx", TextStyle::default().bg_color(Color::new(1, 2, 3)))]
+    #[case::rgb_then_bold("\x1b[38;2;1;2;3;1mx", TextStyle::default().fg_color(Color::new(1, 2, 3)).bold())]
+    #[case::default_foreground("\x1b[31;39mx", TextStyle::default())]
+    #[case::reverse("\x1b[31;7mx", TextStyle::default().fg_color(Color::Black).bg_color(Color::DarkRed))]
+    fn styles(#[case] input: &str, #[case] expected: TextStyle) {
+        let screen = run(3, 1, input);
+        let texts = screen.row_texts(0, 1);
+        assert_eq!(texts, &[Text::new("x", expected)]);
+    }
+
+    #[test]
+    fn erase_uses_background() {
+        let screen = run(3, 1, "\x1b[41m\x1b[2J");
+        let texts = screen.row_texts(0, 3);
+        assert_eq!(texts, &[Text::new("   ", TextStyle::default().bg_color(Color::DarkRed))]);
+    }
+
+    #[test]
+    fn merged_runs() {
+        let screen = run(4, 1, "a\x1b[1mbc\x1b[0md");
+        let texts = screen.row_texts(0, 4);
+        let expected = &[Text::from("a"), Text::new("bc", TextStyle::default().bold()), Text::from("d")];
+        assert_eq!(texts, expected);
+    }
+
+    #[test]
+    fn truncated_wide_char() {
+        let screen = run(4, 1, "a日b");
+        let texts = screen.row_texts(0, 2);
+        assert_eq!(texts, &[Text::from("a ")]);
+    }
+}
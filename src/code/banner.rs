@@ -1,15 +1,22 @@
-use figlet_rs::FIGfont;
 use crate::markdown::text_style::{Color, TextStyle};
 use crate::markdown::elements::Text;
 use crate::markdown::text::{WeightedLine, WeightedText};
-use crate::render::operation::{AsRenderOperations, BlockLine, Pollable, PollableState, RenderAsync, RenderAsyncStartPolicy, RenderOperation};
+use crate::render::operation::{
+    AsRenderOperations, BlockLine, Pollable, PollableState, RenderAsync, RenderAsyncStartPolicy, RenderOperation,
+    RenderTimeline, TimelineKind,
+};
 use crate::render::properties::WindowSize;
 use crate::theme::Alignment;
-use crate::code::snippet::BannerAnimationStyle;
-use crate::code::animations::{AnimationContext, get_animation, hsl_to_rgb, get_glitched_char, get_matrix_char};
+use crate::code::animations::{Animation, AnimationContext, AnimationPalette};
+use crate::code::figlet::FigletFont;
+use crate::code::snippet::BannerLayout;
+use crate::resource::{ResourceBasePath, Resources};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use std::panic::{catch_unwind, AssertUnwindSafe};
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 
@@ -18,10 +25,69 @@ mod fonts {
     /// Standard font (embedded)
     pub const STANDARD: &str = include_str!("../../fonts/standard.flf");
 
-    // TODO: Add more embedded fonts like slant, banner, big, etc.
+    /// Every embedded font by name. New fonts are added by placing them in the top level `fonts`
+    /// directory and listing them here.
+    ///
+    /// Other than `standard`, these are presenterm's own fonts modeled after popular FIGlet ones.
+    /// They're prefixed so they're never mistaken for the fonts they're modeled after.
+    pub const EMBEDDED: &[(&str, &str)] = &[
+        ("standard", STANDARD),
+        ("presenterm-big", include_str!("../../fonts/presenterm-big.flf")),
+        ("presenterm-small", include_str!("../../fonts/presenterm-small.flf")),
+        ("presenterm-mini", include_str!("../../fonts/presenterm-mini.flf")),
+        ("presenterm-slant", include_str!("../../fonts/presenterm-slant.flf")),
+        ("presenterm-banner", include_str!("../../fonts/presenterm-banner.flf")),
+        ("presenterm-block", include_str!("../../fonts/presenterm-block.flf")),
+        ("presenterm-lean", include_str!("../../fonts/presenterm-lean.flf")),
+        ("presenterm-bubble", include_str!("../../fonts/presenterm-bubble.flf")),
+        ("presenterm-digital", include_str!("../../fonts/presenterm-digital.flf")),
+        ("presenterm-term", include_str!("../../fonts/presenterm-term.flf")),
+    ];
+
+    /// Find an embedded font by its case insensitive name.
+    pub fn find(name: &str) -> Option<&'static str> {
+        EMBEDDED.iter().find(|(font_name, _)| font_name.eq_ignore_ascii_case(name)).map(|(_, contents)| *contents)
+    }
+}
+
+/// The names of the fonts compiled into the binary.
+pub(crate) fn embedded_font_names() -> impl Iterator<Item = &'static str> {
+    fonts::EMBEDDED.iter().map(|(name, _)| *name)
+}
+
+/// The listing of embedded fonts printed by `--validate-fonts`.
+pub(crate) fn embedded_fonts_listing() -> String {
+    format!("Embedded FIGlet fonts: {}", embedded_font_names().collect::<Vec<_>>().join(", "))
+}
+
+/// The fonts a fitted banner falls back to when none are listed, from widest to narrowest.
+const DEFAULT_FIT_FONTS: &[&str] = &["presenterm-big", "standard", "presenterm-small", "presenterm-mini"];
+
+/// The fonts a banner in the given font falls back to when it's fitted without listing any.
+///
+/// If the font is one of the default ones, only the ones narrower than it are used.
+pub(crate) fn default_fit_fonts(font: &str) -> Vec<String> {
+    let narrower =
+        DEFAULT_FIT_FONTS.iter().position(|name| name.eq_ignore_ascii_case(font)).map_or(0, |index| index + 1);
+    DEFAULT_FIT_FONTS[narrower..].iter().map(ToString::to_string).collect()
+}
+
+/// A font where every character is itself, in a single row.
+#[cfg(test)]
+pub(crate) fn single_row_font() -> String {
+    let mut font = String::from("flf2a$ 1 1 2 0 0\n$@\n");
+    for c in 33..127_u8 {
+        font.push_str(&format!("{}@\n", c as char));
+    }
+    // The required german characters
+    font.push_str(&"?@\n".repeat(7));
+    font
 }
 
-/// One-time cache of validated, safe-to-use FIGlet fonts found on the system.
+/// The extensions of font files that can be loaded from a path: FIGlet and TOIlet fonts.
+const FONT_FILE_EXTENSIONS: &[&str] = &["flf", "tlf"];
+
+/// One-time cache of valid FIGlet fonts found on the system.
 /// Maps lowercased font name (without .flf) to full file path.
 static VALID_FONT_PATHS: OnceCell<HashMap<String, String>> = OnceCell::new();
 
@@ -42,42 +108,27 @@ fn scan_figlet_font_dirs() -> Vec<String> {
 }
 
 fn validate_font_file(path: &str) -> bool {
-    // Try to load and convert a simple string, catching panics from the figleter crate
-    let loaded = catch_unwind(AssertUnwindSafe(|| FIGfont::from_file(path)));
-    let Ok(Ok(font)) = loaded else {
-        return false;
-    };
-    let res = catch_unwind(AssertUnwindSafe(|| font.convert("TEST")));
-    matches!(res, Ok(Some(_)))
+    std::fs::read_to_string(path).is_ok_and(|contents| parse_font(&contents).is_ok())
 }
 
-fn build_valid_font_map() -> HashMap<String, String> {
-    // Whitelist of fonts tested to work with figlet-rs 0.1.5
-    // Tested: 163 total fonts, 149 working, 14 broken
-    // Broken fonts (cause panics): banner, big, bubble, digital, dwhistled, gradient,
-    // ivrit, l4me, maxfour, morse, pyramid, rot13, term, tsalagi
-    const SAFE_FONTS: &[&str] = &[
-        "3-d", "3x5", "5lineoblique", "acrobatic", "alligator", "alligator2", "alphabet", "avatar",
-        "banner3", "banner3-D", "banner4", "barbwire", "basic", "bell", "bigchief", "binary",
-        "block", "broadway", "bulbhead", "calgphy2", "caligraphy", "catwalk", "chunky", "coinstak",
-        "colossal", "computer", "contessa", "contrast", "cosmic", "cosmike", "crawford", "cricket",
-        "cursive", "cyberlarge", "cybermedium", "cybersmall", "decimal", "diamond", "doh", "doom",
-        "dotmatrix", "double", "drpepper", "eftichess", "eftifont", "eftipiti", "eftirobot", "eftitalic",
-        "eftiwall", "eftiwater", "epic", "fender", "fourtops", "fraktur", "fuzzy", "goofy",
-        "gothic", "graceful", "graffiti", "hex", "hollywood", "invita", "isometric1", "isometric2",
-        "isometric3", "isometric4", "italic", "jazmine", "jerusalem", "katakana", "kban", "larry3d",
-        "lcd", "lean", "letters", "linux", "lockergnome", "madrid", "marquee", "mike",
-        "mini", "mirror", "mnemonic", "moscow", "mshebrew210", "nancyj", "nancyj-fancy", "nancyj-underlined",
-        "nipples", "ntgreek", "nvscript", "o8", "octal", "ogre", "os2", "pawp",
-        "peaks", "pebbles", "pepper", "poison", "puffy", "rectangles", "relief", "relief2",
-        "rev", "roman", "rounded", "rowancap", "rozzo", "runic", "runyc", "sblood",
-        "script", "serifcap", "shadow", "short", "slant", "slide", "slscript", "small",
-        "smisome1", "smkeyboard", "smscript", "smshadow", "smslant", "smtengwar", "speed", "stacey",
-        "stampatello", "standard", "starwars", "stellar", "stop", "straight", "tanja", "tengwar",
-        "thick", "thin", "threepoint", "ticks", "ticksslant", "tinker-toy", "tombstone", "trek",
-        "twopoint", "univers", "usaflag", "weird", "whimsy"
-    ];
+/// Parse a font and make sure it can convert a simple string.
+fn parse_font(contents: &str) -> Result<FigletFont, String> {
+    let font = FigletFont::parse(contents).map_err(|e| e.to_string())?;
+    match font.render("TEST", None) {
+        Some(_) => Ok(font),
+        None => Err("font can't convert text".to_string()),
+    }
+}
+
+/// Whether a font name refers to a font file rather than an embedded or installed font.
+fn is_font_path(font_name: &str) -> bool {
+    std::path::Path::new(font_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| FONT_FILE_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
 
+fn build_valid_font_map() -> HashMap<String, String> {
     let mut map = HashMap::new();
     for d in scan_figlet_font_dirs() {
         if let Ok(entries) = std::fs::read_dir(&d) {
@@ -86,13 +137,9 @@ fn build_valid_font_map() -> HashMap<String, String> {
                 if let Some(ext) = path.extension() {
                     if ext == "flf" {
                         if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                            let stem_lower = stem.to_lowercase();
-                            // Only include whitelisted fonts
-                            if SAFE_FONTS.contains(&stem_lower.as_str()) {
-                                let path_str = path.to_string_lossy().to_string();
-                                if validate_font_file(&path_str) {
-                                    map.entry(stem_lower).or_insert(path_str);
-                                }
+                            let path_str = path.to_string_lossy().to_string();
+                            if validate_font_file(&path_str) {
+                                map.entry(stem.to_lowercase()).or_insert(path_str);
                             }
                         }
                     }
@@ -112,8 +159,8 @@ pub(crate) fn init_figlet_fonts_and_warn() {
     let count = map.len();
     let _ = VALID_FONT_PATHS.set(map);
     if count == 0 {
-        // No system fonts found or none validated — we’ll rely on embedded 'standard'.
-        eprintln!("[presenterm] note: no valid FIGlet fonts found on system; using embedded 'standard' font only");
+        // No system fonts found or none validated — we’ll rely on the embedded ones.
+        eprintln!("[presenterm] note: no valid FIGlet fonts found on system; using embedded fonts only");
     } else {
         eprintln!("[presenterm] detected {count} valid FIGlet font(s)");
     }
@@ -127,74 +174,82 @@ fn get_valid_font_path(name: &str) -> Option<String> {
 
 
 /// Generator for ASCII art banners using FIGlet fonts
+#[derive(Debug)]
 pub(crate) struct BannerGenerator {
-    font: FIGfont,
+    font: FigletFont,
+    layout: Option<BannerLayout>,
 }
 
 impl BannerGenerator {
     /// Create a new banner generator with the specified font
     pub(crate) fn new(font_name: &str) -> Result<Self, BannerError> {
         let font = Self::load_font(font_name)?;
-        Ok(Self { font })
-    }
-
-    /// Generate ASCII art from the given text
-    pub(crate) fn generate(&self, text: &str) -> Result<String, BannerError> {
-        // Wrapper to guard against panics inside figleter when converting some fonts
-        let res = catch_unwind(AssertUnwindSafe(|| self.font.convert(text)));
-        match res {
-            Ok(Some(figure)) => Ok(figure.to_string()),
-            Ok(None) => Err(BannerError::ConversionFailed(text.to_string())),
-            Err(_) => Err(BannerError::ConversionFailed(text.to_string())),
-        }
+        Ok(Self { font, layout: None })
     }
 
-    /// Generate a rainbow color for a given character position
-    pub(crate) fn rainbow_color(char_index: usize, total_chars: usize) -> Color {
-        crate::code::animations::rainbow_color(char_index, total_chars)
+    /// Lay out characters this way rather than the way the font says.
+    pub(crate) fn with_layout(mut self, layout: Option<BannerLayout>) -> Self {
+        self.layout = layout;
+        self
     }
 
-    /// Load a FIGlet font by name
-    fn load_font(font_name: &str) -> Result<FIGfont, BannerError> {
-        // Try embedded fonts first
-        match font_name.to_lowercase().as_str() {
-            "standard" => {
-                // Catch panics from figleter when parsing embedded font (debug overflow issues)
-                let result = catch_unwind(AssertUnwindSafe(|| {
-                    FIGfont::from_content(fonts::STANDARD)
-                }));
-
-                match result {
-                    Ok(Ok(font)) => return Ok(font),
-                    Ok(Err(e)) => return Err(BannerError::FontLoadFailed(font_name.to_string(), e)),
-                    Err(_) => return Err(BannerError::FontLoadFailed(
-                        font_name.to_string(),
-                        "panic during font loading".to_string()
-                    )),
+    /// Create a new banner generator for a font referenced from a presentation.
+    ///
+    /// Names ending in `.flf` or `.tlf` are paths, resolved relative to the given base path and
+    /// then to the fonts directory. Any other name is looked up as a font file in the fonts
+    /// directory before falling back to the embedded and system fonts.
+    pub(crate) fn load(
+        font_name: &str,
+        resources: &Resources,
+        base_path: &ResourceBasePath,
+    ) -> Result<Self, BannerError> {
+        if is_font_path(font_name) {
+            return match resources.font_file(font_name, base_path) {
+                Ok(contents) => Self::from_content(font_name, &contents),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    Err(BannerError::FontUnavailable(font_name.to_string()))
                 }
+                Err(e) => Err(BannerError::FontLoadFailed(font_name.to_string(), e.to_string())),
+            };
+        }
+        let fonts_path = ResourceBasePath::Custom(resources.fonts_path());
+        for extension in FONT_FILE_EXTENSIONS {
+            match resources.external_text_file(format!("{font_name}.{extension}"), &fonts_path) {
+                Ok(contents) => return Self::from_content(font_name, &contents),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(BannerError::FontLoadFailed(font_name.to_string(), e.to_string())),
             }
-            _ => {}
         }
+        Self::new(font_name)
+    }
 
-        // Allow only previously validated fonts (except embedded 'standard')
-        if let Some(path) = get_valid_font_path(font_name) {
-            // These fonts have already been validated, but catch panics anyway for safety
-            let result = catch_unwind(AssertUnwindSafe(|| {
-                FIGfont::from_file(&path)
-            }));
+    fn from_content(font_name: &str, contents: &str) -> Result<Self, BannerError> {
+        let font = parse_font(contents).map_err(|e| BannerError::FontLoadFailed(font_name.to_string(), e))?;
+        Ok(Self { font, layout: None })
+    }
 
-            match result {
-                Ok(Ok(font)) => return Ok(font),
-                Ok(Err(e)) => return Err(BannerError::FontLoadFailed(font_name.to_string(), e)),
-                Err(_) => return Err(BannerError::FontLoadFailed(
-                    font_name.to_string(),
-                    "panic during font loading".to_string()
-                )),
-            }
-        }
+    /// Generate ASCII art from the given text, split into lines.
+    pub(crate) fn generate_lines(&self, text: &str) -> Result<Vec<String>, BannerError> {
+        self.font.render(text, self.layout).ok_or_else(|| BannerError::ConversionFailed(text.to_string()))
+    }
 
-        // If not found/validated, do NOT fallback — reject the request
-        Err(BannerError::FontUnavailable(font_name.to_string()))
+    /// Generate ASCII art from the given text
+    pub(crate) fn generate(&self, text: &str) -> Result<String, BannerError> {
+        Ok(self.generate_lines(text)?.join("\n"))
+    }
+
+    /// Load a FIGlet font by name
+    fn load_font(font_name: &str) -> Result<FigletFont, BannerError> {
+        // Only fonts that were found, and parsed, when scanning the system are allowed. These take
+        // precedence so an embedded font never replaces the installed one a deck was written for.
+        let contents = match get_valid_font_path(font_name) {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| BannerError::FontLoadFailed(font_name.to_string(), e.to_string()))?,
+            None => {
+                fonts::find(font_name).ok_or_else(|| BannerError::FontUnavailable(font_name.to_string()))?.to_string()
+            }
+        };
+        FigletFont::parse(&contents).map_err(|e| BannerError::FontLoadFailed(font_name.to_string(), e.to_string()))
     }
 }
 
@@ -207,17 +262,121 @@ pub enum BannerError {
     #[error("failed to convert text '{0}' to ASCII art")]
     ConversionFailed(String),
 
-    #[error("requested figlet font '{0}' is not available or not validated")]
+    #[error("requested figlet font '{0}' is not available")]
     FontUnavailable(String),
 }
 
+/// The width of a piece of ASCII art, in characters.
+pub(crate) fn art_width(lines: &[String]) -> usize {
+    lines.iter().map(|line| line.chars().count()).max().unwrap_or(0)
+}
+
+/// The ASCII art a banner renders.
+#[derive(Clone, Debug)]
+pub(crate) enum BannerArt {
+    /// Art that's generated once and always rendered as is.
+    Fixed(Vec<String>),
+
+    /// Art that's generated to fit the width it's rendered in.
+    Fitted(FittedBannerArt),
+}
+
+impl BannerArt {
+    /// Get the art's lines when rendered in the given dimensions.
+    pub(crate) fn lines(&self, dimensions: &WindowSize, font_size: u8) -> Cow<'_, [String]> {
+        match self {
+            Self::Fixed(lines) => Cow::Borrowed(lines),
+            Self::Fitted(art) => Cow::Owned(art.lines(dimensions.columns / font_size.max(1) as u16)),
+        }
+    }
+
+    fn block_length(lines: &[String], font_size: u8, alignment: &Alignment) -> u16 {
+        alignment.adjust_size((art_width(lines) * font_size as usize) as u16)
+    }
+}
+
+/// ASCII art that picks the first font in a list that its text fits in.
+///
+/// If the text doesn't fit in a single row with any of them, its words are wrapped into as many
+/// rows as needed, again picking the first font in which every row fits.
+#[derive(Clone, Debug)]
+pub(crate) struct FittedBannerArt {
+    text: String,
+    generators: Vec<Rc<BannerGenerator>>,
+    cache: RefCell<Option<(u16, Vec<String>)>>,
+}
+
+impl FittedBannerArt {
+    /// Construct a new fitted banner art, making sure every font can render the text.
+    pub(crate) fn new(text: String, generators: Vec<Rc<BannerGenerator>>) -> Result<Self, BannerError> {
+        for generator in &generators {
+            generator.generate(&text)?;
+        }
+        Ok(Self { text, generators, cache: Default::default() })
+    }
+
+    fn lines(&self, columns: u16) -> Vec<String> {
+        let mut cache = self.cache.borrow_mut();
+        match cache.as_ref() {
+            Some((cached_columns, lines)) if *cached_columns == columns => lines.clone(),
+            _ => {
+                let lines = self.fit(columns as usize);
+                *cache = Some((columns, lines.clone()));
+                lines
+            }
+        }
+    }
+
+    fn fit(&self, columns: usize) -> Vec<String> {
+        for generator in &self.generators {
+            let lines = Self::render(generator, &self.text);
+            if art_width(&lines) <= columns {
+                return lines;
+            }
+        }
+        let mut lines = Vec::new();
+        for generator in &self.generators {
+            lines = self.wrap(generator, columns);
+            if art_width(&lines) <= columns {
+                break;
+            }
+        }
+        // If nothing fits we end up with the last font, wrapped as much as possible.
+        lines
+    }
+
+    fn wrap(&self, generator: &BannerGenerator, columns: usize) -> Vec<String> {
+        let mut rows = Vec::new();
+        let mut current: Option<String> = None;
+        for word in self.text.split_whitespace() {
+            current = match current {
+                None => Some(word.to_string()),
+                Some(row) => {
+                    let candidate = format!("{row} {word}");
+                    if art_width(&Self::render(generator, &candidate)) <= columns {
+                        Some(candidate)
+                    } else {
+                        rows.push(row);
+                        Some(word.to_string())
+                    }
+                }
+            };
+        }
+        rows.extend(current);
+        rows.iter().flat_map(|row| Self::render(generator, row)).collect()
+    }
+
+    fn render(generator: &BannerGenerator, text: &str) -> Vec<String> {
+        // Every font was checked to render the whole text when this was constructed.
+        generator.generate_lines(text).unwrap_or_default()
+    }
+}
+
 /// Animated rainbow banner
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct RainbowBannerAnimation {
-    /// The banner lines (ASCII art)
-    lines: Vec<String>,
-    /// Block length for alignment
-    block_length: u16,
+    /// The banner's ASCII art
+    art: BannerArt,
     /// Alignment settings
     alignment: Alignment,
     /// Font size
@@ -228,60 +387,103 @@ pub(crate) struct RainbowBannerAnimation {
     loop_animation: bool,
     /// Duration of a full animation cycle
     duration: Duration,
-    /// Animation style
-    style: BannerAnimationStyle,
+    /// The animation that picks every character's colors
+    animation: Arc<dyn Animation>,
+    /// The palette the animation's colors are mapped onto
+    palette: AnimationPalette,
 }
 
-#[derive(Debug)]
-struct AnimationState {
+#[derive(Debug, Default)]
+pub(crate) struct AnimationState {
     /// Current hue offset (0-360)
-    hue_offset: f32,
+    pub(crate) hue_offset: f32,
     /// Animation start time
     start_time: Option<Instant>,
     /// Whether animation has completed
-    completed: bool,
+    pub(crate) completed: bool,
+}
+
+impl AnimationState {
+    /// Stop the animation at its last frame, so nothing waits for it to play.
+    pub(crate) fn freeze(&mut self) {
+        self.start_time = Some(Instant::now());
+        self.hue_offset = 360.0;
+        self.completed = true;
+    }
+
+    /// Move the animation to the given point in time, in seconds.
+    pub(crate) fn seek(&mut self, time: f64, duration: Duration, loop_animation: bool) {
+        let progress = time / duration.as_secs_f64();
+        let progress = if loop_animation { progress.fract() } else { progress.clamp(0.0, 1.0) };
+        self.hue_offset = progress as f32 * 360.0;
+        self.completed = !loop_animation && progress >= 1.0;
+    }
+}
+
+/// The number of frames per second animations are exported at.
+const EXPORT_FRAMES_PER_SECOND: f64 = 10.0;
+
+/// The points in time an animation is rendered at when exporting it.
+///
+/// A looping animation's last frame is left out, since it's the same as its first one.
+pub(crate) fn animation_frame_times(duration: Duration, loop_animation: bool) -> Vec<f64> {
+    let duration = duration.as_secs_f64();
+    let frames = (duration * EXPORT_FRAMES_PER_SECOND).ceil().max(1.0) as usize;
+    let last = if loop_animation { frames - 1 } else { frames };
+    (0..=last).map(|frame| duration * frame as f64 / frames as f64).collect()
+}
+
+/// The kind of timeline for an animation.
+pub(crate) fn animation_timeline_kind(duration: Duration, loop_animation: bool) -> TimelineKind {
+    TimelineKind::Animation { cycle: loop_animation.then_some(duration.as_secs_f64()) }
 }
 
 impl RainbowBannerAnimation {
     pub(crate) fn new(
-        lines: Vec<String>,
-        block_length: u16,
+        art: BannerArt,
         alignment: Alignment,
         font_size: u8,
-        style: BannerAnimationStyle,
+        animation: Arc<dyn Animation>,
         loop_animation: bool,
         duration_millis: u64,
     ) -> Self {
         Self {
-            lines,
-            block_length,
+            art,
             alignment,
             font_size,
-            state: Arc::new(Mutex::new(AnimationState {
-                hue_offset: 0.0,
-                start_time: None,
-                completed: false,
-            })),
-            style,
+            state: Default::default(),
+            animation,
             loop_animation,
             duration: Duration::from_millis(duration_millis.max(1)),
+            palette: AnimationPalette::default(),
         }
     }
 
-    fn render_with_offset(&self, hue_offset: f32) -> Vec<RenderOperation> {
-        let total_chars: usize = self.lines.iter()
+    /// Map the animation's colors onto the given palette.
+    pub(crate) fn with_palette(mut self, palette: AnimationPalette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Render the animation's last frame from now on, without playing it.
+    pub(crate) fn freeze(&self) {
+        self.state.lock().unwrap().freeze();
+    }
+
+    fn render_with_offset(&self, lines: &[String], hue_offset: f32) -> Vec<RenderOperation> {
+        let total_chars: usize = lines.iter()
             .flat_map(|line| line.chars())
             .filter(|c| !c.is_whitespace())
             .count();
 
-        let total_rows = self.lines.len();
+        let total_rows = lines.len();
+        let block_length = BannerArt::block_length(lines, self.font_size, &self.alignment);
         let mut char_index = 0;
         let mut operations = Vec::new();
 
-        // Get the animation implementation
-        let animation = get_animation(self.style.clone());
+        let time = hue_offset / 360.0 * self.duration.as_secs_f32();
 
-        for (row_index, line) in self.lines.iter().enumerate() {
+        for (row_index, line) in lines.iter().enumerate() {
             let mut colored_text: Vec<Text> = Vec::new();
             let total_cols = line.chars().count();
 
@@ -291,6 +493,7 @@ impl RainbowBannerAnimation {
                 // Build animation context
                 let ctx = AnimationContext {
                     hue_offset,
+                    time,
                     char_index,
                     total_chars,
                     row_index,
@@ -298,520 +501,16 @@ impl RainbowBannerAnimation {
                     col_index,
                     total_cols,
                     ch,
+                    base_color: None,
+                    palette: &self.palette,
                 };
 
                 let (color, bg_color, replacement_char) = if is_whitespace {
-                    // For whitespace, calculate background color (foreground doesn't matter)
-                    match self.style {
-                        BannerAnimationStyle::Kaleidoscope => {
-                            // Calculate background for this whitespace position
-                            // Check animation phase for +once mode
-                            let animation_duration = 360.0;
-                            let fadeout_start = 320.0;
-                            let animation_complete = hue_offset > animation_duration;
-
-                            if animation_complete {
-                                // Final state: no background
-                                (Color::new(0, 0, 0), None, None)
-                            } else {
-                                // Active animation with fade-out
-                                let fade_factor = if hue_offset < fadeout_start {
-                                    1.0
-                                } else {
-                                    1.0 - ((hue_offset - fadeout_start) / (animation_duration - fadeout_start))
-                                };
-
-                                let center_y = total_rows as f32 / 2.0;
-                                let dist_y = (row_index as f32 - center_y).abs();
-
-                                let total_cols = line.chars().count() as f32;
-                                let center_x = total_cols / 2.0;
-                                let dist_x = (col_index as f32 - center_x).abs();
-                                let radial_dist = ((dist_x * dist_x + dist_y * dist_y).sqrt()) / center_x.max(1.0);
-
-                                let angle = ((row_index as f32 - center_y).atan2(col_index as f32 - center_x) * 180.0 / std::f32::consts::PI) + 180.0;
-
-                                let radial_wave = (radial_dist * 6.0 - hue_offset * 0.12).sin();
-                                let sector_count = 6.0;
-                                let sector_pattern = ((angle + hue_offset * 2.0) * sector_count / 180.0).sin();
-                                let bg_complexity = radial_wave * 0.6 + sector_pattern * 0.4;
-
-                                let bg_hue_base = (hue_offset * 2.5) % 360.0;
-                                let bg_hue = (bg_hue_base + bg_complexity * 120.0) % 360.0;
-                                let bg_saturation = (60.0 + bg_complexity.abs() * 20.0).clamp(60.0, 80.0);
-
-                                let base_bg_lightness = 8.0 + (radial_dist * 6.0);
-                                let bg_lightness = (base_bg_lightness + bg_complexity.abs() * 6.0).clamp(8.0, 20.0) * fade_factor;
-
-                                // Return background only if lightness is above threshold
-                                let bg_color = if bg_lightness > 0.5 {
-                                    Some(hsl_to_rgb(bg_hue, bg_saturation, bg_lightness))
-                                } else {
-                                    None
-                                };
-
-                                (Color::new(0, 0, 0), bg_color, None)
-                            }
-                        }
-                        _ => {
-                            // Other styles don't use background, return default
-                            (Color::new(0, 0, 0), None, None)
-                        }
-                    }
+                    // For whitespace only the background matters
+                    (Color::new(0, 0, 0), self.animation.render_whitespace(&ctx), None)
                 } else {
-                    // For non-whitespace, calculate full styling
-                    match self.style {
-                        BannerAnimationStyle::Rainbow => {
-                            let base_hue = (char_index as f32 / total_chars as f32) * 360.0;
-                            let hue = (base_hue + hue_offset) % 360.0;
-                            (hsl_to_rgb(hue, 100.0, 50.0), None, None)
-                        }
-                        BannerAnimationStyle::Flash => {
-                            // Single color for all characters, hue cycles with time
-                            let hue = hue_offset % 360.0;
-                            (hsl_to_rgb(hue, 100.0, 50.0), None, None)
-                        }
-                        BannerAnimationStyle::Wave => {
-                            // Hue oscillates around a base using a sine wave along characters
-                            let base = 200.0; // blue-ish base
-                            let amplitude = 60.0;
-                            let freq = 0.35; // chars per cycle
-                            let phase = hue_offset.to_radians();
-                            let hue = base + amplitude * ((char_index as f32 * freq + phase).sin());
-                            (hsl_to_rgb(((hue % 360.0) + 360.0) % 360.0, 100.0, 50.0), None, None)
-                        }
-                        BannerAnimationStyle::Iris => {
-                            // Lightness pulse expanding from center
-                            let center = (total_chars as f32) / 2.0;
-                            let pos = char_index as f32;
-                            let radius = (hue_offset / 360.0) * center.max(1.0);
-                            let dist = (pos - center).abs();
-                            let l = if dist <= radius { 60.0 } else { 35.0 };
-                            // Fixed hue rainbow mapping by index for variety
-                            let hue = (pos / total_chars as f32) * 360.0;
-                            (hsl_to_rgb(hue % 360.0, 100.0, l), None, None)
-                        }
-                        BannerAnimationStyle::Plasma => {
-                            // Psychedelic plasma effect using multiple overlapping sine waves
-                            let pos = char_index as f32;
-                            let time = hue_offset;
-
-                            // Combine multiple sine waves with different frequencies and phases
-                            let wave1 = (pos * 0.1 + time * 0.02).sin();
-                            let wave2 = (pos * 0.13 + time * 0.03).sin();
-                            let wave3 = (pos * 0.08 + time * 0.025).cos();
-
-                            // Average the waves and map from -1..1 to 0..360
-                            let hue = ((wave1 + wave2 + wave3) / 3.0 + 1.0) * 180.0;
-                            (hsl_to_rgb(hue % 360.0, 100.0, 50.0), None, None)
-                        }
-                        BannerAnimationStyle::Scanner => {
-                            // Horizontal scan line effect like KITT from Knight Rider
-                            let scan_pos = (hue_offset / 360.0) * total_chars as f32;
-                            let dist = (char_index as f32 - scan_pos).abs();
-                            let lightness = (70.0 - (dist * 8.0)).max(30.0);
-                            // Classic red scanner
-                            (hsl_to_rgb(0.0, 100.0, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Neon => {
-                            // Bright neon sign colors cycling through classic neon palette
-                            // Neon color palette: hot pink, electric blue, lime green, violet
-                            let palette = [330.0, 195.0, 85.0, 280.0];
-                            let palette_len = palette.len() as f32;
-
-                            // Cycle through palette with character position
-                            let cycle_position = (hue_offset + char_index as f32 * 5.0) % (palette_len * 90.0);
-                            let palette_index = (cycle_position / 90.0).floor() as usize % palette.len();
-                            let next_index = (palette_index + 1) % palette.len();
-
-                            // Interpolate between current and next palette color
-                            let t = (cycle_position % 90.0) / 90.0;
-                            let hue = palette[palette_index] * (1.0 - t) + palette[next_index] * t;
-
-                            // Bright neon glow with slight pulse effect
-                            let base_lightness = 60.0;
-                            let pulse = (hue_offset * 0.1).sin() * 5.0;
-                            let lightness = base_lightness + pulse;
-
-                            (hsl_to_rgb(hue % 360.0, 100.0, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Matrix => {
-                            // Matrix-style digital rain effect with varying green shades and characters
-                            let hue = 120.0; // Green base
-
-                            // Create pseudo-random variation per character for authentic look
-                            let char_seed = (char_index as f32 * 7.919 + row_index as f32 * 3.141).sin() * 100.0;
-                            let char_variation = char_seed.fract();
-
-                            // Cascade effect: brightness falls down the rows over time
-                            let time_offset = hue_offset / 8.0;
-                            let cascade_pos = row_index as f32 - time_offset;
-
-                            // Animation completion: add settle time after cascade finishes
-                            let animation_complete = time_offset > (total_rows as f32 + 3.0);
-                            let cascade_has_passed = time_offset > row_index as f32;
-
-                            // Characters near the cascade front are brightest (white-green)
-                            // Then fade to bright green, then stable after reveal
-                            let distance_from_front = cascade_pos.abs();
-
-                            let (saturation, lightness) = if animation_complete {
-                                // Animation fully complete: stable final state
-                                (90.0, 55.0)
-                            } else if distance_from_front < 1.0 && !cascade_has_passed {
-                                // Leading edge: bright white-green (low saturation, high lightness)
-                                (40.0 + char_variation * 20.0, 75.0 + char_variation * 15.0)
-                            } else if distance_from_front < 3.0 && !cascade_has_passed {
-                                // Bright green trail
-                                (80.0 + char_variation * 20.0, 55.0 + char_variation * 15.0)
-                            } else if cascade_has_passed {
-                                // After cascade passes: stable bright green (revealed state)
-                                (90.0, 50.0 + char_variation * 10.0)
-                            } else {
-                                // Dark green / not yet revealed
-                                (70.0, 15.0 + char_variation * 10.0)
-                            };
-
-                            // Character replacement logic:
-                            // - Animation complete: ALWAYS show actual character (final clean state)
-                            // - Before cascade: show Matrix glyphs
-                            // - After cascade but before complete: show actual character with rare glitches
-                            let replacement = if animation_complete {
-                                // Final frame: no replacements, show actual text
-                                None
-                            } else if cascade_has_passed {
-                                // Cascade has passed - show actual character with occasional glitch
-                                let glitch_chance = (char_seed * 37.1 + hue_offset * 0.03).sin();
-                                if glitch_chance > 0.95 {
-                                    // Rare glitch: briefly show Matrix character
-                                    Some(get_matrix_char(char_seed + hue_offset * 10.0))
-                                } else {
-                                    // Normal: show actual character
-                                    None
-                                }
-                            } else {
-                                // Cascade hasn't reached yet - show Matrix characters
-                                Some(get_matrix_char(char_seed + hue_offset * 0.5))
-                            };
-
-                            (hsl_to_rgb(hue, saturation, lightness), None, replacement)
-                        }
-                        BannerAnimationStyle::Sepia => {
-                            // Subdued vintage monochrome sepia tone with gentle brightness wave
-                            let hue = 30.0; // Warm brown/sepia tone
-                            let saturation = 45.0; // Desaturated vintage look
-                            let lightness = 35.0 + 15.0 * (char_index as f32 * 0.15 + hue_offset * 0.05).sin();
-                            (hsl_to_rgb(hue, saturation, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Kaleidoscope => {
-                            // Enhanced psychedelic symmetrical rotating color patterns
-                            // Creates rich, multi-layered kaleidoscope with radial symmetry
-
-                            // Animation phases: active -> fade-out -> complete
-                            let animation_duration = 360.0;
-                            let fadeout_start = 320.0;
-                            let animation_complete = hue_offset > animation_duration;
-
-                            if animation_complete {
-                                // Final state: maximum readability with NO background
-                                // Use simple rainbow for foreground, transparent background
-                                let base_hue = (char_index as f32 / total_chars as f32) * 360.0;
-                                let fg_color = hsl_to_rgb(base_hue, 85.0, 75.0);
-                                (fg_color, None, None)
-                            } else {
-                                // Active animation state with fade-out
-
-                                // Calculate fade factor: 1.0 during animation, fades to 0.0 during fadeout period
-                                let fade_factor = if hue_offset < fadeout_start {
-                                    1.0
-                                } else {
-                                    // Fade out over the remaining period
-                                    1.0 - ((hue_offset - fadeout_start) / (animation_duration - fadeout_start))
-                                };
-
-                                // Calculate positions relative to center for radial symmetry
-                                let center_x = (total_chars as f32) / 2.0;
-                                let center_y = total_rows as f32 / 2.0;
-                                let dist_x = (char_index as f32 - center_x).abs();
-                                let dist_y = (row_index as f32 - center_y).abs();
-
-                                // Radial distance from center (for circular patterns)
-                                let radial_dist = ((dist_x * dist_x + dist_y * dist_y).sqrt()) / center_x.max(1.0);
-
-                                // Angular position (polar coordinates for rotational symmetry)
-                                let angle = ((row_index as f32 - center_y).atan2(char_index as f32 - center_x) * 180.0 / std::f32::consts::PI) + 180.0;
-
-                                // Simplified pattern generation for cleaner look
-
-                                // Layer 1: Radial waves (primary pattern)
-                                let radial_wave = (radial_dist * 6.0 - hue_offset * 0.12).sin();
-
-                                // Layer 2: Rotational sectors (kaleidoscope mirrors)
-                                let sector_count = 6.0; // 6-fold symmetry
-                                let sector_pattern = ((angle + hue_offset * 2.0) * sector_count / 180.0).sin();
-
-                                // Simplified background pattern (less competing visual noise)
-                                let bg_complexity = radial_wave * 0.6 + sector_pattern * 0.4;
-
-                                // Background: Full spectrum cycling with simplified modulation
-                                let bg_hue_base = (hue_offset * 2.5) % 360.0;
-                                let bg_hue = (bg_hue_base + bg_complexity * 120.0) % 360.0;
-
-                                // Desaturated background (60-80%) for less competition with foreground
-                                let bg_saturation = (60.0 + bg_complexity.abs() * 20.0).clamp(60.0, 80.0);
-
-                                // Background lightness: Very dark (8-20%) for maximum contrast
-                                // Fades darker as fade_factor approaches 0
-                                let base_bg_lightness = 8.0 + (radial_dist * 6.0);
-                                let bg_lightness = (base_bg_lightness + bg_complexity.abs() * 6.0).clamp(8.0, 20.0) * fade_factor;
-
-                                // Foreground: Pure complementary colors (180° offset) for maximum contrast
-                                let fg_pattern = (radial_dist * 5.0 + hue_offset * 0.1).sin() * 0.5 +
-                                                (angle * 0.3 - hue_offset * 0.3).cos() * 0.5;
-
-                                // During fade-out, transition foreground to simple rainbow
-                                let (fg_hue, fg_saturation) = if hue_offset < fadeout_start {
-                                    // Active phase: complementary colors
-                                    let fg_hue_base = (bg_hue + 180.0) % 360.0;
-                                    let fg_hue = (fg_hue_base + fg_pattern * 60.0) % 360.0;
-                                    let fg_saturation = (88.0 + fg_pattern.abs() * 12.0).clamp(88.0, 100.0);
-                                    (fg_hue, fg_saturation)
-                                } else {
-                                    // Fade-out phase: transition to simple rainbow
-                                    let target_hue = (char_index as f32 / total_chars as f32) * 360.0;
-                                    let current_fg_hue_base = (bg_hue + 180.0) % 360.0;
-                                    let current_fg_hue = (current_fg_hue_base + fg_pattern * 60.0) % 360.0;
-
-                                    // Interpolate hue and saturation
-                                    let inverse_fade = 1.0 - fade_factor;
-                                    let fg_hue = current_fg_hue * fade_factor + target_hue * inverse_fade;
-                                    let fg_saturation = 100.0 * fade_factor + 85.0 * inverse_fade;
-                                    (fg_hue, fg_saturation)
-                                };
-
-                                // Foreground lightness: Very bright (75-95%) for maximum readability
-                                let base_fg_lightness = 75.0 + (radial_dist * 8.0);
-                                let pattern_boost = fg_pattern.abs() * 6.0;
-                                let fg_lightness = (base_fg_lightness + pattern_boost).clamp(75.0, 92.0);
-
-                                // Subtle sparkle effect (reduced intensity)
-                                let sparkle = if (radial_wave + sector_pattern).abs() > 0.94 {
-                                    6.0 * fade_factor // Sparkle fades out too
-                                } else {
-                                    0.0
-                                };
-
-                                let final_fg_lightness = (fg_lightness + sparkle).clamp(75.0, 95.0);
-
-                                let fg_color = hsl_to_rgb(fg_hue, fg_saturation, final_fg_lightness);
-
-                                // Return background only if fade_factor > 0
-                                let bg_color = if bg_lightness > 0.5 {
-                                    Some(hsl_to_rgb(bg_hue, bg_saturation, bg_lightness))
-                                } else {
-                                    None
-                                };
-
-                                (fg_color, bg_color, None)
-                            }
-                        }
-                        BannerAnimationStyle::Fire => {
-                            // Flame-like effect with red/orange/yellow gradient moving upward
-                            // Characters at bottom are red, middle orange, top yellow
-
-                            // Calculate vertical position (0.0 at bottom to 1.0 at top)
-                            let vertical_pos = row_index as f32 / (total_rows as f32).max(1.0);
-
-                            // Base hue for fire: 0° (red) at bottom to 60° (yellow) at top
-                            let base_hue = vertical_pos * 60.0;
-
-                            // Add flickering effect that moves upward
-                            let flicker = (hue_offset * 0.1 + char_index as f32 * 0.3).sin() * 10.0;
-                            let hue = (base_hue + flicker).max(0.0).min(60.0);
-
-                            // Saturation: 100% for vibrant fire colors
-                            let saturation = 100.0;
-
-                            // Lightness: 45-65% with random variations for flicker effect
-                            let base_lightness = 55.0;
-                            let lightness_flicker = (hue_offset * 0.15 + char_index as f32 * 0.2 + row_index as f32 * 0.4).sin() * 10.0;
-                            let lightness = (base_lightness + lightness_flicker).max(45.0).min(65.0);
-
-                            (hsl_to_rgb(hue, saturation, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Glitch => {
-                            // Cyberpunk glitch aesthetic with random color flickering and character corruption
-                            // Animation stabilizes at the end for +once mode
-                            let glitch_duration = 300.0; // Glitch for ~300 degrees of hue_offset
-                            let animation_complete = hue_offset > glitch_duration;
-
-                            if animation_complete {
-                                // Final stable state: clean green color (like terminal recovered)
-                                (hsl_to_rgb(120.0, 60.0, 55.0), None, None)
-                            } else {
-                                // Active glitching phase
-                                // Use character index + hue_offset as seed for pseudo-random behavior
-                                let glitch_seed = (char_index as f32 * 12.9898 + hue_offset * 78.233).sin() * 43758.5453;
-
-                                // Pick color: 0=cyan (~180°), 1=magenta (~300°), 2=random
-                                let hue_choice = (glitch_seed.fract() * 3.0) as i32;
-                                let hue = match hue_choice {
-                                    0 => 180.0, // Cyan
-                                    1 => 300.0, // Magenta
-                                    _ => glitch_seed.fract() * 360.0, // Random vibrant color
-                                };
-
-                                // Random brightness variations
-                                let lightness = 35.0 + (glitch_seed.fract() * 40.0);
-
-                                // High saturation for vibrant glitch effect
-                                let saturation = 85.0 + ((glitch_seed * 1.234).fract() * 15.0);
-
-                                // Character glitching: replace with visually similar characters
-                                // Use a different seed for character selection
-                                let char_glitch_seed = (char_index as f32 * 7.321 + hue_offset * 0.5).sin() * 12345.6789;
-
-                                // Increase glitch probability as we approach the end
-                                let glitch_intensity = 1.0 - (hue_offset / glitch_duration).min(1.0);
-                                let glitch_threshold = 0.65 - (glitch_intensity * 0.3); // 35-65% chance
-                                let should_glitch = char_glitch_seed.fract() > glitch_threshold;
-
-                                let replacement = if should_glitch {
-                                    get_glitched_char(ch, char_glitch_seed)
-                                } else {
-                                    None
-                                };
-
-                                (hsl_to_rgb(hue, saturation, lightness), None, replacement)
-                            }
-                        }
-                        BannerAnimationStyle::Breathe => {
-                            // Subdued, gentle synchronized breathing effect
-                            // All characters pulse in lightness together, like breathing in and out
-                            let hue = (char_index as f32 / total_chars as f32) * 360.0;
-                            let saturation = 65.0; // Calming, not too vibrant
-                            // Synchronized breathing: all characters pulse together
-                            let lightness = 35.0 + 20.0 * (hue_offset * 0.05).sin();
-                            (hsl_to_rgb(hue % 360.0, saturation, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Prism => {
-                            // Spectrum split and refraction effect like light through a prism
-                            // Creates 3 distinct rainbow beams that shift position over time
-                            let beam_width = total_chars as f32 / 3.0;
-                            let shifted_pos = char_index as f32 + hue_offset * 1.5; // Faster animation
-                            let beam_pos = shifted_pos % beam_width;
-
-                            // Full spectrum within each beam
-                            let hue = (beam_pos / beam_width) * 360.0;
-
-                            // Add some variation in brightness to emphasize the beams
-                            let beam_center = beam_width / 2.0;
-                            let dist_from_center = (beam_pos - beam_center).abs();
-                            let lightness = 55.0 - (dist_from_center / beam_center) * 10.0;
-
-                            (hsl_to_rgb(hue % 360.0, 100.0, lightness), None, None)
-                        }
-                        BannerAnimationStyle::Aurora => {
-                            // Northern lights effect: flowing vertical curtains of green/teal/purple
-                            // Build a smooth field using overlapping sine waves across columns and time
-                            let t = hue_offset * 0.02; // slow time
-                            let x = col_index as f32;
-                            let y = row_index as f32;
-
-                            // Two moving wave fronts with slight vertical parallax
-                            let wave1 = (x * 0.12 + t + y * 0.10).sin();
-                            let wave2 = (x * 0.07 - t * 0.8 + y * 0.18).cos();
-                            let field = (wave1 + wave2) * 0.5; // -1..1
-
-                            // Map field to a hue band between 120° (green) and 280° (purple)
-                            let hue_min = 120.0;
-                            let hue_max = 280.0;
-                            let hue = hue_min + ((field + 1.0) * 0.5) * (hue_max - hue_min);
-
-                            // Shimmer: saturation and lightness gently fluctuate by height
-                            let saturation = 70.0 + (y * 0.15 + t * 0.8).sin() * 15.0;
-                            let base_lightness = 45.0 + field.abs() * 18.0; // brighter on wave ridges
-                            let lightness = base_lightness + (y * 0.25 + t).sin() * 6.0;
-
-                            (hsl_to_rgb(hue % 360.0, saturation.clamp(35.0, 100.0), lightness.clamp(30.0, 80.0)), None, None)
-                        }
-                        BannerAnimationStyle::Crt => {
-                            // Retro CRT effect with scanlines, phosphor triads, and rolling highlight
-                            let t = hue_offset; // time proxy
-                            let y = row_index as f32;
-                            let x = col_index as f32;
-
-                            // Base hue cycles subtly to simulate phosphor decay/recharge
-                            let base_hue = (180.0 + (x * 0.5 + t * 0.2).sin() * 20.0 + (y * 0.8 + t * 0.15).cos() * 15.0) % 360.0;
-
-                            // Chromatic subpixel triad: tint columns R/G/B in sequence
-                            let triad = (col_index % 3) as u8;
-                            let hue = match triad {
-                                0 => (base_hue + 10.0) % 360.0, // slight red tint
-                                1 => (base_hue + 140.0) % 360.0, // green tint
-                                _ => (base_hue + 260.0) % 360.0, // blue tint
-                            };
-
-                            // Horizontal scanlines: every other row is slightly darker
-                            let scanline = if row_index % 2 == 0 { -8.0 } else { 0.0 };
-
-                            // Rolling bright bar moving down (vertical retrace)
-                            let bar_pos = (t * 0.35) % 360.0; // 0..360
-                            // Map 0..360 to rows cyclically
-                            let bar_row = (bar_pos / 360.0) * (total_rows as f32).max(1.0);
-                            let dist = (y - bar_row).abs();
-                            let bar_boost = (1.0 - (dist / 2.5).min(1.0)) * 18.0; // strong near the bar
-
-                            // Subtle noise flicker
-                            let noise = ((x * 12.9898 + y * 78.233 + t).sin() * 43758.5453).fract() * 4.0 - 2.0;
-
-                            let saturation = 75.0;
-                            let lightness = 42.0 + scanline + bar_boost + noise;
-
-                            (hsl_to_rgb(hue, saturation, lightness.clamp(25.0, 80.0)), None, None)
-                        }
-                        BannerAnimationStyle::Typewriter => {
-                            // Gradual left-to-right, top-to-bottom reveal
-                            // Use a longer duration to ensure we have time to reveal all characters
-                            // and add settling time before animation completes
-                            let typing_duration = 320.0; // Time to type all characters
-                            let settle_time = 40.0; // Brief pause after typing completes
-                            let total_duration = typing_duration + settle_time;
-
-                            if hue_offset > total_duration {
-                                // Animation fully complete: all text revealed, no caret
-                                let color = hsl_to_rgb(40.0, 20.0, 85.0);
-                                (color, None, None)
-                            } else {
-                                let progress = (hue_offset / typing_duration).clamp(0.0, 1.0);
-                                // Add 1 to ensure we reach total_chars, not total_chars-1
-                                let reveal_count = ((progress * total_chars as f32).floor() as usize).min(total_chars);
-
-                                if char_index < reveal_count {
-                                    // Already revealed: warm white ink
-                                    let color = hsl_to_rgb(40.0, 20.0, 85.0);
-                                    (color, None, None)
-                                } else if char_index == reveal_count && reveal_count < total_chars {
-                                    // Currently being typed: show with caret (only if not at end)
-                                    let color = hsl_to_rgb(200.0, 85.0, 65.0);
-                                    // Draw a block caret instead of the ASCII glyph to emphasize typing
-                                    (color, None, Some('▌'))
-                                } else if reveal_count >= total_chars {
-                                    // All characters revealed but still in settling time
-                                    let color = hsl_to_rgb(40.0, 20.0, 85.0);
-                                    (color, None, None)
-                                } else {
-                                    // Not yet revealed: render as space (no ink)
-                                    let color = hsl_to_rgb(0.0, 0.0, 0.0);
-                                    (color, None, Some(' '))
-                                }
-                            }
-                        }
-                        _ => {
-                            // Fallback for unimplemented styles
-                            (hsl_to_rgb(0.0, 0.0, 50.0), None, None)
-                        }
-                    }
+                    let result = self.animation.render_char(&ctx);
+                    (result.color, result.bg_color, result.replacement_char)
                 };
 
                 // Increment char_index only for non-whitespace characters
@@ -841,7 +540,7 @@ impl RainbowBannerAnimation {
                 right_padding_length: 0,
                 repeat_prefix_on_wrap: false,
                 text: weighted_line,
-                block_length: self.block_length,
+                block_length,
                 alignment: self.alignment,
                 block_color: None,
             }));
@@ -853,19 +552,16 @@ impl RainbowBannerAnimation {
 }
 
 impl AsRenderOperations for RainbowBannerAnimation {
-    fn as_render_operations(&self, _: &WindowSize) -> Vec<RenderOperation> {
+    fn as_render_operations(&self, dimensions: &WindowSize) -> Vec<RenderOperation> {
+        let lines = self.art.lines(dimensions, self.font_size);
         let state = self.state.lock().unwrap();
-        self.render_with_offset(state.hue_offset)
+        self.render_with_offset(&lines, state.hue_offset)
     }
 }
 
 impl RenderAsync for RainbowBannerAnimation {
     fn pollable(&self) -> Box<dyn Pollable> {
-        Box::new(RainbowAnimationPollable {
-            state: self.state.clone(),
-            loop_animation: self.loop_animation,
-            duration: self.duration,
-        })
+        Box::new(RainbowAnimationPollable::new(self.state.clone(), self.loop_animation, self.duration))
     }
 
     fn start_policy(&self) -> RenderAsyncStartPolicy {
@@ -877,17 +573,45 @@ impl RenderAsync for RainbowBannerAnimation {
             RenderAsyncStartPolicy::OnDemand
         }
     }
+
+    fn timeline(&self) -> Option<Rc<dyn RenderTimeline>> {
+        Some(Rc::new(self.clone()))
+    }
+}
+
+impl RenderTimeline for RainbowBannerAnimation {
+    fn frame_times(&self) -> Vec<f64> {
+        animation_frame_times(self.duration, self.loop_animation)
+    }
+
+    fn seek_to(&self, time: f64) {
+        self.state.lock().unwrap().seek(time, self.duration, self.loop_animation);
+    }
+
+    fn kind(&self) -> TimelineKind {
+        animation_timeline_kind(self.duration, self.loop_animation)
+    }
 }
 
-struct RainbowAnimationPollable {
+/// Drives an [AnimationState] forward in time.
+pub(crate) struct RainbowAnimationPollable {
     state: Arc<Mutex<AnimationState>>,
     loop_animation: bool,
     duration: Duration,
 }
 
+impl RainbowAnimationPollable {
+    pub(crate) fn new(state: Arc<Mutex<AnimationState>>, loop_animation: bool, duration: Duration) -> Self {
+        Self { state, loop_animation, duration }
+    }
+}
+
 impl Pollable for RainbowAnimationPollable {
     fn poll(&mut self) -> PollableState {
         let mut state = self.state.lock().unwrap();
+        if state.completed {
+            return PollableState::Done;
+        }
 
         // Initialize start time on first poll
         if state.start_time.is_none() {
@@ -979,7 +703,7 @@ impl crate::presentation::ChunkMutator for MultiBannerMutator {
 }
 
 /// A multi-line banner that renders different words based on the current context
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct MultiBannerLine {
     /// All the banner animations, one per word
     animations: Vec<RainbowBannerAnimation>,
@@ -994,6 +718,13 @@ impl MultiBannerLine {
     ) -> Self {
         Self { animations: banners, context }
     }
+
+    /// Render every word's last frame from now on, without playing them.
+    pub(crate) fn freeze(&self) {
+        for animation in &self.animations {
+            animation.freeze();
+        }
+    }
 }
 
 impl AsRenderOperations for MultiBannerLine {
@@ -1026,6 +757,26 @@ impl RenderAsync for MultiBannerLine {
             .map(|a| a.start_policy())
             .unwrap_or(RenderAsyncStartPolicy::OnDemand)
     }
+
+    fn timeline(&self) -> Option<Rc<dyn RenderTimeline>> {
+        Some(Rc::new(self.clone()))
+    }
+}
+
+impl RenderTimeline for MultiBannerLine {
+    fn frame_times(&self) -> Vec<f64> {
+        self.animations.first().map(RenderTimeline::frame_times).unwrap_or_default()
+    }
+
+    fn seek_to(&self, time: f64) {
+        for animation in &self.animations {
+            animation.seek_to(time);
+        }
+    }
+
+    fn kind(&self) -> TimelineKind {
+        self.animations.first().map(RenderTimeline::kind).unwrap_or(TimelineKind::Animation { cycle: None })
+    }
 }
 
 struct MultiBannerPollable {
@@ -1047,54 +798,31 @@ impl Pollable for MultiBannerPollable {
     }
 }
 
-/// Static (non-animated) multi-line banner renderer.
-/// Renders one of multiple pre-generated ASCII banners (one per input line),
-/// with static rainbow coloring, selected by a shared context and cycled via a mutator.
+/// A static (non-animated) banner, rendered in a single style.
 #[derive(Debug)]
-pub(crate) struct MultiBannerLineStatic {
-    /// All the banner ASCII lines, one per word
-    banners: Vec<Vec<String>>,
-    /// Block lengths per banner for proper centering
-    block_lengths: Vec<u16>,
+pub(crate) struct StaticBanner {
+    /// The banner's ASCII art
+    art: BannerArt,
     /// Alignment settings
     alignment: Alignment,
-    /// Font size
-    font_size: u8,
-    /// Shared context determining which word to display
-    context: Arc<Mutex<MultiBannerContext>>,
+    /// The style, including the font size
+    style: TextStyle,
 }
 
-impl MultiBannerLineStatic {
-    pub(crate) fn new(
-        banners: Vec<Vec<String>>,
-        block_lengths: Vec<u16>,
-        alignment: Alignment,
-        font_size: u8,
-        context: Arc<Mutex<MultiBannerContext>>,
-    ) -> Self {
-        Self { banners, block_lengths, alignment, font_size, context }
+impl StaticBanner {
+    pub(crate) fn new(art: BannerArt, alignment: Alignment, style: TextStyle) -> Self {
+        Self { art, alignment, style }
     }
 }
 
-impl AsRenderOperations for MultiBannerLineStatic {
-    fn as_render_operations(&self, _window: &WindowSize) -> Vec<RenderOperation> {
-        use crate::markdown::elements::Text;
-        use crate::markdown::text::{WeightedLine, WeightedText};
-        use crate::markdown::text_style::TextStyle;
-
-        let context = self.context.lock().unwrap();
-        let current = context.current;
-        drop(context);
-
-        let Some(lines) = self.banners.get(current) else { return vec![]; };
-        let block_length = self.block_lengths.get(current).copied().unwrap_or(0);
-
-        // Static plain rendering - no colors, just use theme defaults
-        let text_style = TextStyle::default().size(self.font_size);
+impl AsRenderOperations for StaticBanner {
+    fn as_render_operations(&self, dimensions: &WindowSize) -> Vec<RenderOperation> {
+        let lines = self.art.lines(dimensions, self.style.size);
+        let block_length = BannerArt::block_length(&lines, self.style.size, &self.alignment);
 
         let mut operations = Vec::new();
         for line in lines.iter() {
-            let text = Text::new(line, text_style);
+            let text = Text::new(line, self.style);
             let weighted_line = WeightedLine::from(vec![text]);
             operations.push(RenderOperation::RenderBlockLine(BlockLine {
                 prefix: WeightedText::from(""),
@@ -1112,6 +840,30 @@ impl AsRenderOperations for MultiBannerLineStatic {
     }
 }
 
+/// Static (non-animated) multi-line banner renderer.
+/// Renders one of multiple banners (one per input line), selected by a shared context and cycled
+/// via a mutator.
+#[derive(Debug)]
+pub(crate) struct MultiBannerLineStatic {
+    /// All the banners, one per word
+    banners: Vec<StaticBanner>,
+    /// Shared context determining which word to display
+    context: Arc<Mutex<MultiBannerContext>>,
+}
+
+impl MultiBannerLineStatic {
+    pub(crate) fn new(banners: Vec<StaticBanner>, context: Arc<Mutex<MultiBannerContext>>) -> Self {
+        Self { banners, context }
+    }
+}
+
+impl AsRenderOperations for MultiBannerLineStatic {
+    fn as_render_operations(&self, dimensions: &WindowSize) -> Vec<RenderOperation> {
+        let current = self.context.lock().unwrap().current;
+        self.banners.get(current).map(|banner| banner.as_render_operations(dimensions)).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1125,9 +877,139 @@ mod tests {
     }
 
     #[test]
-    fn test_fallback_to_standard() {
-        let generator = BannerGenerator::new("nonexistent_font_12345").expect("failed to create generator");
-        let result = generator.generate("Hi").expect("failed to generate");
-        assert!(!result.is_empty());
+    fn layout() {
+        let generator = BannerGenerator::new("standard").unwrap();
+        let smushed = art_width(&generator.generate_lines("Hello").unwrap());
+        let generator = generator.with_layout(Some(BannerLayout::Full));
+        assert!(art_width(&generator.generate_lines("Hello").unwrap()) > smushed);
+    }
+
+    #[test]
+    fn test_unknown_font_rejected() {
+        let result = BannerGenerator::new("nonexistent_font_12345");
+        assert!(matches!(result, Err(BannerError::FontUnavailable(_))));
+    }
+
+    #[test]
+    fn test_embedded_fonts() {
+        let listing = embedded_fonts_listing();
+        for name in embedded_font_names() {
+            let generator = BannerGenerator::new(&name.to_uppercase()).expect("failed to load embedded font");
+            let art = generator.generate("Hello, World! 42 $ÄÖÜäöüß").expect("failed to render");
+            assert!(art.lines().any(|line| !line.trim().is_empty()), "font {name} rendered nothing");
+            assert!(listing.contains(name), "font {name} not listed: {listing}");
+        }
+    }
+
+    #[test]
+    fn test_embedded_fit_chain() {
+        for name in DEFAULT_FIT_FONTS {
+            assert!(fonts::find(name).is_some(), "font {name} isn't embedded");
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::other_font("slant", &["presenterm-big", "standard", "presenterm-small", "presenterm-mini"])]
+    #[case::widest("presenterm-big", &["standard", "presenterm-small", "presenterm-mini"])]
+    #[case::standard("Standard", &["presenterm-small", "presenterm-mini"])]
+    #[case::narrowest("presenterm-mini", &[])]
+    fn default_fit_chain(#[case] font: &str, #[case] expected: &[&str]) {
+        assert_eq!(default_fit_fonts(font), expected);
+    }
+
+    fn fitted_art(text: &str) -> FittedBannerArt {
+        let standard = BannerGenerator::new("standard").unwrap();
+        let single_row = BannerGenerator::from_content("single", &single_row_font()).unwrap();
+        FittedBannerArt::new(text.into(), vec![Rc::new(standard), Rc::new(single_row)]).unwrap()
+    }
+
+    #[test]
+    fn fit_first_font() {
+        let art = fitted_art("hi");
+        let expected = BannerGenerator::new("standard").unwrap().generate_lines("hi").unwrap();
+        assert_eq!(art.lines(80), expected);
+    }
+
+    #[test]
+    fn fit_fallback_font() {
+        let art = fitted_art("hello world");
+        assert_eq!(art.lines(20), &["hello world"]);
+    }
+
+    #[rstest::rstest]
+    #[case::two_rows(10, &["hello big", "world"])]
+    #[case::three_rows(8, &["hello", "big", "world"])]
+    #[case::too_narrow(3, &["hello", "big", "world"])]
+    fn fit_wrap(#[case] columns: u16, #[case] expected: &[&str]) {
+        let art = fitted_art("hello big world");
+        assert_eq!(art.lines(columns), expected);
+    }
+
+    #[test]
+    fn fit_wrap_first_font() {
+        // This doesn't fit in a single row with either font but every word fits in the first one.
+        let art = fitted_art("hi hi hi hi hi");
+        let hi = BannerGenerator::new("standard").unwrap().generate_lines("hi").unwrap();
+        assert_eq!(art.lines(art_width(&hi) as u16), vec![hi.clone(); 5].concat());
+    }
+
+    #[rstest::rstest]
+    #[case::relative_path("deck/custom.flf")]
+    #[case::fonts_directory_path("other.flf")]
+    #[case::fonts_directory_name("other")]
+    #[case::toilet_font("deck/custom.tlf")]
+    #[case::toilet_font_name("toilet")]
+    fn test_font_files(#[case] font_name: &str) {
+        let root = tempfile::tempdir().expect("failed to create tempdir");
+        let themes_path = root.path().join("themes");
+        std::fs::create_dir_all(root.path().join("deck")).unwrap();
+        std::fs::create_dir_all(themes_path.join("fonts")).unwrap();
+        std::fs::write(root.path().join("deck/custom.flf"), fonts::STANDARD).unwrap();
+        std::fs::write(root.path().join("deck/custom.tlf"), fonts::STANDARD.replacen("flf2a", "tlf2a", 1)).unwrap();
+        std::fs::write(themes_path.join("fonts/other.flf"), fonts::STANDARD).unwrap();
+        std::fs::write(themes_path.join("fonts/toilet.tlf"), fonts::STANDARD.replacen("flf2a", "tlf2a", 1)).unwrap();
+
+        let resources = Resources::new(root.path(), themes_path, Default::default());
+        let generator = BannerGenerator::load(font_name, &resources, &ResourceBasePath::Presentation)
+            .expect("failed to load font");
+        let expected = BannerGenerator::new("standard").unwrap().generate("hi").unwrap();
+        assert_eq!(generator.generate("hi").unwrap(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case::missing_file("missing.flf", true)]
+    #[case::invalid_file("broken.flf", false)]
+    fn test_invalid_font_files(#[case] font_name: &str, #[case] missing: bool) {
+        let root = tempfile::tempdir().expect("failed to create tempdir");
+        std::fs::write(root.path().join("broken.flf"), "flf2a$ nope").unwrap();
+
+        let resources = Resources::new(root.path(), root.path(), Default::default());
+        let result = BannerGenerator::load(font_name, &resources, &ResourceBasePath::Presentation);
+        match result {
+            Err(BannerError::FontUnavailable(_)) => assert!(missing),
+            Err(BannerError::FontLoadFailed(..)) => assert!(!missing),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::once(false, &[0.0, 0.1, 0.2, 0.3])]
+    #[case::looping(true, &[0.0, 0.1, 0.2])]
+    fn export_frame_times(#[case] loop_animation: bool, #[case] expected: &[f64]) {
+        let times = animation_frame_times(Duration::from_millis(300), loop_animation);
+        let times: Vec<_> = times.into_iter().map(|time| (time * 1000.0).round() / 1000.0).collect();
+        assert_eq!(times, expected);
+    }
+
+    #[rstest::rstest]
+    #[case::start(0.5, false, 90.0, false)]
+    #[case::end(2.0, false, 360.0, true)]
+    #[case::past_end(3.0, false, 360.0, true)]
+    #[case::wraps_around(2.5, true, 90.0, false)]
+    fn seek(#[case] time: f64, #[case] loop_animation: bool, #[case] hue_offset: f32, #[case] completed: bool) {
+        let mut state = AnimationState::default();
+        state.seek(time, Duration::from_secs(2), loop_animation);
+        assert_eq!(state.hue_offset, hue_offset);
+        assert_eq!(state.completed, completed);
     }
 }
@@ -2,7 +2,10 @@
 
 use super::snippet::{SnippetExecutorSpec, SnippetRepr};
 use crate::{
-    code::snippet::{Snippet, SnippetLanguage},
+    code::{
+        asciinema::record::{CastWriter, record_command},
+        snippet::{Snippet, SnippetLanguage},
+    },
     config::{LanguageSnippetExecutionConfig, SnippetExecutorConfig},
 };
 use once_cell::sync::Lazy;
@@ -157,6 +160,55 @@ impl LanguageSnippetExecutor {
         Ok(())
     }
 
+    /// Executes a piece of code synchronously in a pseudo terminal, recording it into an asciicast file.
+    ///
+    /// Only the last command is recorded: any commands before it, like the ones compiling the snippet, run with
+    /// their output captured. The file is only written if all commands succeed.
+    pub(crate) fn execute_recorded(
+        &self,
+        snippet: &Snippet,
+        output_path: &Path,
+        (columns, rows): (u16, u16),
+    ) -> Result<(), CodeExecuteError> {
+        let script_dir = self.write_snippet(snippet)?;
+        let script_dir_path = script_dir.path().to_string_lossy();
+        let mut commands = self.config.commands.clone();
+        for command in commands.iter_mut().flatten() {
+            *command = command.replace("$pwd", &script_dir_path);
+        }
+        let (run_commands, setup_commands) = commands.split_last().expect("no commands");
+        for commands in setup_commands {
+            let (command, args) = commands.split_first().expect("no commands");
+            let output = process::Command::new(command)
+                .args(args)
+                .envs(&self.config.environment)
+                .current_dir(&self.cwd)
+                .output()
+                .map_err(|e| CodeExecuteError::SpawnProcess(command.clone(), e))?;
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr).to_string();
+                return Err(CodeExecuteError::Running(error));
+            }
+        }
+
+        let output_directory = output_path.parent().unwrap_or(Path::new("."));
+        std::fs::create_dir_all(output_directory).map_err(CodeExecuteError::Recording)?;
+        let output = tempfile::NamedTempFile::new_in(output_directory).map_err(CodeExecuteError::Recording)?;
+        let mut writer = CastWriter::new(io::BufWriter::new(output.as_file()), columns, rows)
+            .map_err(CodeExecuteError::Recording)?;
+        let (command, args) = run_commands.split_first().expect("no commands");
+        let mut process = process::Command::new(command);
+        process.args(args).envs(&self.config.environment).current_dir(&self.cwd);
+        let status = record_command(process, &mut writer, io::stdout(), columns, rows)
+            .map_err(|e| CodeExecuteError::SpawnProcess(command.clone(), e))?;
+        if !status.success() {
+            return Err(CodeExecuteError::Running(format!("process exited with {status}")));
+        }
+        writer.finish().and_then(|mut writer| writer.flush()).map_err(CodeExecuteError::Recording)?;
+        output.persist(output_path).map_err(|e| CodeExecuteError::Recording(e.error))?;
+        Ok(())
+    }
+
     fn write_snippet(&self, snippet: &Snippet) -> Result<TempDir, CodeExecuteError> {
         let hide_prefix = self.hidden_line_prefix.as_deref();
         let code = snippet.executable_contents(hide_prefix);
@@ -196,6 +248,9 @@ pub(crate) enum CodeExecuteError {
 
     #[error("error running process: {0}")]
     Running(String),
+
+    #[error("error recording process: {0}")]
+    Recording(io::Error),
 }
 
 /// A handle for the execution of a piece of code.
@@ -427,4 +482,47 @@ echo 'hello world'
     fn built_in_executors() {
         SnippetExecutor::new(Default::default(), PathBuf::from("./")).expect("invalid default executors");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn record_last_command_only() {
+        let commands =
+            [vec!["sh", "-c", "echo compiling; cp $pwd/snippet.sh $pwd/built.sh"], vec!["sh", "$pwd/built.sh"]];
+        let config = SnippetExecutorConfig {
+            filename: "snippet.sh".into(),
+            environment: Default::default(),
+            commands: commands.iter().map(|command| command.iter().map(ToString::to_string).collect()).collect(),
+        };
+        let executor = LanguageSnippetExecutor { hidden_line_prefix: None, config, cwd: PathBuf::from(".") };
+        let snippet = Snippet {
+            contents: "echo running".into(),
+            language: SnippetLanguage::Bash,
+            attributes: SnippetAttributes::default(),
+        };
+        let directory = tempfile::tempdir().expect("failed to create directory");
+        let path = directory.path().join("output.cast");
+        executor.execute_recorded(&snippet, &path, (20, 5)).expect("recording failed");
+
+        let cast = std::fs::read_to_string(path).expect("no recording");
+        assert!(cast.contains("running"), "{cast}");
+        assert!(!cast.contains("compiling"), "{cast}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn record_failed_setup() {
+        let config = SnippetExecutorConfig {
+            filename: "snippet.sh".into(),
+            environment: Default::default(),
+            commands: vec![vec!["false".into()], vec!["true".into()]],
+        };
+        let executor = LanguageSnippetExecutor { hidden_line_prefix: None, config, cwd: PathBuf::from(".") };
+        let snippet =
+            Snippet { contents: "".into(), language: SnippetLanguage::Bash, attributes: SnippetAttributes::default() };
+        let directory = tempfile::tempdir().expect("failed to create directory");
+        let path = directory.path().join("output.cast");
+        let result = executor.execute_recorded(&snippet, &path, (20, 5));
+        assert!(matches!(result, Err(CodeExecuteError::Running(_))), "{result:?}");
+        assert!(!path.exists());
+    }
 }
@@ -0,0 +1,418 @@
+use crate::code::snippet::BannerLayout;
+use std::collections::HashMap;
+
+// The horizontal layout bits in a font's full layout header field.
+const SMUSH_EQUAL: u32 = 1;
+const SMUSH_LOWLINE: u32 = 2;
+const SMUSH_HIERARCHY: u32 = 4;
+const SMUSH_PAIR: u32 = 8;
+const SMUSH_BIGX: u32 = 16;
+const SMUSH_HARDBLANK: u32 = 32;
+const SMUSH_RULES: u32 = 63;
+const KERNING: u32 = 64;
+const SMUSHING: u32 = 128;
+
+/// The german characters every font defines after printable ASCII and before any code tagged ones.
+const GERMAN_CHARACTERS: &[u32] = &[196, 214, 220, 228, 246, 252, 223];
+
+/// A FIGlet or TOIlet font.
+///
+/// See <http://www.jave.de/figlet/figfont.html> for the format and the layout rules.
+#[derive(Debug)]
+pub(crate) struct FigletFont {
+    hardblank: char,
+    height: usize,
+    layout: u32,
+    right_to_left: bool,
+    glyphs: HashMap<char, Glyph>,
+}
+
+impl FigletFont {
+    /// Parse a font.
+    ///
+    /// Like `figlet` itself, this stops at the first character that's missing or can't be parsed
+    /// rather than rejecting the whole font.
+    pub(crate) fn parse(contents: &str) -> Result<Self, FontParseError> {
+        let mut lines = contents.lines();
+        let header = lines.next().ok_or(FontParseError::Signature)?;
+        let mut fields = header.split_whitespace();
+        let signature = fields.next().ok_or(FontParseError::Signature)?;
+        let hardblank = match signature.strip_prefix("flf2a").or_else(|| signature.strip_prefix("tlf2a")) {
+            Some(rest) => rest.chars().next().ok_or(FontParseError::Signature)?,
+            None => return Err(FontParseError::Signature),
+        };
+        let mut numbers = fields.map(|field| field.parse::<i64>().map_err(|_| FontParseError::Header(field.into())));
+        let mut next = |name: &'static str| numbers.next().unwrap_or(Err(FontParseError::MissingField(name)));
+        let height = next("height")?;
+        let _baseline = next("baseline")?;
+        let _max_length = next("max length")?;
+        let old_layout = next("old layout")?;
+        let comment_lines = next("comment lines")?;
+        let print_direction = next("print direction").unwrap_or(0);
+        let full_layout = next("full layout").ok();
+        if height < 1 {
+            return Err(FontParseError::Header(height.to_string()));
+        }
+        let height = height as usize;
+        let layout = match full_layout {
+            Some(layout) => layout as u32 & (SMUSH_RULES | KERNING | SMUSHING),
+            None if old_layout < 0 => 0,
+            None if old_layout == 0 => KERNING,
+            None => (old_layout as u32 & SMUSH_RULES) | SMUSHING,
+        };
+
+        let mut lines = lines.skip(comment_lines.max(0) as usize);
+        let mut glyphs = HashMap::new();
+        for code in (32..127).chain(GERMAN_CHARACTERS.iter().copied()) {
+            let Some(glyph) = Glyph::parse(&mut lines, height) else {
+                break;
+            };
+            if let Some(c) = char::from_u32(code) {
+                glyphs.insert(c, glyph);
+            }
+        }
+        while let Some(tag) = lines.next() {
+            let Some(code) = tag.split_whitespace().next().and_then(Self::parse_code) else {
+                break;
+            };
+            let Some(glyph) = Glyph::parse(&mut lines, height) else {
+                break;
+            };
+            // Negative codes are only meaningful to translation tables, which aren't supported.
+            if let Some(c) = u32::try_from(code).ok().and_then(char::from_u32) {
+                glyphs.insert(c, glyph);
+            }
+        }
+        Ok(Self { hardblank, height, layout, right_to_left: print_direction == 1, glyphs })
+    }
+
+    /// Parse a code tag's code, which can be decimal, octal with a leading 0 or hex with a
+    /// leading 0x.
+    fn parse_code(code: &str) -> Option<i64> {
+        let (negative, code) = match code.strip_prefix('-') {
+            Some(code) => (true, code),
+            None => (false, code),
+        };
+        let code = if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+            i64::from_str_radix(hex, 16).ok()?
+        } else if code.len() > 1 && code.starts_with('0') {
+            i64::from_str_radix(&code[1..], 8).ok()?
+        } else {
+            code.parse().ok()?
+        };
+        Some(if negative { -code } else { code })
+    }
+
+    /// Render a piece of text using the given layout, or the font's own one if there's none.
+    ///
+    /// Characters the font doesn't define are rendered using its missing character glyph, if it
+    /// has one, and skipped otherwise. Returns `None` if nothing in the text can be rendered.
+    pub(crate) fn render(&self, text: &str, layout: Option<BannerLayout>) -> Option<Vec<String>> {
+        let mode = match layout {
+            None => self.layout,
+            Some(BannerLayout::Full) => 0,
+            Some(BannerLayout::Kerning) => KERNING,
+            Some(BannerLayout::Smushing) => (self.layout & SMUSH_RULES) | SMUSHING,
+        };
+        let mut renderer = Renderer {
+            mode,
+            hardblank: self.hardblank,
+            right_to_left: self.right_to_left,
+            rows: vec![Vec::new(); self.height],
+            previous_width: 0,
+        };
+        let mut rendered = false;
+        for c in text.chars() {
+            if let Some(glyph) = self.glyphs.get(&c).or_else(|| self.glyphs.get(&'\0')) {
+                renderer.push(glyph);
+                rendered = true;
+            }
+        }
+        rendered.then(|| renderer.finish())
+    }
+}
+
+/// A character in a font.
+#[derive(Debug)]
+struct Glyph {
+    rows: Vec<Vec<char>>,
+    width: usize,
+}
+
+impl Glyph {
+    fn parse<'a, I: Iterator<Item = &'a str>>(lines: &mut I, height: usize) -> Option<Self> {
+        let mut rows = Vec::new();
+        for _ in 0..height {
+            // Every row ends with one or more endmarks, which are whatever its last character is.
+            let row = lines.next()?.trim_end();
+            let row = match row.chars().last() {
+                Some(endmark) => row.trim_end_matches(endmark),
+                None => row,
+            };
+            rows.push(row.chars().collect::<Vec<_>>());
+        }
+        // Rows should all be the same width but not every font is careful about that.
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, ' ');
+        }
+        Some(Self { rows, width })
+    }
+}
+
+/// Lays out the glyphs for a piece of text one after the other.
+struct Renderer {
+    mode: u32,
+    hardblank: char,
+    right_to_left: bool,
+    rows: Vec<Vec<char>>,
+    previous_width: usize,
+}
+
+impl Renderer {
+    fn push(&mut self, glyph: &Glyph) {
+        let amount = self.overlap(glyph);
+        let rows = std::mem::take(&mut self.rows);
+        for (row, glyph_row) in rows.iter().zip(&glyph.rows) {
+            let (left, right) = if self.right_to_left { (glyph_row, row) } else { (row, glyph_row) };
+            // The first glyph can overlap the empty text, dropping its leading blank columns.
+            let start = left.len().saturating_sub(amount);
+            let dropped = amount.saturating_sub(left.len());
+            let mut merged = left[..start].to_vec();
+            for (l, r) in left[start..].iter().zip(&right[dropped..]) {
+                merged.push(self.smush(*l, *r, glyph.width).unwrap_or(*r));
+            }
+            merged.extend_from_slice(&right[amount..]);
+            self.rows.push(merged);
+        }
+        self.previous_width = glyph.width;
+    }
+
+    /// The number of columns a glyph can be moved into the text rendered so far.
+    fn overlap(&self, glyph: &Glyph) -> usize {
+        if self.mode & (KERNING | SMUSHING) == 0 {
+            return 0;
+        }
+        let mut amount = match self.right_to_left {
+            true => glyph.width.min(self.rows.first().map(Vec::len).unwrap_or(0)),
+            false => glyph.width,
+        };
+        for (row, glyph_row) in self.rows.iter().zip(&glyph.rows) {
+            let (left, right) = if self.right_to_left { (glyph_row, row) } else { (row, glyph_row) };
+            let (left_boundary, left_char) = match left.iter().rposition(|c| *c != ' ') {
+                Some(index) => (index, Some(left[index])),
+                None => (0, None),
+            };
+            let (right_boundary, right_char) = match right.iter().position(|c| *c != ' ') {
+                Some(index) => (index, Some(right[index])),
+                None => (right.len(), None),
+            };
+            let mut row_amount = (right_boundary + left.len()) as isize - left_boundary as isize - 1;
+            match (left_char, right_char) {
+                (None, _) => row_amount += 1,
+                (Some(l), Some(r)) if self.smush(l, r, glyph.width).is_some() => row_amount += 1,
+                _ => (),
+            }
+            amount = amount.min(row_amount.max(0) as usize);
+        }
+        amount
+    }
+
+    /// Merge two overlapping characters into one, if the layout allows it.
+    fn smush(&self, l: char, r: char, width: usize) -> Option<char> {
+        let Self { mode, hardblank, right_to_left, previous_width, .. } = *self;
+        if l == ' ' {
+            return Some(r);
+        }
+        if r == ' ' {
+            return Some(l);
+        }
+        if previous_width < 2 || width < 2 || mode & SMUSHING == 0 {
+            return None;
+        }
+        if mode & SMUSH_RULES == 0 {
+            // Universal smushing: visible characters win over hardblanks, otherwise the latter
+            // character in the text does.
+            return Some(match (l, r) {
+                (l, r) if l == hardblank => r,
+                (l, r) if r == hardblank => l,
+                (l, _) if right_to_left => l,
+                (_, r) => r,
+            });
+        }
+        if mode & SMUSH_HARDBLANK != 0 && l == hardblank && r == hardblank {
+            return Some(l);
+        }
+        if l == hardblank || r == hardblank {
+            return None;
+        }
+        if mode & SMUSH_EQUAL != 0 && l == r {
+            return Some(l);
+        }
+        if mode & SMUSH_LOWLINE != 0 {
+            const REPLACEMENTS: &str = "|/\\[]{}()<>";
+            if l == '_' && REPLACEMENTS.contains(r) {
+                return Some(r);
+            }
+            if r == '_' && REPLACEMENTS.contains(l) {
+                return Some(l);
+            }
+        }
+        if mode & SMUSH_HIERARCHY != 0 {
+            const CLASSES: &[&str] = &["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |c| CLASSES.iter().position(|class| class.contains(c));
+            if let (Some(left_class), Some(right_class)) = (class(l), class(r)) {
+                if left_class < right_class {
+                    return Some(r);
+                }
+                if right_class < left_class {
+                    return Some(l);
+                }
+            }
+        }
+        if mode & SMUSH_PAIR != 0
+            && matches!((l, r), ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '('))
+        {
+            return Some('|');
+        }
+        if mode & SMUSH_BIGX != 0 {
+            match (l, r) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => (),
+            }
+        }
+        None
+    }
+
+    fn finish(self) -> Vec<String> {
+        let hardblank = self.hardblank;
+        self.rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| if c == hardblank { ' ' } else { c }).collect())
+            .collect()
+    }
+}
+
+/// An error parsing a font.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FontParseError {
+    #[error("not a FIGlet or TOIlet font")]
+    Signature,
+
+    #[error("invalid header field '{0}'")]
+    Header(String),
+
+    #[error("header has no {0}")]
+    MissingField(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const STANDARD: &str = include_str!("../../fonts/standard.flf");
+
+    /// A single row font where every character is itself, followed by the given code tagged ones.
+    fn single_row_font(header: &str, tagged: &str) -> FigletFont {
+        let mut font = format!("{header}\n");
+        for c in (32..127_u8).map(char::from).chain("ÄÖÜäöüß".chars()) {
+            font.push_str(&format!("{}@\n", if c == ' ' { '$' } else { c }));
+        }
+        font.push_str(tagged);
+        FigletFont::parse(&font).expect("invalid font")
+    }
+
+    fn render(font: &FigletFont, text: &str, layout: Option<BannerLayout>) -> Vec<String> {
+        font.render(text, layout).expect("nothing rendered")
+    }
+
+    #[test]
+    fn font_layout() {
+        let font = FigletFont::parse(STANDARD).unwrap();
+        let expected = &[
+            " _   _      _ _       ",
+            "| | | | ___| | | ___  ",
+            "| |_| |/ _ \\ | |/ _ \\ ",
+            "|  _  |  __/ | | (_) |",
+            "|_| |_|\\___|_|_|\\___/ ",
+            "                      ",
+        ];
+        assert_eq!(render(&font, "Hello", None), expected);
+        assert_eq!(render(&font, "Hello", Some(BannerLayout::Smushing)), expected);
+    }
+
+    #[rstest]
+    #[case::full(
+        BannerLayout::Full,
+        &["  _   _   _ ", " | | | | (_)", " | |_| | | |", " |  _  | | |", " |_| |_| |_|", "            "]
+    )]
+    #[case::kerning(
+        BannerLayout::Kerning,
+        &[" _   _  _ ", "| | | |(_)", "| |_| || |", "|  _  || |", "|_| |_||_|", "          "]
+    )]
+    fn layouts(#[case] layout: BannerLayout, #[case] expected: &[&str]) {
+        let font = FigletFont::parse(STANDARD).unwrap();
+        assert_eq!(render(&font, "Hi", Some(layout)), expected);
+    }
+
+    #[test]
+    fn right_to_left() {
+        let font = single_row_font("flf2a$ 1 1 2 -1 0 1", "");
+        assert_eq!(render(&font, "abc d", None), &["d cba"]);
+    }
+
+    #[test]
+    fn code_tags() {
+        let tagged = "0x100 hex\nh@\n0401 octal\no@\n300\nd@\n-2 negative\nn@\n0 missing\n?@\n";
+        let font = single_row_font("flf2a$ 1 1 2 -1 0", tagged);
+        assert_eq!(render(&font, "\u{100}\u{101}\u{12c}", None), &["hod"]);
+        assert_eq!(render(&font, "a\u{4e00}", None), &["a?"]);
+    }
+
+    #[test]
+    fn missing_characters_skipped() {
+        let font = single_row_font("flf2a$ 1 1 2 -1 0", "");
+        assert_eq!(render(&font, "a\u{4e00}b", None), &["ab"]);
+        assert!(font.render("\u{4e00}", None).is_none());
+        assert!(font.render("", None).is_none());
+    }
+
+    #[test]
+    fn lenient_glyphs() {
+        // Uneven rows, trailing whitespace after endmarks and a font that ends early.
+        let font = FigletFont::parse("flf2a$ 2 1 4 -1 1\ncomment\n$@\n$@@\n!!@   \n!@@\n\"#\n##\n").unwrap();
+        assert_eq!(render(&font, "!\" ", None), &["!!\" ", "!   "]);
+        assert!(font.render("#", None).is_none());
+    }
+
+    #[rstest]
+    #[case::universal(SMUSHING, '|', '/', Some('/'))]
+    #[case::universal_hardblank(SMUSHING, '|', '$', Some('|'))]
+    #[case::equal(SMUSHING | SMUSH_EQUAL, '|', '|', Some('|'))]
+    #[case::not_equal(SMUSHING | SMUSH_EQUAL, '|', '/', None)]
+    #[case::lowline(SMUSHING | SMUSH_LOWLINE, '_', '/', Some('/'))]
+    #[case::hierarchy(SMUSHING | SMUSH_HIERARCHY, '}', '/', Some('}'))]
+    #[case::same_class(SMUSHING | SMUSH_HIERARCHY, '/', '\\', None)]
+    #[case::pair(SMUSHING | SMUSH_PAIR, ']', '[', Some('|'))]
+    #[case::big_x(SMUSHING | SMUSH_BIGX, '\\', '/', Some('Y'))]
+    #[case::hardblank(SMUSHING | SMUSH_HARDBLANK, '$', '$', Some('$'))]
+    #[case::rule_hardblank(SMUSHING | SMUSH_EQUAL, '$', '$', None)]
+    #[case::kerning(KERNING, '|', '|', None)]
+    #[case::blank(KERNING, ' ', '|', Some('|'))]
+    fn smush(#[case] mode: u32, #[case] l: char, #[case] r: char, #[case] expected: Option<char>) {
+        let renderer = Renderer { mode, hardblank: '$', right_to_left: false, rows: Vec::new(), previous_width: 2 };
+        assert_eq!(renderer.smush(l, r, 2), expected);
+    }
+
+    #[rstest]
+    #[case::signature("flf2 1 1 2 -1 0")]
+    #[case::no_height("flf2a$")]
+    #[case::invalid_field("flf2a$ 1 x 2 -1 0")]
+    #[case::zero_height("flf2a$ 0 0 2 -1 0")]
+    fn invalid(#[case] header: &str) {
+        assert!(FigletFont::parse(header).is_err());
+    }
+}
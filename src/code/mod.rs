@@ -2,6 +2,7 @@ pub(crate) mod animations;
 pub(crate) mod asciinema;
 pub(crate) mod banner;
 pub(crate) mod execute;
+pub(crate) mod figlet;
 pub(crate) mod highlighting;
 pub(crate) mod padding;
 pub(crate) mod snippet;
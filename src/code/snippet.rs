@@ -291,10 +291,21 @@ impl SnippetParser {
                 }
                 AsciinemaLoop(loop_mode) => attributes.asciinema_loop = loop_mode,
                 AsciinemaStart(start_mode) => attributes.asciinema_start = start_mode,
+                AsciinemaProgress => attributes.asciinema_progress = true,
+                AsciinemaSpeed(speed) => attributes.asciinema_timing.speed = Some(speed),
+                AsciinemaIdleLimit(limit) => attributes.asciinema_timing.idle_limit = Some(limit),
+                AsciinemaRange(range) => attributes.asciinema_timing.range = Some(range),
+                AsciinemaPoster(poster) => attributes.asciinema_timing.poster = Some(poster),
+                Record(path) => attributes.record = Some(path),
+                Fit(fonts) => attributes.fit = Some(fonts),
+                Layout(layout) => attributes.layout = Some(layout),
             };
             processed_attributes.push(discriminant);
             input = rest;
         }
+        if attributes.record.is_some() && !matches!(attributes.execution, SnippetExec::AcquireTerminal(_)) {
+            return Err(SnippetBlockParseError::RecordWithoutAcquireTerminal);
+        }
         if attributes.highlight_groups.is_empty() {
             attributes.highlight_groups.push(HighlightGroup::new(vec![Highlight::All]));
         }
@@ -318,6 +329,8 @@ impl SnippetParser {
                     "acquire_terminal" => SnippetAttribute::AcquireTerminal(SnippetExecutorSpec::default()),
                     "loop" => SnippetAttribute::AnimationLoop(true),
                     "once" => SnippetAttribute::AnimationLoop(false),
+                    "progress" => SnippetAttribute::AsciinemaProgress,
+                    "fit" => SnippetAttribute::Fit(Vec::new()),
                     // new label
                     "static" => SnippetAttribute::Animation(BannerAnimation::None),
                     // backwards compatibility
@@ -359,28 +372,12 @@ impl SnippetParser {
                                 }
                             },
                             "animate" => match parameter {
-                            "rainbow" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Rainbow),
-                            "flash" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Flash),
-                            "wave" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Wave),
-                            "iris" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Iris),
-                            "plasma" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Plasma),
-                            "scanner" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Scanner),
-                            "matrix" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Matrix),
-                            "neon" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Neon),
-                            "kaleidoscope" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Kaleidoscope),
-                            "sepia" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Sepia),
-                            "prism" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Prism),
-                            "glitch" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Glitch),
-                            "breathe" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Breathe),
-                            "fire" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Fire),
-                            "aurora" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Aurora),
-                            "crt" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Crt),
-                            "typewriter" => SnippetAttribute::AnimationStyle(BannerAnimationStyle::Typewriter),
-                                _ => {
+                                "" => {
                                     return Err(SnippetBlockParseError::InvalidToken(
                                         Self::next_identifier(input).into(),
                                     ));
                                 }
+                                name => SnippetAttribute::AnimationStyle(name.into()),
                             },
                             "play" => match parameter {
                                 "once" => SnippetAttribute::AsciinemaLoop(AsciinemaLoop::Once),
@@ -391,6 +388,30 @@ impl SnippetParser {
                                     ));
                                 }
                             },
+                            "speed" => SnippetAttribute::AsciinemaSpeed(Self::parse_seconds("speed", parameter)?),
+                            "idle" => SnippetAttribute::AsciinemaIdleLimit(Self::parse_seconds("idle", parameter)?),
+                            "range" => SnippetAttribute::AsciinemaRange(Self::parse_time_range(parameter)?),
+                            "poster" => SnippetAttribute::AsciinemaPoster(Self::parse_poster_frame(parameter)?),
+                            "record" if !parameter.is_empty() => SnippetAttribute::Record(parameter.into()),
+                            "fit" => {
+                                let fonts: Vec<String> = parameter.split(',').map(String::from).collect();
+                                if fonts.iter().any(String::is_empty) {
+                                    return Err(SnippetBlockParseError::InvalidToken(
+                                        Self::next_identifier(input).into(),
+                                    ));
+                                }
+                                SnippetAttribute::Fit(fonts)
+                            }
+                            "layout" => match parameter {
+                                "full" => SnippetAttribute::Layout(BannerLayout::Full),
+                                "kern" => SnippetAttribute::Layout(BannerLayout::Kerning),
+                                "smush" => SnippetAttribute::Layout(BannerLayout::Smushing),
+                                _ => {
+                                    return Err(SnippetBlockParseError::InvalidToken(
+                                        Self::next_identifier(input).into(),
+                                    ));
+                                }
+                            },
                             "start" => match parameter {
                                 "wait" => SnippetAttribute::AsciinemaStart(AsciinemaStart::Wait),
                                 "auto" => SnippetAttribute::AsciinemaStart(AsciinemaStart::Auto),
@@ -416,6 +437,37 @@ impl SnippetParser {
         Ok((attribute, input))
     }
 
+    fn parse_seconds(attribute: &'static str, input: &str) -> ParseResult<f64> {
+        match input.parse::<f64>() {
+            Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+            _ => Err(SnippetBlockParseError::InvalidNumber(attribute, input.into())),
+        }
+    }
+
+    fn parse_time_range(input: &str) -> ParseResult<TimeRange> {
+        let invalid = || SnippetBlockParseError::InvalidNumber("range", input.into());
+        let (start, end) = input.split_once('-').ok_or_else(invalid)?;
+        let start = match start {
+            "" => 0.0,
+            start => start.parse::<f64>().ok().filter(|start| start.is_finite() && *start >= 0.0).ok_or_else(invalid)?,
+        };
+        let end = match end {
+            "" => None,
+            end => Some(end.parse::<f64>().ok().filter(|end| end.is_finite() && *end > start).ok_or_else(invalid)?),
+        };
+        Ok(TimeRange { start, end })
+    }
+
+    fn parse_poster_frame(input: &str) -> ParseResult<PosterFrame> {
+        match input {
+            "end" => Ok(PosterFrame::End),
+            _ => match input.parse::<f64>() {
+                Ok(time) if time.is_finite() && time >= 0.0 => Ok(PosterFrame::Time(time)),
+                _ => Err(SnippetBlockParseError::InvalidNumber("poster", input.into())),
+            },
+        }
+    }
+
     fn parse_highlight_groups(input: &str) -> ParseResult<(Vec<HighlightGroup>, &str)> {
         use SnippetBlockParseError::InvalidHighlightedLines;
         let Some((head, tail)) = input.split_once('}') else {
@@ -498,6 +550,12 @@ pub enum SnippetBlockParseError {
 
     #[error("attribute {0} can only be set in +render blocks")]
     NotRenderSnippet(&'static str),
+
+    #[error("invalid {0}: {1}")]
+    InvalidNumber(&'static str, String),
+
+    #[error("+record can only be used along with +acquire_terminal")]
+    RecordWithoutAcquireTerminal,
 }
 
 #[derive(EnumDiscriminants)]
@@ -520,6 +578,14 @@ enum SnippetAttribute {
     AnimationLoop(bool),
     AsciinemaLoop(AsciinemaLoop),
     AsciinemaStart(AsciinemaStart),
+    AsciinemaProgress,
+    AsciinemaSpeed(f64),
+    AsciinemaIdleLimit(f64),
+    AsciinemaRange(TimeRange),
+    AsciinemaPoster(PosterFrame),
+    Record(PathBuf),
+    Fit(Vec<String>),
+    Layout(BannerLayout),
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -658,11 +724,11 @@ impl FromStr for SnippetLanguage {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use SnippetLanguage::*;
 
-        // Handle banner:font syntax
-        let lower = s.to_lowercase();
-        if let Some(font) = lower.strip_prefix("banner:") {
-            return Ok(Banner { font: font.to_string() });
+        // Handle banner:font syntax, keeping the font's case as it can be a path
+        if let Some(prefix) = s.get(.."banner:".len()).filter(|prefix| prefix.eq_ignore_ascii_case("banner:")) {
+            return Ok(Banner { font: s[prefix.len()..].to_string() });
         }
+        let lower = s.to_lowercase();
 
         let language = match lower.as_str() {
             "ascii" => Ascii,
@@ -777,6 +843,22 @@ pub(crate) struct SnippetAttributes {
 
     /// Asciinema playback start mode.
     pub(crate) asciinema_start: AsciinemaStart,
+
+    /// Whether to show the asciinema playback progress under the recording.
+    pub(crate) asciinema_progress: bool,
+
+    /// The speed, portion and poster frame of an asciinema recording.
+    pub(crate) asciinema_timing: AsciinemaTiming,
+
+    /// The asciinema file the session is recorded into, and replayed from once it exists.
+    pub(crate) record: Option<PathBuf>,
+
+    /// Whether a banner is fitted to the width it's rendered in, along with the fonts it falls
+    /// back to, in order, when it doesn't fit in its own.
+    pub(crate) fit: Option<Vec<String>>,
+
+    /// How a banner's characters are laid out, if not the way its font says.
+    pub(crate) layout: Option<BannerLayout>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -818,22 +900,56 @@ pub(crate) enum BannerAnimationStyle {
     Aurora,
     Crt,
     Typewriter,
+    /// An animation defined in the front matter or the themes directory, by name.
+    Custom(String),
+}
+
+impl From<&str> for BannerAnimationStyle {
+    fn from(name: &str) -> Self {
+        match name {
+            "rainbow" => Self::Rainbow,
+            "flash" => Self::Flash,
+            "wave" => Self::Wave,
+            "iris" => Self::Iris,
+            "plasma" => Self::Plasma,
+            "scanner" => Self::Scanner,
+            "matrix" => Self::Matrix,
+            "neon" => Self::Neon,
+            "kaleidoscope" => Self::Kaleidoscope,
+            "sepia" => Self::Sepia,
+            "prism" => Self::Prism,
+            "glitch" => Self::Glitch,
+            "breathe" => Self::Breathe,
+            "fire" => Self::Fire,
+            "aurora" => Self::Aurora,
+            "crt" => Self::Crt,
+            "typewriter" => Self::Typewriter,
+            // Anything else is looked up when building the presentation.
+            name => Self::Custom(name.into()),
+        }
+    }
 }
 
 /// Animation mode for banners
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) enum BannerAnimation {
     /// No animation, static rainbow coloring (aka `+static`)
+    #[default]
     None,
     /// Animated banner, with a style and loop flag
     Animated { style: BannerAnimationStyle, loop_animation: bool },
 }
 
-impl Default for BannerAnimation {
-    fn default() -> Self {
-        // Default to no animation to render static banners/ascii unless explicitly requested
-        Self::None
-    }
+/// How the characters in a banner are put next to each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BannerLayout {
+    /// Every character takes up its full width.
+    Full,
+    /// Characters are moved together until they touch.
+    Kerning,
+    /// Characters are moved together until they overlap by one column, merging the overlapping
+    /// characters using the font's rules.
+    Smushing,
 }
 
 /// Asciinema playback loop mode
@@ -856,6 +972,42 @@ pub(crate) enum AsciinemaStart {
     Auto,
 }
 
+/// Asciinema playback timing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct AsciinemaTiming {
+    /// The playback speed multiplier.
+    pub(crate) speed: Option<f64>,
+
+    /// The maximum number of seconds to wait between events.
+    pub(crate) idle_limit: Option<f64>,
+
+    /// The part of the recording to play.
+    pub(crate) range: Option<TimeRange>,
+
+    /// The frame to show when exporting the presentation.
+    pub(crate) poster: Option<PosterFrame>,
+}
+
+// All values are validated to be finite when parsed.
+impl Eq for AsciinemaTiming {}
+
+/// The frame in a recording that represents it when it can't be played back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PosterFrame {
+    /// The frame at the given number of seconds into the recording.
+    Time(f64),
+
+    /// The last frame in the recording.
+    End,
+}
+
+/// A range of time within a recording, in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TimeRange {
+    pub(crate) start: f64,
+    pub(crate) end: Option<f64>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct HighlightGroup(Vec<Highlight>);
 
@@ -943,6 +1095,13 @@ mod test {
         assert_eq!(parse_language("potato"), SnippetLanguage::Unknown("potato".to_string()));
     }
 
+    #[rstest]
+    #[case::name("banner:Slant", "Slant")]
+    #[case::path("Banner:./fonts/MyCorp.flf", "./fonts/MyCorp.flf")]
+    fn banner_font(#[case] input: &str, #[case] font: &str) {
+        assert_eq!(parse_language(input), SnippetLanguage::Banner { font: font.to_string() });
+    }
+
     #[test]
     fn no_attributes() {
         assert_eq!(parse_language("rust"), SnippetLanguage::Rust);
@@ -962,6 +1121,50 @@ mod test {
         assert!(attributes.line_numbers);
     }
 
+    #[test]
+    fn record() {
+        let attributes = parse_attributes("bash +record:casts/demo.cast +acquire_terminal");
+        assert_eq!(attributes.execution, SnippetExec::AcquireTerminal(Default::default()));
+        assert_eq!(attributes.record, Some(PathBuf::from("casts/demo.cast")));
+    }
+
+    #[rstest]
+    #[case::no_fonts("banner +fit", Some(vec![]))]
+    #[case::fonts("banner:big +fit:standard,small", Some(vec!["standard".into(), "small".into()]))]
+    #[case::not_fitted("banner", None)]
+    fn fit(#[case] input: &str, #[case] expected: Option<Vec<String>>) {
+        assert_eq!(parse_attributes(input).fit, expected);
+    }
+
+    #[rstest]
+    #[case::empty("banner +fit:")]
+    #[case::empty_font("banner +fit:small,,mini")]
+    fn invalid_fit(#[case] input: &str) {
+        assert!(try_parse_attributes(input).is_err());
+    }
+
+    #[rstest]
+    #[case::full("banner +layout:full", Some(BannerLayout::Full))]
+    #[case::kern("banner +layout:kern", Some(BannerLayout::Kerning))]
+    #[case::smush("banner:big +layout:smush", Some(BannerLayout::Smushing))]
+    #[case::font_default("banner", None)]
+    fn layout(#[case] input: &str, #[case] expected: Option<BannerLayout>) {
+        assert_eq!(parse_attributes(input).layout, expected);
+    }
+
+    #[rstest]
+    #[case::empty("banner +layout:")]
+    #[case::unknown("banner +layout:squash")]
+    fn invalid_layout(#[case] input: &str) {
+        assert!(try_parse_attributes(input).is_err());
+    }
+
+    #[test]
+    fn record_without_acquire_terminal() {
+        let result = try_parse_attributes("bash +exec +record:demo.cast");
+        assert!(matches!(result, Err(SnippetBlockParseError::RecordWithoutAcquireTerminal)), "{result:?}");
+    }
+
     #[test]
     fn acquire_terminal() {
         let attributes = parse_attributes("bash +acquire_terminal +exec");
@@ -1108,4 +1311,42 @@ println!("Hello world");
         let attributes = parse_attributes(&format!("bash +{input}"));
         assert_eq!(attributes.expected_execution_result, expected);
     }
+
+    #[test]
+    fn asciinema_progress() {
+        let attributes = parse_attributes("asciinema +start:auto +progress");
+        assert_eq!(attributes.asciinema_start, AsciinemaStart::Auto);
+        assert!(attributes.asciinema_progress);
+    }
+
+    #[test]
+    fn asciinema_timing() {
+        let attributes = parse_attributes("asciinema +speed:2 +idle:1.5 +range:12.0-40.5 +poster:3");
+        let expected = AsciinemaTiming {
+            speed: Some(2.0),
+            idle_limit: Some(1.5),
+            range: Some(TimeRange { start: 12.0, end: Some(40.5) }),
+            poster: Some(PosterFrame::Time(3.0)),
+        };
+        assert_eq!(attributes.asciinema_timing, expected);
+    }
+
+    #[rstest]
+    #[case::open_end("10-", TimeRange { start: 10.0, end: None })]
+    #[case::open_start("-5.5", TimeRange { start: 0.0, end: Some(5.5) })]
+    fn asciinema_partial_range(#[case] input: &str, #[case] expected: TimeRange) {
+        let attributes = parse_attributes(&format!("asciinema +range:{input}"));
+        assert_eq!(attributes.asciinema_timing.range, Some(expected));
+    }
+
+    #[rstest]
+    #[case::zero_speed("+speed:0")]
+    #[case::negative_idle("+idle:-1")]
+    #[case::not_a_number("+speed:fast")]
+    #[case::reversed_range("+range:10-5")]
+    #[case::no_range_separator("+range:10")]
+    #[case::poster_start("+poster:start")]
+    fn invalid_asciinema_timing(#[case] input: &str) {
+        try_parse_attributes(&format!("asciinema {input}")).expect_err("parse succeeded");
+    }
 }
@@ -3,15 +3,51 @@ use crate::config::KeyBindingsConfig;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, poll, read};
 use std::{fmt, io, iter, mem, str::FromStr, time::Duration};
 
+/// Where keyboard input is sent to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum InputFocus {
+    /// Every key is matched against the key bindings.
+    #[default]
+    Presentation,
+
+    /// A modal is open: navigation keys go to it and anything else is matched against the key
+    /// bindings.
+    Modal,
+
+    /// A modal is taking text input: every key that isn't a control sequence goes to it.
+    Text,
+}
+
+/// A key pressed while a modal has the input focus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ModalKey {
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Escape,
+    Char(char),
+}
+
 /// A keyboard command listener.
 pub struct KeyboardListener {
     bindings: CommandKeyBindings,
     events: Vec<KeyEvent>,
+    focus: InputFocus,
 }
 
 impl KeyboardListener {
     pub fn new(bindings: CommandKeyBindings) -> Self {
-        Self { bindings, events: Vec::new() }
+        Self { bindings, events: Vec::new(), focus: InputFocus::default() }
+    }
+
+    /// Set where keyboard input is sent to.
+    pub(crate) fn set_input_focus(&mut self, focus: InputFocus) {
+        if focus != self.focus {
+            // Any partially typed key binding doesn't make sense anymore.
+            self.events.clear();
+            self.focus = focus;
+        }
     }
 
     /// Polls for the next input command coming from the keyboard.
@@ -25,10 +61,13 @@ impl KeyboardListener {
         let (command, events) = match read()? {
             // Ignore release events
             Event::Key(event) if event.kind == KeyEventKind::Release => (None, events),
-            Event::Key(event) => {
-                events.push(event);
-                self.match_events(events)
-            }
+            Event::Key(event) => match self.modal_key(&event) {
+                Some(key) => (Some(Command::ModalInput(key)), Vec::new()),
+                None => {
+                    events.push(event);
+                    self.match_events(events)
+                }
+            },
             Event::Resize(..) => (Some(Command::Redraw), events),
             _ => (None, vec![]),
         };
@@ -38,11 +77,38 @@ impl KeyboardListener {
 
     fn match_events(&self, events: Vec<KeyEvent>) -> (Option<Command>, Vec<KeyEvent>) {
         match self.bindings.apply(&events) {
-            InputAction::Emit(command) => (Some(command), Vec::new()),
+            InputAction::Emit(command) => (Some(self.redirect_command(command)), Vec::new()),
             InputAction::Buffer => (None, events),
             InputAction::Reset => (None, Vec::new()),
         }
     }
+
+    fn modal_key(&self, event: &KeyEvent) -> Option<ModalKey> {
+        if event.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return None;
+        }
+        let key = match (self.focus, event.code) {
+            (InputFocus::Presentation, _) => return None,
+            (_, KeyCode::Up) => ModalKey::Up,
+            (_, KeyCode::Down) => ModalKey::Down,
+            (_, KeyCode::Enter) => ModalKey::Enter,
+            (InputFocus::Modal, KeyCode::Char('/')) => ModalKey::Char('/'),
+            (InputFocus::Text, KeyCode::Char(c)) => ModalKey::Char(c),
+            (InputFocus::Text, KeyCode::Backspace) => ModalKey::Backspace,
+            (InputFocus::Text, KeyCode::Esc) => ModalKey::Escape,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    /// While a modal is open, moving forwards and backwards moves within the modal instead.
+    fn redirect_command(&self, command: Command) -> Command {
+        match (self.focus, command) {
+            (InputFocus::Modal, Command::Next) => Command::ModalInput(ModalKey::Down),
+            (InputFocus::Modal, Command::Previous) => Command::ModalInput(ModalKey::Up),
+            (_, command) => command,
+        }
+    }
 }
 
 enum InputAction {
@@ -96,7 +162,13 @@ impl CommandKeyBindings {
             ToggleLayoutGrid => Command::ToggleLayoutGrid,
             CloseModal => Command::CloseModal,
             SkipPauses => Command::SkipPauses,
+            TogglePlayback => Command::TogglePlayback,
+            SeekPlaybackForward => Command::SeekPlaybackForward,
+            SeekPlaybackBackward => Command::SeekPlaybackBackward,
+            SpeedUpPlayback => Command::SpeedUpPlayback,
+            SlowDownPlayback => Command::SlowDownPlayback,
             GoToSlideChunk => panic!("go to slide chunk is not configurable"),
+            ModalInput => panic!("modal input is not configurable"),
         };
         InputAction::Emit(command)
     }
@@ -143,6 +215,11 @@ impl TryFrom<KeyBindingsConfig> for CommandKeyBindings {
             exit,
             suspend,
             skip_pauses,
+            toggle_playback,
+            seek_playback_forward,
+            seek_playback_backward,
+            speed_up_playback,
+            slow_down_playback,
         } = config;
         let bindings: Vec<_> = iter::empty()
             .chain(zip(CommandDiscriminants::Next, next))
@@ -161,6 +238,11 @@ impl TryFrom<KeyBindingsConfig> for CommandKeyBindings {
             .chain(zip(CommandDiscriminants::RenderAsyncOperations, execute_code))
             .chain(zip(CommandDiscriminants::CloseModal, close_modal))
             .chain(zip(CommandDiscriminants::SkipPauses, skip_pauses))
+            .chain(zip(CommandDiscriminants::TogglePlayback, toggle_playback))
+            .chain(zip(CommandDiscriminants::SeekPlaybackForward, seek_playback_forward))
+            .chain(zip(CommandDiscriminants::SeekPlaybackBackward, seek_playback_backward))
+            .chain(zip(CommandDiscriminants::SpeedUpPlayback, speed_up_playback))
+            .chain(zip(CommandDiscriminants::SlowDownPlayback, slow_down_playback))
             .collect();
         Self::validate_conflicts(bindings.iter().map(|binding| &binding.0))?;
         Ok(Self { bindings })
@@ -1,16 +1,23 @@
 use super::{
-    keyboard::{CommandKeyBindings, KeyBindingsValidationError, KeyboardListener},
-    speaker_notes::{SpeakerNotesEvent, SpeakerNotesEventListener},
+    keyboard::{CommandKeyBindings, InputFocus, KeyBindingsValidationError, KeyboardListener, ModalKey},
+    remote::RemoteCommands,
+    speaker_notes::{
+        SpeakerNotesCommand, SpeakerNotesCommandListener, SpeakerNotesCommandPublisher, SpeakerNotesEvent,
+        SpeakerNotesEventListener,
+    },
 };
 use crate::{config::KeyBindingsConfig, presenter::PresentationError};
 use serde::Deserialize;
-use std::time::Duration;
+use std::{io, time::Duration};
 use strum::EnumDiscriminants;
 
 /// A command listener that allows polling all command sources in a single place.
 pub struct CommandListener {
     keyboard: KeyboardListener,
     speaker_notes_event_listener: Option<SpeakerNotesEventListener>,
+    speaker_notes_command_listener: Option<SpeakerNotesCommandListener>,
+    speaker_notes_command_publisher: Option<SpeakerNotesCommandPublisher>,
+    remote_commands: Option<RemoteCommands>,
 }
 
 impl CommandListener {
@@ -20,14 +27,46 @@ impl CommandListener {
         speaker_notes_event_listener: Option<SpeakerNotesEventListener>,
     ) -> Result<Self, KeyBindingsValidationError> {
         let bindings = CommandKeyBindings::try_from(config)?;
-        Ok(Self { keyboard: KeyboardListener::new(bindings), speaker_notes_event_listener })
+        Ok(Self {
+            keyboard: KeyboardListener::new(bindings),
+            speaker_notes_event_listener,
+            speaker_notes_command_listener: None,
+            speaker_notes_command_publisher: None,
+            remote_commands: None,
+        })
+    }
+
+    /// Accept commands sent by speaker notes windows.
+    pub fn with_speaker_notes_command_listener(mut self, listener: Option<SpeakerNotesCommandListener>) -> Self {
+        self.speaker_notes_command_listener = listener;
+        self
+    }
+
+    /// Accept commands sent to the remote control server.
+    pub fn with_remote_commands(mut self, remote_commands: Option<RemoteCommands>) -> Self {
+        self.remote_commands = remote_commands;
+        self
+    }
+
+    /// Forward navigation commands to the main presentation rather than handling them locally.
+    ///
+    /// This is meant to be used in the speaker notes window, which will follow the main
+    /// presentation as it moves.
+    pub fn with_speaker_notes_command_publisher(mut self, publisher: Option<SpeakerNotesCommandPublisher>) -> Self {
+        self.speaker_notes_command_publisher = publisher;
+        self
+    }
+
+    /// Set where keyboard input is sent to.
+    pub(crate) fn set_input_focus(&mut self, focus: InputFocus) {
+        self.keyboard.set_input_focus(focus);
     }
 
     /// Try to get the next command.
     ///
     /// This attempts to get a command and returns `Ok(None)` on timeout.
     pub(crate) fn try_next_command(&mut self) -> Result<Option<Command>, PresentationError> {
-        if let Some(receiver) = &self.speaker_notes_event_listener {
+        if let Some(receiver) = &mut self.speaker_notes_event_listener {
             if let Some(msg) = receiver.try_recv()? {
                 let command = match msg {
                     SpeakerNotesEvent::GoTo { slide, chunk } => Command::GoToSlideChunk { slide, chunk },
@@ -36,15 +75,60 @@ impl CommandListener {
                 return Ok(Some(command));
             }
         }
-        match self.keyboard.poll_next_command(Duration::from_millis(100))? {
-            Some(command) => Ok(Some(command)),
-            None => Ok(None),
+        if let Some(receiver) = &mut self.speaker_notes_command_listener {
+            if let Some(msg) = receiver.try_recv()? {
+                let command = match msg {
+                    SpeakerNotesCommand::Next => Command::Next,
+                    SpeakerNotesCommand::NextFast => Command::NextFast,
+                    SpeakerNotesCommand::Previous => Command::Previous,
+                    SpeakerNotesCommand::PreviousFast => Command::PreviousFast,
+                    SpeakerNotesCommand::GoToSlide { slide } => Command::GoToSlide(slide),
+                    SpeakerNotesCommand::LastSlide => Command::LastSlide,
+                    SpeakerNotesCommand::SkipPauses => Command::SkipPauses,
+                    SpeakerNotesCommand::RenderAsyncOperations => Command::RenderAsyncOperations,
+                };
+                return Ok(Some(command));
+            }
+        }
+        if let Some(command) = self.remote_commands.as_ref().and_then(RemoteCommands::try_recv) {
+            return Ok(Some(command));
+        }
+        let command = match self.keyboard.poll_next_command(Duration::from_millis(100))? {
+            Some(command) => command,
+            None => return Ok(None),
+        };
+        Ok(self.forward(command)?)
+    }
+
+    /// Forward a command to the main presentation if this is a speaker notes instance.
+    ///
+    /// This returns the command back if it wasn't forwarded and should be applied locally.
+    pub(crate) fn forward(&mut self, command: Command) -> io::Result<Option<Command>> {
+        if let Some(publisher) = &mut self.speaker_notes_command_publisher {
+            let forwarded = match command {
+                Command::Next => Some(SpeakerNotesCommand::Next),
+                Command::NextFast => Some(SpeakerNotesCommand::NextFast),
+                Command::Previous => Some(SpeakerNotesCommand::Previous),
+                Command::PreviousFast => Some(SpeakerNotesCommand::PreviousFast),
+                Command::FirstSlide => Some(SpeakerNotesCommand::GoToSlide { slide: 1 }),
+                Command::GoToSlide(slide) => Some(SpeakerNotesCommand::GoToSlide { slide }),
+                Command::LastSlide => Some(SpeakerNotesCommand::LastSlide),
+                Command::SkipPauses => Some(SpeakerNotesCommand::SkipPauses),
+                Command::RenderAsyncOperations => Some(SpeakerNotesCommand::RenderAsyncOperations),
+                _ => None,
+            };
+            if let Some(forwarded) = forwarded {
+                publisher.send(forwarded)?;
+                return Ok(None);
+            }
         }
+        Ok(Some(command))
     }
 }
 
 /// A command.
-#[derive(Clone, Debug, PartialEq, Eq, EnumDiscriminants)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, EnumDiscriminants)]
+#[serde(rename_all = "snake_case")]
 #[strum_discriminants(derive(Deserialize))]
 pub(crate) enum Command {
     /// Redraw the presentation.
@@ -107,4 +191,23 @@ pub(crate) enum Command {
 
     /// Skip pauses in the current slide.
     SkipPauses,
+
+    /// Pause or resume the recording being played in the current slide.
+    TogglePlayback,
+
+    /// Seek forward in the recording being played in the current slide.
+    SeekPlaybackForward,
+
+    /// Seek backward in the recording being played in the current slide.
+    SeekPlaybackBackward,
+
+    /// Speed up the recording being played in the current slide.
+    SpeedUpPlayback,
+
+    /// Slow down the recording being played in the current slide.
+    SlowDownPlayback,
+
+    /// A key pressed while a modal has the input focus.
+    #[serde(skip)]
+    ModalInput(ModalKey),
 }
@@ -1,3 +1,5 @@
 pub(crate) mod keyboard;
 pub(crate) mod listener;
+pub(crate) mod remote;
 pub(crate) mod speaker_notes;
+pub(crate) mod transport;
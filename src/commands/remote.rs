@@ -0,0 +1,613 @@
+use super::listener::Command;
+use serde::Serialize;
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{IpAddr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{Receiver, Sender, channel},
+    },
+    thread,
+    time::Duration,
+};
+
+/// The biggest request head we accept.
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// The biggest request body we accept.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// How long to wait for a client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait for a client to read what we write before dropping it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often to write something into event streams so we notice when clients go away.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The maximum number of connections handled at the same time, including event streams.
+const MAX_CONNECTIONS: usize = 32;
+
+/// The maximum number of event streams open at the same time.
+const MAX_EVENT_STREAMS: usize = 8;
+
+/// An HTTP server that allows controlling the presentation remotely.
+///
+/// This exposes the following endpoints:
+///
+/// * `POST /command`: run the [Command] in the request's body.
+/// * `GET /state`: get the current [RemoteState].
+/// * `GET /events`: a stream of server sent events that contains the [RemoteState] every time it
+///   changes.
+///
+/// Every connection is handled in its own thread, up to [MAX_CONNECTIONS] at a time, and commands
+/// are handed over to the presentation via [RemoteCommands].
+///
+/// Requests are only accepted if their `Host` header is the address we're listening on or a
+/// loopback one, so websites can't reach the server by pointing their own domain to it.
+pub struct RemoteControlServer;
+
+impl RemoteControlServer {
+    /// Start listening on the given address.
+    ///
+    /// A token is required unless the address is a loopback one.
+    pub fn start(address: SocketAddr, token: Option<String>) -> io::Result<(RemoteCommands, RemoteStatePublisher)> {
+        if token.is_none() && !address.ip().is_loopback() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a token is required when listening on a non loopback address",
+            ));
+        }
+        Self::serve(TcpListener::bind(address)?, token)
+    }
+
+    fn serve(listener: TcpListener, token: Option<String>) -> io::Result<(RemoteCommands, RemoteStatePublisher)> {
+        let (sender, receiver) = channel();
+        let shared = Arc::new(SharedState::default());
+        let context = Arc::new(Context {
+            address: listener.local_addr()?,
+            token,
+            sender: Mutex::new(sender),
+            shared: shared.clone(),
+            event_streams: Default::default(),
+        });
+        let connections = Arc::new(AtomicUsize::new(0));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                let Some(slot) = Slot::acquire(&connections, MAX_CONNECTIONS) else {
+                    let _ = Response::error(503, "too many connections").write(&mut stream);
+                    continue;
+                };
+                let context = context.clone();
+                thread::spawn(move || {
+                    context.handle(stream);
+                    drop(slot);
+                });
+            }
+        });
+        Ok((RemoteCommands { receiver }, RemoteStatePublisher { shared }))
+    }
+}
+
+/// A slot in a pool of limited size that's given back when dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn acquire(used: &Arc<AtomicUsize>, limit: usize) -> Option<Self> {
+        used.fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| (count < limit).then_some(count + 1))
+            .ok()
+            .map(|_| Self(used.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// The commands received by the remote control server.
+pub struct RemoteCommands {
+    receiver: Receiver<Command>,
+}
+
+impl RemoteCommands {
+    pub(crate) fn try_recv(&self) -> Option<Command> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Publishes the presentation's state so remote control clients can see it.
+pub struct RemoteStatePublisher {
+    shared: Arc<SharedState>,
+}
+
+impl RemoteStatePublisher {
+    pub(crate) fn publish(&self, state: &RemoteState) {
+        let state = serde_json::to_string(state).expect("serialization failed");
+        let mut current = self.shared.state.lock().unwrap();
+        if current.1.as_ref() != Some(&state) {
+            *current = (current.0 + 1, Some(state));
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+/// The state of the presentation, as seen by remote control clients.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub(crate) struct RemoteState {
+    /// The current slide, 1 based.
+    pub(crate) slide: usize,
+
+    /// The current chunk in the slide, 0 based.
+    pub(crate) chunk: usize,
+
+    pub(crate) total_slides: usize,
+    pub(crate) total_chunks: usize,
+
+    /// The speaker notes for the current slide.
+    pub(crate) notes: Vec<String>,
+}
+
+#[derive(Default)]
+struct SharedState {
+    /// The serialized state along with a version that's bumped every time it changes.
+    state: Mutex<(u64, Option<String>)>,
+    changed: Condvar,
+}
+
+struct Context {
+    address: SocketAddr,
+    token: Option<String>,
+    sender: Mutex<Sender<Command>>,
+    shared: Arc<SharedState>,
+    event_streams: Arc<AtomicUsize>,
+}
+
+impl Context {
+    fn handle(&self, stream: TcpStream) {
+        let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+        let Ok(mut writer) = stream.try_clone() else {
+            return;
+        };
+        let response = match Request::parse(&mut BufReader::new(stream)) {
+            Ok(request) => self.process(request, &mut writer),
+            Err(e) => Some(Response::error(e.status(), e.to_string())),
+        };
+        if let Some(response) = response {
+            let _ = response.write(&mut writer);
+        }
+    }
+
+    fn process(&self, request: Request, writer: &mut TcpStream) -> Option<Response> {
+        if !request.host.as_deref().is_some_and(|host| self.is_valid_host(host)) {
+            return Some(Response::error(403, "invalid host"));
+        }
+        if let Some(token) = &self.token {
+            if !request.token.as_ref().is_some_and(|t| constant_time_eq(t.as_bytes(), token.as_bytes())) {
+                return Some(Response::error(401, "invalid token"));
+            }
+        }
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/command") => {
+                // Requiring JSON makes browsers ask before sending cross origin requests, which
+                // we never allow.
+                if !request.content_type.as_ref().is_some_and(|t| t.starts_with("application/json")) {
+                    return Some(Response::error(415, "content type must be application/json"));
+                }
+                match serde_json::from_slice::<Command>(&request.body) {
+                    Ok(command) => {
+                        let _ = self.sender.lock().unwrap().send(command);
+                        Response::new(202, "application/json", "{}")
+                    }
+                    Err(e) => Response::error(400, format!("invalid command: {e}")),
+                }
+            }
+            ("GET", "/state") => match &self.shared.state.lock().unwrap().1 {
+                Some(state) => Response::new(200, "application/json", state.clone()),
+                None => Response::error(503, "presentation not loaded yet"),
+            },
+            ("GET", "/events") => {
+                let Some(_slot) = Slot::acquire(&self.event_streams, MAX_EVENT_STREAMS) else {
+                    return Some(Response::error(503, "too many event streams"));
+                };
+                self.stream_events(writer);
+                return None;
+            }
+            (_, "/command" | "/state" | "/events") => Response::error(405, "method not allowed"),
+            _ => Response::error(404, "not found"),
+        };
+        Some(response)
+    }
+
+    /// Checks that a `Host` header points to us.
+    ///
+    /// Websites can make a domain they own resolve to our address but they can't make browsers
+    /// send anything but that domain in this header.
+    fn is_valid_host(&self, host: &str) -> bool {
+        let (name, port) = match host.rsplit_once(':') {
+            Some((name, port)) if !port.contains(']') => (name, port.parse().ok()),
+            _ => (host, Some(80)),
+        };
+        if port != Some(self.address.port()) {
+            return false;
+        }
+        if name.eq_ignore_ascii_case("localhost") {
+            return true;
+        }
+        let name = name.strip_prefix('[').and_then(|name| name.strip_suffix(']')).unwrap_or(name);
+        match name.parse::<IpAddr>() {
+            // When listening on every interface, clients can use any of our addresses.
+            Ok(ip) => ip.is_loopback() || ip == self.address.ip() || self.address.ip().is_unspecified(),
+            Err(_) => false,
+        }
+    }
+
+    fn stream_events(&self, writer: &mut TcpStream) {
+        let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n";
+        if writer.write_all(head.as_bytes()).is_err() {
+            return;
+        }
+        let mut last_version = 0;
+        loop {
+            let state = self.shared.state.lock().unwrap();
+            let (state, _) = self
+                .shared
+                .changed
+                .wait_timeout_while(state, KEEP_ALIVE_INTERVAL, |(version, _)| *version == last_version)
+                .unwrap();
+            let message = match &*state {
+                (version, Some(data)) if *version != last_version => {
+                    last_version = *version;
+                    format!("data: {data}\n\n")
+                }
+                _ => ": keep-alive\n\n".to_string(),
+            };
+            drop(state);
+            if writer.write_all(message.as_bytes()).and_then(|_| writer.flush()).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    host: Option<String>,
+    token: Option<String>,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn parse<R: BufRead>(reader: &mut R) -> Result<Self, RequestError> {
+        let mut head_size = 0;
+        let mut read_line = |reader: &mut R| -> Result<String, RequestError> {
+            let mut line = String::new();
+            let bytes_read = reader.take((MAX_HEAD_SIZE - head_size) as u64).read_line(&mut line)?;
+            head_size += bytes_read;
+            if !line.ends_with('\n') {
+                return Err(if head_size >= MAX_HEAD_SIZE { RequestError::TooLarge } else { RequestError::Malformed });
+            }
+            Ok(line.trim_end().to_string())
+        };
+        let request_line = read_line(reader)?;
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(RequestError::Malformed);
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        // Browsers can't set headers when using `EventSource`, so the token can be in the query too.
+        let mut token = query.split('&').find_map(|pair| pair.strip_prefix("token=")).map(ToString::to_string);
+        let mut host = None;
+        let mut content_type = None;
+        let mut content_length = 0;
+        loop {
+            let line = read_line(reader)?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').ok_or(RequestError::Malformed)?;
+            let value = value.trim();
+            match name.to_ascii_lowercase().as_str() {
+                "host" => host = Some(value.to_string()),
+                "authorization" => token = value.strip_prefix("Bearer ").map(ToString::to_string),
+                "content-type" => content_type = Some(value.to_ascii_lowercase()),
+                "content-length" => content_length = value.parse().map_err(|_| RequestError::Malformed)?,
+                _ => (),
+            };
+        }
+        if content_length > MAX_BODY_SIZE {
+            return Err(RequestError::TooLarge);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        Ok(Self { method: method.to_string(), path: path.to_string(), host, token, content_type, body })
+    }
+}
+
+/// Compares two byte strings in a way that doesn't leak how much of them matches.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len() && left.iter().zip(right).fold(0, |diff, (l, r)| diff | (l ^ r)) == 0
+}
+
+#[derive(Debug, thiserror::Error)]
+enum RequestError {
+    #[error("malformed request")]
+    Malformed,
+
+    #[error("request too large")]
+    TooLarge,
+
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl RequestError {
+    fn status(&self) -> u16 {
+        match self {
+            Self::Malformed | Self::Io(_) => 400,
+            Self::TooLarge => 413,
+        }
+    }
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new<S: Into<String>>(status: u16, content_type: &'static str, body: S) -> Self {
+        Self { status, content_type, body: body.into() }
+    }
+
+    fn error<S: Into<String>>(status: u16, message: S) -> Self {
+        let body = serde_json::json!({ "error": message.into() }).to_string();
+        Self::new(status, "application/json", body)
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Content Too Large",
+            415 => "Unsupported Media Type",
+            503 => "Service Unavailable",
+            _ => "",
+        };
+        write!(
+            writer,
+            "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::io::Cursor;
+
+    fn serve(token: Option<&str>) -> (SocketAddr, RemoteCommands, RemoteStatePublisher) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind failed");
+        let address = listener.local_addr().expect("no address");
+        let (commands, publisher) =
+            RemoteControlServer::serve(listener, token.map(ToString::to_string)).expect("serve failed");
+        (address, commands, publisher)
+    }
+
+    fn send(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).expect("connect failed");
+        stream.write_all(request.as_bytes()).expect("write failed");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read failed");
+        response
+    }
+
+    /// Sends a request adding a valid `Host` header to it.
+    fn request(address: SocketAddr, request: &str) -> String {
+        let (request_line, rest) = request.split_once("\r\n").expect("no request line");
+        send(address, &format!("{request_line}\r\nHost: {address}\r\n{rest}"))
+    }
+
+    fn open_events(address: SocketAddr) -> BufReader<TcpStream> {
+        let mut stream = TcpStream::connect(address).expect("connect failed");
+        stream.write_all(format!("GET /events HTTP/1.1\r\nHost: {address}\r\n\r\n").as_bytes()).expect("write failed");
+        BufReader::new(stream)
+    }
+
+    fn post(address: SocketAddr, body: &str, token: &str) -> String {
+        request(
+            address,
+            &format!(
+                "POST /command HTTP/1.1\r\nAuthorization: Bearer {token}\r\nContent-Type: application/json\r\n\
+                Content-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+    }
+
+    fn status(response: &str) -> &str {
+        response.split(' ').nth(1).expect("no status")
+    }
+
+    #[test]
+    fn parse_request() {
+        let input =
+            "POST /command?token=a HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 6\r\n\r\n\"next\"";
+        let request = Request::parse(&mut Cursor::new(input)).expect("parse failed");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/command");
+        assert_eq!(request.token.as_deref(), Some("a"));
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+        assert_eq!(request.body, b"\"next\"");
+    }
+
+    #[rstest]
+    #[case::no_version("GET /state\r\n\r\n")]
+    #[case::bad_header("GET /state HTTP/1.1\r\nnope\r\n\r\n")]
+    #[case::truncated("GET /state HTTP/1.1\r\nHost: a")]
+    #[case::too_large(&format!("GET /state HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_SIZE + 1))]
+    fn invalid_request(#[case] input: &str) {
+        Request::parse(&mut Cursor::new(input)).expect_err("parse succeeded");
+    }
+
+    #[rstest]
+    #[case::unit("\"next\"", Command::Next)]
+    #[case::tuple("{\"go_to_slide\": 3}", Command::GoToSlide(3))]
+    #[case::named(r#"{"go_to_slide_chunk": {"slide": 2, "chunk": 1}}"#, Command::GoToSlideChunk { slide: 2, chunk: 1 })]
+    fn commands(#[case] body: &str, #[case] expected: Command) {
+        let (address, commands, _) = serve(Some("secret"));
+        let response = post(address, body, "secret");
+        assert_eq!(status(&response), "202");
+        assert_eq!(commands.try_recv(), Some(expected));
+    }
+
+    #[test]
+    fn rejected_commands() {
+        let (address, commands, _) = serve(Some("secret"));
+        assert_eq!(status(&post(address, "\"next\"", "guess")), "401");
+        assert_eq!(status(&post(address, "\"jump\"", "secret")), "400");
+
+        let response = request(
+            address,
+            "POST /command HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Type: text/plain\r\n\
+            Content-Length: 6\r\n\r\n\"next\"",
+        );
+        assert_eq!(status(&response), "415");
+        assert_eq!(commands.try_recv(), None);
+    }
+
+    #[test]
+    fn state() {
+        let (address, _, publisher) = serve(None);
+        assert_eq!(status(&request(address, "GET /state HTTP/1.1\r\n\r\n")), "503");
+
+        let state = RemoteState { slide: 2, chunk: 1, total_slides: 3, total_chunks: 2, notes: vec!["hi".into()] };
+        publisher.publish(&state);
+        let response = request(address, "GET /state HTTP/1.1\r\n\r\n");
+        assert_eq!(status(&response), "200");
+        let body = response.split("\r\n\r\n").nth(1).expect("no body");
+        let expected = r#"{"slide":2,"chunk":1,"total_slides":3,"total_chunks":2,"notes":["hi"]}"#;
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn events() {
+        let (address, _, publisher) = serve(None);
+        let state = RemoteState { slide: 1, chunk: 0, total_slides: 1, total_chunks: 1, notes: vec![] };
+        publisher.publish(&state);
+
+        let mut reader = open_events(address);
+        let mut read_event = || loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read failed");
+            if let Some(data) = line.strip_prefix("data: ") {
+                return data.trim_end().to_string();
+            }
+        };
+        assert!(read_event().contains(r#""slide":1"#));
+
+        publisher.publish(&RemoteState { slide: 2, ..state });
+        assert!(read_event().contains(r#""slide":2"#));
+    }
+
+    #[rstest]
+    #[case::bound_address("127.0.0.1:{port}", true)]
+    #[case::localhost("localhost:{port}", true)]
+    #[case::ipv6_loopback("[::1]:{port}", true)]
+    #[case::other_domain("evil.example.com:{port}", false)]
+    #[case::other_port("127.0.0.1:1", false)]
+    #[case::no_port("localhost", false)]
+    fn host_header(#[case] host: &str, #[case] valid: bool) {
+        let (address, _, _) = serve(None);
+        let host = host.replace("{port}", &address.port().to_string());
+        let response = send(address, &format!("GET /state HTTP/1.1\r\nHost: {host}\r\n\r\n"));
+        let expected = if valid { "503" } else { "403" };
+        assert_eq!(status(&response), expected);
+    }
+
+    #[test]
+    fn missing_host() {
+        let (address, _, _) = serve(None);
+        assert_eq!(status(&send(address, "GET /state HTTP/1.1\r\n\r\n")), "403");
+    }
+
+    #[test]
+    fn token_required_on_public_address() {
+        let Err(e) = RemoteControlServer::start("0.0.0.0:0".parse().unwrap(), None) else {
+            panic!("server started");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[rstest]
+    #[case::equal(b"secret", b"secret", true)]
+    #[case::different(b"secret", b"secreT", false)]
+    #[case::prefix(b"secret".as_slice(), b"secre".as_slice(), false)]
+    fn token_comparison(#[case] left: &[u8], #[case] right: &[u8], #[case] expected: bool) {
+        assert_eq!(constant_time_eq(left, right), expected);
+    }
+
+    #[test]
+    fn event_stream_limit() {
+        let (address, _, publisher) = serve(None);
+        let streams: Vec<_> = (0..MAX_EVENT_STREAMS)
+            .map(|_| {
+                let mut reader = open_events(address);
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("read failed");
+                assert_eq!(status(&line), "200");
+                reader
+            })
+            .collect();
+        assert_eq!(status(&request(address, "GET /events HTTP/1.1\r\n\r\n")), "503");
+
+        // Streams are only closed once writing to them fails, so keep publishing until that happens.
+        drop(streams);
+        let mut line = String::new();
+        for slide in 0..30 {
+            publisher.publish(&RemoteState { slide, chunk: 0, total_slides: 30, total_chunks: 1, notes: vec![] });
+            line.clear();
+            open_events(address).read_line(&mut line).expect("read failed");
+            if status(&line) == "200" {
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        panic!("event stream slots not released");
+    }
+
+    #[test]
+    fn connection_limit() {
+        let (address, _, _) = serve(None);
+        let connections: Vec<_> =
+            (0..MAX_CONNECTIONS).map(|_| TcpStream::connect(address).expect("connect failed")).collect();
+        // The request isn't read so don't send one, otherwise closing the socket resets the connection.
+        let mut response = String::new();
+        TcpStream::connect(address).and_then(|mut s| s.read_to_string(&mut response)).expect("read failed");
+        assert_eq!(status(&response), "503");
+        assert!(response.contains("too many connections"), "{response}");
+        drop(connections);
+    }
+}
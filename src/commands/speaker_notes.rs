@@ -1,70 +1,330 @@
-use serde::{Deserialize, Serialize};
-use socket2::{Domain, Protocol, Socket, Type};
+use super::transport::{Endpoint, Transport};
+use crate::config::{SpeakerNotesConfig, SpeakerNotesTransport};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::Sha256;
 use std::{
-    io,
-    net::{SocketAddr, UdpSocket},
-    path::PathBuf,
+    collections::hash_map::DefaultHasher,
+    fs::{self, OpenOptions},
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+/// The length of the tokens generated for a presentation session.
+const SESSION_TOKEN_LENGTH: usize = 32;
+
+/// How far a command's timestamp can be from the current time before the command is ignored.
+const MAX_COMMAND_AGE: Duration = Duration::from_secs(30);
+
+type CommandMac = Hmac<Sha256>;
+
 pub struct SpeakerNotesEventPublisher {
-    socket: UdpSocket,
+    transport: Transport,
     presentation_path: PathBuf,
 }
 
 impl SpeakerNotesEventPublisher {
-    pub fn new(address: SocketAddr, presentation_path: PathBuf) -> io::Result<Self> {
-        let socket = UdpSocket::bind("127.0.0.1:0")?;
-        socket.set_broadcast(true)?;
-        socket.connect(address)?;
-        Ok(Self { socket, presentation_path })
+    pub fn new(config: &SpeakerNotesConfig, presentation_path: PathBuf) -> io::Result<Self> {
+        let transport = match stream_endpoint(config, &presentation_path, Channel::Events)? {
+            Some(endpoint) => Transport::server(endpoint)?,
+            None => Transport::udp_publisher(config.publish_address)?,
+        };
+        Ok(Self { transport, presentation_path })
     }
 
-    pub(crate) fn send(&self, event: SpeakerNotesEvent) -> io::Result<()> {
+    pub(crate) fn send(&mut self, event: SpeakerNotesEvent) -> io::Result<()> {
         // Wrap this event in an envelope that contains the presentation path so listeners can
         // ignore unrelated events.
         let envelope = SpeakerNotesEventEnvelope { event, presentation_path: self.presentation_path.clone() };
-        let data = serde_json::to_string(&envelope).expect("serialization failed");
-        match self.socket.send(data.as_bytes()) {
-            Ok(_) => Ok(()),
-            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => Ok(()),
-            Err(e) => Err(e),
-        }
+        send(&mut self.transport, &envelope)
+    }
+
+    /// Accept any speaker notes windows that connected since the last time an event was sent.
+    pub(crate) fn poll(&mut self) -> io::Result<()> {
+        self.transport.poll()
     }
 }
 
 pub struct SpeakerNotesEventListener {
-    socket: UdpSocket,
+    transport: Transport,
     presentation_path: PathBuf,
 }
 
 impl SpeakerNotesEventListener {
-    pub fn new(address: SocketAddr, presentation_path: PathBuf) -> io::Result<Self> {
-        let s = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
-        // Use SO_REUSEADDR so we can have multiple listeners on the same port.
-        #[cfg(not(target_os = "macos"))]
-        s.set_reuse_address(true)?;
-        // Don't block so we can listen to the keyboard and this socket at the same time.
-        s.set_nonblocking(true)?;
-        s.bind(&address.into())?;
-        Ok(Self { socket: s.into(), presentation_path })
-    }
-
-    pub(crate) fn try_recv(&self) -> io::Result<Option<SpeakerNotesEvent>> {
-        let mut buffer = [0; 1024];
-        let bytes_read = match self.socket.recv(&mut buffer) {
-            Ok(bytes_read) => bytes_read,
-            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
-            Err(e) => return Err(e),
+    pub fn new(config: &SpeakerNotesConfig, presentation_path: PathBuf) -> io::Result<Self> {
+        let transport = match stream_endpoint(config, &presentation_path, Channel::Events)? {
+            Some(endpoint) => Transport::client(endpoint),
+            None => Transport::udp_listener(config.listen_address)?,
         };
-        // Ignore garbage. Odds are this is someone else sending garbage rather than presenterm
-        // itself.
-        let Ok(envelope) = serde_json::from_slice::<SpeakerNotesEventEnvelope>(&buffer[0..bytes_read]) else {
+        Ok(Self { transport, presentation_path })
+    }
+
+    pub(crate) fn try_recv(&mut self) -> io::Result<Option<SpeakerNotesEvent>> {
+        let Some(envelope) = try_recv::<SpeakerNotesEventEnvelope>(&mut self.transport)? else {
             return Ok(None);
         };
         if envelope.presentation_path == self.presentation_path { Ok(Some(envelope.event)) } else { Ok(None) }
     }
 }
 
+/// Sends commands from the speaker notes window back to the main presentation.
+pub struct SpeakerNotesCommandPublisher {
+    transport: Transport,
+    presentation_path: PathBuf,
+    token: Option<String>,
+    last_nonce: u64,
+}
+
+impl SpeakerNotesCommandPublisher {
+    /// Construct a new publisher.
+    ///
+    /// If no token is configured, the one generated by the main presentation running on this same
+    /// host is used.
+    pub fn new(config: &SpeakerNotesConfig, presentation_path: PathBuf) -> io::Result<Self> {
+        let transport = match stream_endpoint(config, &presentation_path, Channel::Commands)? {
+            Some(endpoint) => Transport::client(endpoint),
+            None => Transport::udp_publisher(config.command_publish_address)?,
+        };
+        Ok(Self { transport, presentation_path, token: config.token.clone(), last_nonce: 0 })
+    }
+
+    pub(crate) fn send(&mut self, command: SpeakerNotesCommand) -> io::Result<()> {
+        match self.seal(command)? {
+            Some(envelope) => send(&mut self.transport, &envelope),
+            None => Ok(()),
+        }
+    }
+
+    /// Sign a command with the session token. The token itself never leaves this host.
+    fn seal(&mut self, command: SpeakerNotesCommand) -> io::Result<Option<SpeakerNotesCommandEnvelope>> {
+        // The main presentation may have been restarted since we last sent a command, so always
+        // look up its token again.
+        let token = match &self.token {
+            Some(token) => token.clone(),
+            None => match fs::read_to_string(session_token_path(&self.presentation_path)?) {
+                Ok(token) => token,
+                // If there's no token then the main presentation isn't running.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e),
+            },
+        };
+        // Nonces are timestamps so they keep increasing across restarts of this window, bumped if
+        // needed so two commands never share one.
+        let nonce = timestamp_micros().max(self.last_nonce + 1);
+        self.last_nonce = nonce;
+        let mac = command_mac(&token, nonce, &self.presentation_path, &command).finalize().into_bytes();
+        Ok(Some(SpeakerNotesCommandEnvelope {
+            presentation_path: self.presentation_path.clone(),
+            nonce,
+            mac: hex::encode(mac),
+            command,
+        }))
+    }
+}
+
+/// Listens to commands sent by speaker notes windows.
+pub struct SpeakerNotesCommandListener {
+    transport: Transport,
+    presentation_path: PathBuf,
+    token: String,
+    token_path: Option<PathBuf>,
+    last_nonce: u64,
+}
+
+impl SpeakerNotesCommandListener {
+    /// Construct a new listener.
+    ///
+    /// If no token is configured, one is generated for this session and stored in a file that
+    /// only the current user can read, which is where speaker notes windows on this same host find
+    /// it.
+    pub fn new(config: &SpeakerNotesConfig, presentation_path: PathBuf) -> io::Result<Self> {
+        let transport = match stream_endpoint(config, &presentation_path, Channel::Commands)? {
+            Some(endpoint) => Transport::server(endpoint)?,
+            None => Transport::udp_listener(config.command_listen_address)?,
+        };
+        let (token, token_path) = match &config.token {
+            Some(token) => (token.clone(), None),
+            None => {
+                let token: String = (0..SESSION_TOKEN_LENGTH).map(|_| fastrand::alphanumeric()).collect();
+                let path = session_token_path(&presentation_path)?;
+                write_session_token(&path, &token)?;
+                (token, Some(path))
+            }
+        };
+        Ok(Self { transport, presentation_path, token, token_path, last_nonce: 0 })
+    }
+
+    pub(crate) fn try_recv(&mut self) -> io::Result<Option<SpeakerNotesCommand>> {
+        let Some(envelope) = try_recv::<SpeakerNotesCommandEnvelope>(&mut self.transport)? else {
+            return Ok(None);
+        };
+        if self.open(&envelope) { Ok(Some(envelope.command)) } else { Ok(None) }
+    }
+
+    /// Check that a command was signed with our token, and that it's neither stale nor a replay.
+    fn open(&mut self, envelope: &SpeakerNotesCommandEnvelope) -> bool {
+        let SpeakerNotesCommandEnvelope { presentation_path, nonce, mac, command } = envelope;
+        if *presentation_path != self.presentation_path || *nonce <= self.last_nonce {
+            return false;
+        }
+        if timestamp_micros().abs_diff(*nonce) > MAX_COMMAND_AGE.as_micros() as u64 {
+            return false;
+        }
+        let Ok(mac) = hex::decode(mac) else {
+            return false;
+        };
+        if command_mac(&self.token, *nonce, presentation_path, command).verify_slice(&mac).is_err() {
+            return false;
+        }
+        self.last_nonce = *nonce;
+        true
+    }
+}
+
+impl Drop for SpeakerNotesCommandListener {
+    fn drop(&mut self) {
+        if let Some(path) = &self.token_path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// The direction messages flow in.
+#[derive(Clone, Copy)]
+enum Channel {
+    /// Events from the presentation to speaker notes windows.
+    Events,
+
+    /// Commands from speaker notes windows to the presentation.
+    Commands,
+}
+
+/// Get the endpoint to use for a channel, if the configured transport is stream based.
+fn stream_endpoint(
+    config: &SpeakerNotesConfig,
+    presentation_path: &Path,
+    channel: Channel,
+) -> io::Result<Option<Endpoint>> {
+    match &config.transport {
+        SpeakerNotesTransport::Udp => Ok(None),
+        SpeakerNotesTransport::Tcp { address, command_address } => {
+            let address = match channel {
+                Channel::Events => address,
+                Channel::Commands => command_address,
+            };
+            Ok(Some(Endpoint::Tcp(*address)))
+        }
+        #[cfg(unix)]
+        SpeakerNotesTransport::Unix { directory } => {
+            let directory = match directory {
+                Some(directory) => directory.clone(),
+                None => std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from).unwrap_or_else(std::env::temp_dir),
+            };
+            let name = match channel {
+                Channel::Events => "events",
+                Channel::Commands => "commands",
+            };
+            let file_name = format!("presenterm-{:016x}-{name}.sock", hash_path(presentation_path));
+            Ok(Some(Endpoint::Unix(directory.join(file_name))))
+        }
+        #[cfg(not(unix))]
+        SpeakerNotesTransport::Unix { .. } => {
+            Err(io::Error::new(io::ErrorKind::Unsupported, "unix sockets are not supported on this platform"))
+        }
+    }
+}
+
+fn send<T: Serialize>(transport: &mut Transport, envelope: &T) -> io::Result<()> {
+    let data = serde_json::to_vec(envelope).expect("serialization failed");
+    transport.send(&data)
+}
+
+fn try_recv<T: DeserializeOwned>(transport: &mut Transport) -> io::Result<Option<T>> {
+    let Some(data) = transport.try_recv()? else {
+        return Ok(None);
+    };
+    // Ignore garbage. Odds are this is someone else sending garbage rather than presenterm
+    // itself.
+    Ok(serde_json::from_slice(&data).ok())
+}
+
+fn hash_path(path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The number of microseconds since the unix epoch.
+fn timestamp_micros() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_micros() as u64
+}
+
+/// The MAC for a command, keyed by the session token.
+fn command_mac(token: &str, nonce: u64, presentation_path: &Path, command: &SpeakerNotesCommand) -> CommandMac {
+    let mut mac = CommandMac::new_from_slice(token.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(&serde_json::to_vec(&(nonce, presentation_path, command)).expect("serialization failed"));
+    mac
+}
+
+/// The path to the file that holds the token for the presentation session running on this host.
+fn session_token_path(presentation_path: &Path) -> io::Result<PathBuf> {
+    let directory = match std::env::var_os("XDG_RUNTIME_DIR") {
+        // The runtime directory is only accessible by its owner already.
+        Some(directory) => {
+            let directory = PathBuf::from(directory).join("presenterm");
+            fs::create_dir_all(&directory)?;
+            directory
+        }
+        None => private_temp_directory()?,
+    };
+    Ok(directory.join(format!("speaker-notes-{:016x}.token", hash_path(presentation_path))))
+}
+
+/// A directory within the temporary directory that only the current user can access.
+#[cfg(unix)]
+fn private_temp_directory() -> io::Result<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    // SAFETY: this can't fail.
+    let user_id = unsafe { libc::geteuid() };
+    let directory = std::env::temp_dir().join(format!("presenterm-{user_id}"));
+    match fs::DirBuilder::new().mode(0o700).create(&directory) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => (),
+        Err(e) => return Err(e),
+    };
+    // Someone else could have created it before we did.
+    let metadata = fs::symlink_metadata(&directory)?;
+    if !metadata.is_dir() || metadata.uid() != user_id || metadata.permissions().mode() & 0o077 != 0 {
+        let message = format!("{} is not private to the current user", directory.display());
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, message));
+    }
+    Ok(directory)
+}
+
+/// A directory within the temporary directory that only the current user can access.
+#[cfg(not(unix))]
+fn private_temp_directory() -> io::Result<PathBuf> {
+    // The temporary directory is already specific to each user.
+    Ok(std::env::temp_dir())
+}
+
+fn write_session_token(path: &Path, token: &str) -> io::Result<()> {
+    // Never reuse an existing file as it could be left over from a previous session.
+    match fs::remove_file(path) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    };
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(token.as_bytes())
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "command")]
 pub(crate) enum SpeakerNotesEvent {
@@ -78,19 +338,40 @@ struct SpeakerNotesEventEnvelope {
     event: SpeakerNotesEvent,
 }
 
+/// A command sent from the speaker notes window to the main presentation.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "command")]
+pub(crate) enum SpeakerNotesCommand {
+    Next,
+    NextFast,
+    Previous,
+    PreviousFast,
+    GoToSlide { slide: u32 },
+    LastSlide,
+    SkipPauses,
+    RenderAsyncOperations,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SpeakerNotesCommandEnvelope {
+    presentation_path: PathBuf,
+    nonce: u64,
+    mac: String,
+    command: SpeakerNotesCommand,
+}
+
 #[cfg(not(target_os = "macos"))]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{default_speaker_notes_listen_address, default_speaker_notes_publish_address};
     use std::{thread::sleep, time::Duration};
 
     fn make_listener(path: PathBuf) -> SpeakerNotesEventListener {
-        SpeakerNotesEventListener::new(default_speaker_notes_listen_address(), path).expect("building listener")
+        SpeakerNotesEventListener::new(&Default::default(), path).expect("building listener")
     }
 
     fn make_publisher(path: PathBuf) -> SpeakerNotesEventPublisher {
-        SpeakerNotesEventPublisher::new(default_speaker_notes_publish_address(), path).expect("building publisher")
+        SpeakerNotesEventPublisher::new(&Default::default(), path).expect("building publisher")
     }
 
     #[test]
@@ -102,9 +383,9 @@ mod tests {
     #[test]
     fn multicast() {
         let path = PathBuf::from("/tmp/test.md");
-        let l1 = make_listener(path.clone());
-        let l2 = make_listener(path.clone());
-        let publisher = make_publisher(path);
+        let mut l1 = make_listener(path.clone());
+        let mut l2 = make_listener(path.clone());
+        let mut publisher = make_publisher(path);
         let event = SpeakerNotesEvent::Exit;
         publisher.send(event.clone()).expect("send failed");
         sleep(Duration::from_millis(100));
@@ -112,4 +393,117 @@ mod tests {
         assert_eq!(l1.try_recv().expect("recv first failed"), Some(event.clone()));
         assert_eq!(l2.try_recv().expect("recv second failed"), Some(event));
     }
+
+    fn make_command_listener(path: PathBuf, token: Option<String>) -> SpeakerNotesCommandListener {
+        let config = SpeakerNotesConfig { token, ..Default::default() };
+        SpeakerNotesCommandListener::new(&config, path).expect("building command listener")
+    }
+
+    fn make_command_publisher(path: PathBuf, token: Option<String>) -> SpeakerNotesCommandPublisher {
+        let config = SpeakerNotesConfig { token, ..Default::default() };
+        SpeakerNotesCommandPublisher::new(&config, path).expect("building command publisher")
+    }
+
+    #[test]
+    fn session_token() {
+        let path = PathBuf::from("/tmp/session-token.md");
+        let mut listener = make_command_listener(path.clone(), None);
+        let mut publisher = make_command_publisher(path.clone(), None);
+        let command = SpeakerNotesCommand::GoToSlide { slide: 3 };
+        publisher.send(command.clone()).expect("send failed");
+        sleep(Duration::from_millis(100));
+        assert_eq!(listener.try_recv().expect("recv failed"), Some(command));
+
+        // The token goes away along with the listener.
+        drop(listener);
+        assert!(!session_token_path(&path).expect("no token path").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_token_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory = private_temp_directory().expect("no directory");
+        let mode = fs::metadata(&directory).expect("no metadata").permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    #[test]
+    fn wrong_token() {
+        let path = PathBuf::from("/tmp/wrong-token.md");
+        let mut listener = make_command_listener(path.clone(), Some("secret".into()));
+        let mut publisher = make_command_publisher(path, Some("guess".into()));
+        publisher.send(SpeakerNotesCommand::Next).expect("send failed");
+        sleep(Duration::from_millis(100));
+        assert_eq!(listener.try_recv().expect("recv failed"), None);
+    }
+
+    #[test]
+    fn replayed_command() {
+        let path = PathBuf::from("/tmp/replayed-command.md");
+        let mut listener = make_command_listener(path.clone(), Some("secret".into()));
+        let mut publisher = make_command_publisher(path, Some("secret".into()));
+        let envelope = publisher.seal(SpeakerNotesCommand::Next).expect("seal failed").expect("no envelope");
+        let data = serde_json::to_string(&envelope).expect("serialization failed");
+        assert!(!data.contains("secret"));
+
+        // The first copy goes through, a captured copy sent again doesn't.
+        send(&mut publisher.transport, &envelope).expect("send failed");
+        send(&mut publisher.transport, &envelope).expect("send failed");
+        sleep(Duration::from_millis(100));
+        assert_eq!(listener.try_recv().expect("recv failed"), Some(SpeakerNotesCommand::Next));
+        assert_eq!(listener.try_recv().expect("recv failed"), None);
+    }
+
+    #[test]
+    fn rejected_envelopes() {
+        let path = PathBuf::from("/tmp/rejected-envelopes.md");
+        let mut listener = make_command_listener(path.clone(), Some("secret".into()));
+        let mut publisher = make_command_publisher(path.clone(), Some("secret".into()));
+
+        let envelope = publisher.seal(SpeakerNotesCommand::Next).expect("seal failed").expect("no envelope");
+        let tampered = SpeakerNotesCommandEnvelope { command: SpeakerNotesCommand::LastSlide, ..envelope };
+        assert!(!listener.open(&tampered));
+
+        let nonce = timestamp_micros() - 2 * MAX_COMMAND_AGE.as_micros() as u64;
+        let mac = command_mac("secret", nonce, &path, &SpeakerNotesCommand::Next).finalize().into_bytes();
+        let command = SpeakerNotesCommand::Next;
+        let stale = SpeakerNotesCommandEnvelope { presentation_path: path, nonce, mac: hex::encode(mac), command };
+        assert!(!listener.open(&stale));
+
+        // Rejected envelopes don't affect later ones.
+        let envelope = publisher.seal(SpeakerNotesCommand::Next).expect("seal failed").expect("no envelope");
+        assert!(listener.open(&envelope));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_transport() {
+        let directory = tempfile::tempdir().expect("no temp dir");
+        let config = SpeakerNotesConfig {
+            token: Some("secret".into()),
+            transport: SpeakerNotesTransport::Unix { directory: Some(directory.path().into()) },
+            ..Default::default()
+        };
+        let path = PathBuf::from("/tmp/unix.md");
+        let mut publisher = SpeakerNotesEventPublisher::new(&config, path.clone()).expect("building publisher");
+        let mut command_listener =
+            SpeakerNotesCommandListener::new(&config, path.clone()).expect("building command listener");
+        let mut listener = SpeakerNotesEventListener::new(&config, path.clone()).expect("building listener");
+        let mut command_publisher =
+            SpeakerNotesCommandPublisher::new(&config, path).expect("building command publisher");
+
+        // The listener connects, and gets the last event once it's accepted.
+        let event = SpeakerNotesEvent::GoTo { slide: 2, chunk: 1 };
+        publisher.send(event.clone()).expect("send failed");
+        assert_eq!(listener.try_recv().expect("recv failed"), None);
+        publisher.poll().expect("poll failed");
+        sleep(Duration::from_millis(50));
+        assert_eq!(listener.try_recv().expect("recv failed"), Some(event));
+
+        command_publisher.send(SpeakerNotesCommand::Next).expect("send failed");
+        sleep(Duration::from_millis(50));
+        assert_eq!(command_listener.try_recv().expect("recv failed"), Some(SpeakerNotesCommand::Next));
+    }
 }
@@ -0,0 +1,492 @@
+use socket2::{Domain, Protocol, Socket, Type};
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
+    time::{Duration, Instant},
+};
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+
+/// The biggest message we accept over a stream.
+const MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+/// The most data waiting to be sent to a peer before it's considered gone.
+const MAX_OUTGOING_SIZE: usize = MAX_FRAME_SIZE;
+
+/// The biggest datagram we can receive.
+const MAX_DATAGRAM_SIZE: usize = 65536;
+
+/// How long to wait between attempts to connect to a server.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for a TCP connection to be established.
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// The transport used to send messages between presenterm instances.
+///
+/// All transports are non blocking so they can be polled along with the keyboard.
+pub(crate) enum Transport {
+    /// UDP datagrams, optionally sent to a broadcast address.
+    Udp(UdpSocket),
+
+    /// A server that sends messages to every connected client and receives messages from all of them.
+    Server(StreamServer),
+
+    /// A client that connects to a server, reconnecting to it whenever the connection is lost.
+    Client(StreamClient),
+}
+
+impl Transport {
+    /// Create a UDP transport that sends messages to the given address.
+    pub(crate) fn udp_publisher(address: SocketAddr) -> io::Result<Self> {
+        let socket = UdpSocket::bind("127.0.0.1:0")?;
+        socket.set_broadcast(true)?;
+        socket.connect(address)?;
+        Ok(Self::Udp(socket))
+    }
+
+    /// Create a UDP transport that receives messages sent to the given address.
+    pub(crate) fn udp_listener(address: SocketAddr) -> io::Result<Self> {
+        let s = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+        // Use SO_REUSEADDR so we can have multiple listeners on the same port.
+        #[cfg(not(target_os = "macos"))]
+        s.set_reuse_address(true)?;
+        // Don't block so we can listen to the keyboard and this socket at the same time.
+        s.set_nonblocking(true)?;
+        s.bind(&address.into())?;
+        Ok(Self::Udp(s.into()))
+    }
+
+    /// Create a stream transport that listens for clients on the given endpoint.
+    pub(crate) fn server(endpoint: Endpoint) -> io::Result<Self> {
+        Ok(Self::Server(StreamServer::bind(endpoint)?))
+    }
+
+    /// Create a stream transport that connects to the server on the given endpoint.
+    pub(crate) fn client(endpoint: Endpoint) -> Self {
+        Self::Client(StreamClient::new(endpoint))
+    }
+
+    /// Send a message.
+    ///
+    /// Messages that can't be delivered because there's nobody on the other side are dropped.
+    pub(crate) fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Udp(socket) => match socket.send(data) {
+                Ok(_) => Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => Ok(()),
+                Err(e) => Err(e),
+            },
+            Self::Server(server) => server.send(data),
+            Self::Client(client) => {
+                client.send(data);
+                Ok(())
+            }
+        }
+    }
+
+    /// Handle any pending work, like accepting new clients, without sending or receiving anything.
+    pub(crate) fn poll(&mut self) -> io::Result<()> {
+        match self {
+            Self::Udp(_) | Self::Client(_) => Ok(()),
+            Self::Server(server) => server.poll(),
+        }
+    }
+
+    /// Try to receive a message, returning `Ok(None)` if there's none available.
+    pub(crate) fn try_recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        match self {
+            Self::Udp(socket) => {
+                let mut buffer = vec![0; MAX_DATAGRAM_SIZE];
+                match socket.recv(&mut buffer) {
+                    Ok(bytes_read) => {
+                        buffer.truncate(bytes_read);
+                        Ok(Some(buffer))
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+            Self::Server(server) => server.try_recv(),
+            Self::Client(client) => Ok(client.try_recv()),
+        }
+    }
+}
+
+/// The endpoint a stream server listens on.
+#[derive(Clone, Debug)]
+pub(crate) enum Endpoint {
+    Tcp(SocketAddr),
+
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// A server that accepts any number of clients.
+pub(crate) struct StreamServer {
+    acceptor: Acceptor,
+    connections: Vec<Connection>,
+    last_sent: Option<Vec<u8>>,
+    frames: VecDeque<Vec<u8>>,
+}
+
+impl StreamServer {
+    fn bind(endpoint: Endpoint) -> io::Result<Self> {
+        let acceptor = match endpoint {
+            Endpoint::Tcp(address) => {
+                let listener = TcpListener::bind(address)?;
+                listener.set_nonblocking(true)?;
+                Acceptor::Tcp(listener)
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                // A socket file can outlive the process that created it, so only complain if
+                // someone is still listening on it.
+                if path.exists() {
+                    if UnixStream::connect(&path).is_ok() {
+                        return Err(io::Error::new(io::ErrorKind::AddrInUse, format!("{} is in use", path.display())));
+                    }
+                    fs::remove_file(&path)?;
+                }
+                let listener = UnixListener::bind(&path)?;
+                listener.set_nonblocking(true)?;
+                Acceptor::Unix(listener, path)
+            }
+        };
+        Ok(Self { acceptor, connections: Vec::new(), last_sent: None, frames: Default::default() })
+    }
+
+    fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        self.accept()?;
+        self.last_sent = Some(data.to_vec());
+        self.connections.retain_mut(|connection| connection.push(data).and_then(|_| connection.flush()).is_ok());
+        Ok(())
+    }
+
+    fn poll(&mut self) -> io::Result<()> {
+        self.accept()?;
+        self.connections.retain_mut(|connection| connection.flush().is_ok());
+        Ok(())
+    }
+
+    fn try_recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self.accept()?;
+        let frames = &mut self.frames;
+        self.connections.retain_mut(|connection| connection.flush().and_then(|_| connection.receive(frames)).is_ok());
+        Ok(self.frames.pop_front())
+    }
+
+    fn accept(&mut self) -> io::Result<()> {
+        loop {
+            let stream = match self.acceptor.accept() {
+                Ok(stream) => stream,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::ConnectionAborted => continue,
+                Err(e) => return Err(e),
+            };
+            let Ok(mut connection) = Connection::new(stream) else {
+                continue;
+            };
+            // Let clients catch up right away rather than waiting for the next message.
+            if let Some(data) = &self.last_sent {
+                if connection.push(data).and_then(|_| connection.flush()).is_err() {
+                    continue;
+                }
+            }
+            self.connections.push(connection);
+        }
+    }
+}
+
+/// A client that keeps trying to connect to a server.
+pub(crate) struct StreamClient {
+    endpoint: Endpoint,
+    connection: Option<Connection>,
+    last_attempt: Option<Instant>,
+    frames: VecDeque<Vec<u8>>,
+}
+
+impl StreamClient {
+    fn new(endpoint: Endpoint) -> Self {
+        Self { endpoint, connection: None, last_attempt: None, frames: Default::default() }
+    }
+
+    fn send(&mut self, data: &[u8]) {
+        self.reconnect();
+        if let Some(connection) = &mut self.connection {
+            if connection.push(data).and_then(|_| connection.flush()).is_err() {
+                self.connection = None;
+            }
+        }
+    }
+
+    fn try_recv(&mut self) -> Option<Vec<u8>> {
+        self.reconnect();
+        if let Some(connection) = &mut self.connection {
+            if connection.flush().and_then(|_| connection.receive(&mut self.frames)).is_err() {
+                self.connection = None;
+            }
+        }
+        self.frames.pop_front()
+    }
+
+    fn reconnect(&mut self) {
+        let can_retry = self.last_attempt.map(|last| last.elapsed() >= RECONNECT_INTERVAL).unwrap_or(true);
+        if self.connection.is_none() && can_retry {
+            self.last_attempt = Some(Instant::now());
+            self.connection = self.connect().ok();
+        }
+    }
+
+    fn connect(&self) -> io::Result<Connection> {
+        let stream = match &self.endpoint {
+            Endpoint::Tcp(address) => Stream::Tcp(TcpStream::connect_timeout(address, CONNECT_TIMEOUT)?),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Stream::Unix(UnixStream::connect(path)?),
+        };
+        Connection::new(stream)
+    }
+}
+
+enum Acceptor {
+    Tcp(TcpListener),
+
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
+
+impl Acceptor {
+    fn accept(&self) -> io::Result<Stream> {
+        match self {
+            Self::Tcp(listener) => listener.accept().map(|(stream, _)| Stream::Tcp(stream)),
+            #[cfg(unix)]
+            Self::Unix(listener, _) => listener.accept().map(|(stream, _)| Stream::Unix(stream)),
+        }
+    }
+}
+
+impl Drop for Acceptor {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Self::Unix(_, path) = self {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+enum Stream {
+    Tcp(TcpStream),
+
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    fn set_nonblocking(&self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.set_nonblocking(true),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.set_nonblocking(true),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// A connection that exchanges length prefixed messages.
+///
+/// Every message is preceded by its length as a 32 bit big endian integer.
+struct Connection {
+    stream: Stream,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+}
+
+impl Connection {
+    fn new(stream: Stream) -> io::Result<Self> {
+        stream.set_nonblocking()?;
+        Ok(Self { stream, incoming: Vec::new(), outgoing: Vec::new() })
+    }
+
+    /// Queue a message to be sent.
+    ///
+    /// This fails if the peer isn't reading what's sent to it fast enough, in which case the
+    /// connection should be dropped.
+    fn push(&mut self, data: &[u8]) -> io::Result<()> {
+        if self.outgoing.len() + 4 + data.len() > MAX_OUTGOING_SIZE {
+            return Err(io::Error::other("peer is not reading its messages"));
+        }
+        self.outgoing.extend((data.len() as u32).to_be_bytes());
+        self.outgoing.extend(data);
+        Ok(())
+    }
+
+    /// Write as much of the pending data as possible without blocking.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(bytes_written) => {
+                    self.outgoing.drain(..bytes_written);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Read everything available without blocking and push any complete messages into `frames`.
+    fn receive(&mut self, frames: &mut VecDeque<Vec<u8>>) -> io::Result<()> {
+        let mut buffer = [0; 4096];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(bytes_read) => self.incoming.extend(&buffer[0..bytes_read]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        decode_frames(&mut self.incoming, frames)
+    }
+}
+
+fn decode_frames(buffer: &mut Vec<u8>, frames: &mut VecDeque<Vec<u8>>) -> io::Result<()> {
+    let mut start = 0;
+    while let Some(header) = buffer.get(start..start + 4) {
+        let length = u32::from_be_bytes(header.try_into().expect("invalid header")) as usize;
+        if length > MAX_FRAME_SIZE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"));
+        }
+        let Some(frame) = buffer.get(start + 4..start + 4 + length) else {
+            break;
+        };
+        frames.push_back(frame.to_vec());
+        start += 4 + length;
+    }
+    buffer.drain(..start);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    fn frame(data: &[u8]) -> Vec<u8> {
+        let mut output = (data.len() as u32).to_be_bytes().to_vec();
+        output.extend(data);
+        output
+    }
+
+    #[test]
+    fn partial_frames() {
+        let mut buffer = frame(b"hello");
+        buffer.extend(frame(b"bye"));
+        let full_length = buffer.len();
+        let mut frames = VecDeque::new();
+
+        // Only the first one plus part of the second one have arrived.
+        let mut partial = buffer[0..full_length - 2].to_vec();
+        decode_frames(&mut partial, &mut frames).expect("decode failed");
+        assert_eq!(frames, &[b"hello".to_vec()]);
+        assert_eq!(partial.len(), 5);
+
+        partial.extend(&buffer[full_length - 2..]);
+        decode_frames(&mut partial, &mut frames).expect("decode failed");
+        assert_eq!(frames, &[b"hello".to_vec(), b"bye".to_vec()]);
+        assert!(partial.is_empty());
+    }
+
+    #[test]
+    fn frame_too_large() {
+        let mut buffer = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes().to_vec();
+        assert!(decode_frames(&mut buffer, &mut VecDeque::new()).is_err());
+    }
+
+    /// Keep polling until a message comes in, giving up after a while.
+    fn poll_recv(receiver: &mut Transport, other: &mut Transport) -> Option<Vec<u8>> {
+        for _ in 0..30 {
+            other.poll().expect("poll failed");
+            if let Some(data) = receiver.try_recv().expect("recv failed") {
+                return Some(data);
+            }
+            sleep(Duration::from_millis(100));
+        }
+        None
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reconnect() {
+        let directory = tempfile::tempdir().expect("no temp dir");
+        let endpoint = Endpoint::Unix(directory.path().join("test.sock"));
+        let mut client = Transport::client(endpoint.clone());
+        let mut server = Transport::server(endpoint.clone()).expect("bind failed");
+
+        // The client connects and gets the last message right away.
+        server.send(b"first").expect("send failed");
+        assert_eq!(client.try_recv().expect("recv failed"), None);
+        assert_eq!(poll_recv(&mut client, &mut server), Some(b"first".to_vec()));
+        client.send(b"back").expect("send failed");
+        assert_eq!(poll_recv(&mut server, &mut client), Some(b"back".to_vec()));
+
+        // Restart the server, the client should reconnect to it on its own.
+        drop(server);
+        let mut server = Transport::server(endpoint).expect("bind failed");
+        server.send(b"second").expect("send failed");
+        assert_eq!(poll_recv(&mut client, &mut server), Some(b"second".to_vec()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn drop_slow_clients() {
+        let directory = tempfile::tempdir().expect("no temp dir");
+        let path = directory.path().join("test.sock");
+        let mut server = StreamServer::bind(Endpoint::Unix(path.clone())).expect("bind failed");
+
+        // This client never reads anything so everything sent to it piles up.
+        let _client = UnixStream::connect(&path).expect("connect failed");
+        server.poll().expect("poll failed");
+        assert_eq!(server.connections.len(), 1);
+
+        let data = vec![0; 64 * 1024];
+        for _ in 0..2 * MAX_OUTGOING_SIZE / data.len() {
+            server.send(&data).expect("send failed");
+        }
+        assert!(server.connections.is_empty());
+    }
+}
@@ -42,6 +42,9 @@ pub struct Config {
     #[serde(default)]
     pub speaker_notes: SpeakerNotesConfig,
 
+    #[serde(default)]
+    pub remote: RemoteControlConfig,
+
     #[serde(default)]
     pub export: ExportConfig,
 
@@ -523,6 +526,26 @@ pub struct KeyBindingsConfig {
     /// The key binding to show the entire slide, after skipping any pauses in it.
     #[serde(default = "default_skip_pauses")]
     pub(crate) skip_pauses: Vec<KeyBinding>,
+
+    /// The key binding to pause or resume the asciinema recording in the current slide.
+    #[serde(default = "default_toggle_playback_bindings")]
+    pub(crate) toggle_playback: Vec<KeyBinding>,
+
+    /// The key binding to seek forward in the asciinema recording in the current slide.
+    #[serde(default = "default_seek_playback_forward_bindings")]
+    pub(crate) seek_playback_forward: Vec<KeyBinding>,
+
+    /// The key binding to seek backward in the asciinema recording in the current slide.
+    #[serde(default = "default_seek_playback_backward_bindings")]
+    pub(crate) seek_playback_backward: Vec<KeyBinding>,
+
+    /// The key binding to speed up the asciinema recording in the current slide.
+    #[serde(default = "default_speed_up_playback_bindings")]
+    pub(crate) speed_up_playback: Vec<KeyBinding>,
+
+    /// The key binding to slow down the asciinema recording in the current slide.
+    #[serde(default = "default_slow_down_playback_bindings")]
+    pub(crate) slow_down_playback: Vec<KeyBinding>,
 }
 
 impl Default for KeyBindingsConfig {
@@ -544,6 +567,11 @@ impl Default for KeyBindingsConfig {
             exit: default_exit_bindings(),
             suspend: default_suspend_bindings(),
             skip_pauses: default_skip_pauses(),
+            toggle_playback: default_toggle_playback_bindings(),
+            seek_playback_forward: default_seek_playback_forward_bindings(),
+            seek_playback_backward: default_seek_playback_backward_bindings(),
+            speed_up_playback: default_speed_up_playback_bindings(),
+            slow_down_playback: default_slow_down_playback_bindings(),
         }
     }
 }
@@ -563,6 +591,73 @@ pub struct SpeakerNotesConfig {
     /// Whether to always publish speaker notes.
     #[serde(default)]
     pub always_publish: bool,
+
+    /// The address in which to listen for commands sent by the speaker notes window.
+    #[serde(default = "default_speaker_notes_command_listen_address")]
+    pub command_listen_address: SocketAddr,
+
+    /// The address in which to publish commands from the speaker notes window.
+    #[serde(default = "default_speaker_notes_command_publish_address")]
+    pub command_publish_address: SocketAddr,
+
+    /// The token the speaker notes window signs its commands with to control the presentation.
+    ///
+    /// When unset, a token is generated every time a presentation is started. This only allows
+    /// controlling it from speaker notes windows running on the same host.
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// The transport used to send events and commands between instances.
+    #[serde(default)]
+    pub transport: SpeakerNotesTransport,
+}
+
+/// The remote control configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RemoteControlConfig {
+    /// The address to listen for HTTP requests on.
+    ///
+    /// The remote control server only runs if this is set.
+    #[serde(default)]
+    pub address: Option<SocketAddr>,
+
+    /// The token that requests must include to be accepted.
+    ///
+    /// This is required unless the address is a loopback one.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// The transport used to send speaker notes events and commands.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SpeakerNotesTransport {
+    /// Send UDP datagrams to the publish addresses.
+    #[default]
+    Udp,
+
+    /// Connect through Unix domain sockets.
+    Unix {
+        /// The directory to create sockets in.
+        ///
+        /// This defaults to `$XDG_RUNTIME_DIR`, or the temporary directory if that's not set.
+        #[serde(default)]
+        directory: Option<PathBuf>,
+    },
+
+    /// Connect through TCP.
+    Tcp {
+        /// The address the presentation listens on for speaker notes windows to receive events.
+        #[serde(default = "default_speaker_notes_tcp_address")]
+        address: SocketAddr,
+
+        /// The address the presentation listens on for speaker notes windows to send commands.
+        #[serde(default = "default_speaker_notes_tcp_command_address")]
+        command_address: SocketAddr,
+    },
 }
 
 impl Default for SpeakerNotesConfig {
@@ -571,6 +666,10 @@ impl Default for SpeakerNotesConfig {
             listen_address: default_speaker_notes_listen_address(),
             publish_address: default_speaker_notes_publish_address(),
             always_publish: false,
+            command_listen_address: default_speaker_notes_command_listen_address(),
+            command_publish_address: default_speaker_notes_command_publish_address(),
+            token: None,
+            transport: Default::default(),
         }
     }
 }
@@ -594,6 +693,50 @@ pub struct ExportConfig {
     /// The PDF specific export configs.
     #[serde(default)]
     pub pdf: PdfExportConfig,
+
+    /// The asciinema recording specific export configs.
+    #[serde(default)]
+    pub asciinema: AsciinemaExportConfig,
+}
+
+/// The asciinema recording specific export configs.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AsciinemaExportConfig {
+    /// The frame to show for recordings that don't pick one via the `+poster` attribute.
+    #[serde(default)]
+    pub poster: AsciinemaPosterPolicy,
+
+    /// How recordings are exported into HTML.
+    #[serde(default)]
+    pub html: AsciinemaHtmlExportPolicy,
+}
+
+/// The frame used to represent a recording when exporting.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum AsciinemaPosterPolicy {
+    /// Use the first frame in the recording.
+    Start,
+
+    /// Use the last frame in the recording.
+    #[default]
+    End,
+}
+
+/// The way recordings are exported into HTML.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum AsciinemaHtmlExportPolicy {
+    /// Only include the poster frame.
+    #[default]
+    Poster,
+
+    /// Include every frame and play them back when the slide is shown.
+    Player,
 }
 
 /// The policy for pauses when exporting.
@@ -676,12 +819,41 @@ pub struct SlideTransitionConfig {
 
     /// The slide transition style.
     pub animation: SlideTransitionStyleConfig,
+
+    /// How the transition's progress is spread over its duration.
+    #[serde(default)]
+    pub easing: TransitionEasing,
+}
+
+/// How a transition's progress is spread over its duration.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum TransitionEasing {
+    /// Progress at a constant pace.
+    #[default]
+    Linear,
+
+    /// Start slowly and speed up towards the end.
+    EaseIn,
+
+    /// Start fast and slow down towards the end.
+    EaseOut,
+
+    /// Start and end slowly.
+    EaseInOut,
+
+    /// A custom cubic bézier curve defined by its two control points: `[x1, y1, x2, y2]`.
+    ///
+    /// The `x` coordinates are clamped to the `[0, 1]` range.
+    CubicBezier([f64; 4]),
 }
 
 // The slide transition style configuration.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, strum::EnumString)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "style", rename_all = "snake_case", deny_unknown_fields)]
+#[strum(serialize_all = "snake_case")]
 pub enum SlideTransitionStyleConfig {
     /// Slide horizontally.
     SlideHorizontal,
@@ -691,6 +863,135 @@ pub enum SlideTransitionStyleConfig {
 
     /// Collapse the current slide into the center of the screen.
     CollapseHorizontal,
+
+    /// Slide vertically.
+    SlideVertical,
+
+    /// Reveal the new slide from the right edge of the screen towards the left one.
+    WipeLeft,
+
+    /// Reveal the new slide from the left edge of the screen towards the right one.
+    WipeRight,
+
+    /// Reveal the new slide from the bottom of the screen towards the top.
+    WipeUp,
+
+    /// Reveal the new slide from the top of the screen towards the bottom.
+    WipeDown,
+
+    /// Dissolve the current slide into the new one, one random cell at a time.
+    Dissolve,
+
+    /// Reveal the new slide through a circle that expands from the center of the screen.
+    CircleReveal,
+
+    /// Rotate the slides as if they were the sides of a cube.
+    Cube,
+
+    /// Push the new slide in from the right, on top of the current one.
+    Push,
+}
+
+/// A slide transition set from within a presentation.
+///
+/// This can either be just a style name, `none` to disable transitions, or a map with any of the
+/// `style`, `duration_millis`, `frames`, and `easing` keys. Anything left out is taken from the
+/// configured transition.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "RawSlideTransitionOverride")]
+pub enum SlideTransitionOverride {
+    /// Don't animate the transition.
+    Disabled,
+
+    /// Override parts of the configured transition.
+    Enabled {
+        animation: Option<SlideTransitionStyleConfig>,
+        duration_millis: Option<u16>,
+        frames: Option<usize>,
+        easing: Option<TransitionEasing>,
+    },
+}
+
+impl SlideTransitionOverride {
+    /// Get the transition to use after applying this override on top of the configured one.
+    pub(crate) fn apply(&self, base: Option<&SlideTransitionConfig>) -> Option<SlideTransitionConfig> {
+        let Self::Enabled { animation, duration_millis, frames, easing } = self else {
+            return None;
+        };
+        let animation = animation.clone().or_else(|| base.map(|base| base.animation.clone()))?;
+        let duration_millis = duration_millis
+            .or_else(|| base.map(|base| base.duration_millis))
+            .unwrap_or_else(default_transition_duration_millis);
+        let frames = frames.or_else(|| base.map(|base| base.frames)).unwrap_or_else(default_transition_frames);
+        let easing = easing.clone().or_else(|| base.map(|base| base.easing.clone())).unwrap_or_default();
+        Some(SlideTransitionConfig { duration_millis, frames, animation, easing })
+    }
+
+    /// Layer this override on top of another one, taking anything left out from it.
+    pub(crate) fn layer(self, base: &Self) -> Self {
+        match (self, base) {
+            (
+                Self::Enabled { animation, duration_millis, frames, easing },
+                Self::Enabled {
+                    animation: base_animation,
+                    duration_millis: base_duration_millis,
+                    frames: base_frames,
+                    easing: base_easing,
+                },
+            ) => Self::Enabled {
+                animation: animation.or_else(|| base_animation.clone()),
+                duration_millis: duration_millis.or(*base_duration_millis),
+                frames: frames.or(*base_frames),
+                easing: easing.or_else(|| base_easing.clone()),
+            },
+            (this, _) => this,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawSlideTransitionOverride {
+    Style(String),
+    Custom(RawCustomSlideTransition),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCustomSlideTransition {
+    #[serde(default)]
+    style: Option<String>,
+
+    #[serde(default)]
+    duration_millis: Option<u16>,
+
+    #[serde(default)]
+    frames: Option<usize>,
+
+    #[serde(default)]
+    easing: Option<TransitionEasing>,
+}
+
+impl TryFrom<RawSlideTransitionOverride> for SlideTransitionOverride {
+    type Error = String;
+
+    fn try_from(raw: RawSlideTransitionOverride) -> Result<Self, Self::Error> {
+        let RawCustomSlideTransition { style, duration_millis, frames, easing } = match raw {
+            RawSlideTransitionOverride::Style(style) => {
+                RawCustomSlideTransition { style: Some(style), duration_millis: None, frames: None, easing: None }
+            }
+            RawSlideTransitionOverride::Custom(custom) => custom,
+        };
+        if frames == Some(0) {
+            return Err("transition frames must be greater than 0".into());
+        }
+        let animation = match style.as_deref() {
+            Some("none") => return Ok(Self::Disabled),
+            Some(style) => Some(style.parse().map_err(|_| format!("unknown transition style '{style}'"))?),
+            None => None,
+        };
+        Ok(Self::Enabled { animation, duration_millis, frames, easing })
+    }
 }
 
 fn make_keybindings<const N: usize>(raw_bindings: [&str; N]) -> Vec<KeyBinding> {
@@ -765,6 +1066,26 @@ fn default_skip_pauses() -> Vec<KeyBinding> {
     make_keybindings(["s"])
 }
 
+fn default_toggle_playback_bindings() -> Vec<KeyBinding> {
+    make_keybindings(["K"])
+}
+
+fn default_seek_playback_forward_bindings() -> Vec<KeyBinding> {
+    make_keybindings(["L"])
+}
+
+fn default_seek_playback_backward_bindings() -> Vec<KeyBinding> {
+    make_keybindings(["J"])
+}
+
+fn default_speed_up_playback_bindings() -> Vec<KeyBinding> {
+    make_keybindings(["+"])
+}
+
+fn default_slow_down_playback_bindings() -> Vec<KeyBinding> {
+    make_keybindings(["-"])
+}
+
 fn default_transition_duration_millis() -> u16 {
     1000
 }
@@ -793,6 +1114,26 @@ pub(crate) fn default_speaker_notes_publish_address() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 59418)
 }
 
+pub(crate) fn default_speaker_notes_command_listen_address() -> SocketAddr {
+    let mut address = default_speaker_notes_listen_address();
+    address.set_port(59419);
+    address
+}
+
+pub(crate) fn default_speaker_notes_command_publish_address() -> SocketAddr {
+    let mut address = default_speaker_notes_publish_address();
+    address.set_port(59419);
+    address
+}
+
+fn default_speaker_notes_tcp_address() -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 59418)
+}
+
+fn default_speaker_notes_tcp_command_address() -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 59419)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -808,4 +1149,68 @@ mod test {
     fn default_options_serde() {
         serde_yaml::from_str::<'_, OptionsConfig>("implicit_slide_ends: true").expect("failed to parse");
     }
+
+    #[test]
+    fn speaker_notes_transport() {
+        let config: SpeakerNotesConfig =
+            serde_yaml::from_str("transport: {type: tcp, address: 0.0.0.0:1234}").expect("failed to parse");
+        let SpeakerNotesTransport::Tcp { address, command_address } = config.transport else {
+            panic!("not tcp");
+        };
+        assert_eq!(address.port(), 1234);
+        assert_eq!(command_address, default_speaker_notes_tcp_command_address());
+
+        serde_yaml::from_str::<'_, SpeakerNotesConfig>("transport: {type: unix, address: 0.0.0.0:1234}")
+            .expect_err("parse succeeded");
+    }
+
+    #[rstest::rstest]
+    #[case::style("fade", Some((SlideTransitionStyleConfig::Fade, 200, 10)))]
+    #[case::disabled("none", None)]
+    #[case::disabled_map("{style: none, frames: 5}", None)]
+    #[case::duration("{duration_millis: 500}", Some((SlideTransitionStyleConfig::SlideHorizontal, 500, 10)))]
+    #[case::everything(
+        "{style: collapse_horizontal, duration_millis: 100, frames: 3}",
+        Some((SlideTransitionStyleConfig::CollapseHorizontal, 100, 3))
+    )]
+    fn transition_override(#[case] input: &str, #[case] expected: Option<(SlideTransitionStyleConfig, u16, usize)>) {
+        let animation = SlideTransitionStyleConfig::SlideHorizontal;
+        let base =
+            SlideTransitionConfig { duration_millis: 200, frames: 10, animation, easing: TransitionEasing::EaseIn };
+        let transition: SlideTransitionOverride = serde_yaml::from_str(input).expect("failed to parse");
+        let config = transition.apply(Some(&base));
+        let config = config.map(|config| (config.animation, config.duration_millis, config.frames));
+        assert_eq!(config, expected);
+    }
+
+    #[rstest::rstest]
+    #[case::inherited("fade", TransitionEasing::EaseIn)]
+    #[case::named("{easing: ease_out}", TransitionEasing::EaseOut)]
+    #[case::bezier("{easing: {cubic_bezier: [0.1, 0.2, 0.3, 0.4]}}", TransitionEasing::CubicBezier([0.1, 0.2, 0.3, 0.4]))]
+    fn transition_override_easing(#[case] input: &str, #[case] expected: TransitionEasing) {
+        let animation = SlideTransitionStyleConfig::Fade;
+        let base =
+            SlideTransitionConfig { duration_millis: 200, frames: 10, animation, easing: TransitionEasing::EaseIn };
+        let transition: SlideTransitionOverride = serde_yaml::from_str(input).expect("failed to parse");
+        assert_eq!(transition.apply(Some(&base)).expect("no transition").easing, expected);
+    }
+
+    #[test]
+    fn transition_override_without_base() {
+        let transition: SlideTransitionOverride = serde_yaml::from_str("{frames: 5}").expect("failed to parse");
+        assert!(transition.apply(None).is_none());
+
+        let transition: SlideTransitionOverride = serde_yaml::from_str("fade").expect("failed to parse");
+        let config = transition.apply(None).expect("no transition");
+        assert_eq!(config.duration_millis, default_transition_duration_millis());
+        assert_eq!(config.frames, default_transition_frames());
+    }
+
+    #[rstest::rstest]
+    #[case::unknown_style("spin")]
+    #[case::unknown_key("{speed: 2}")]
+    #[case::zero_frames("{frames: 0}")]
+    fn invalid_transition_override(#[case] input: &str) {
+        serde_yaml::from_str::<SlideTransitionOverride>(input).expect_err("parse succeeded");
+    }
 }
@@ -0,0 +1,51 @@
+document.addEventListener('DOMContentLoaded', function() {
+  // How long the last frame stays on screen before the slide starts over, unless it loops on its own.
+  const lastFrameSeconds = 2;
+
+  document.querySelectorAll('.container[data-frame-times]').forEach((container) => {
+    const frameTimes = container.dataset.frameTimes.split(',').map(Number);
+    const rows = Array.from(container.querySelectorAll('.content-line[data-frames]')).map((row) => {
+      return {
+        variants: Array.from(row.children),
+        frames: row.dataset.frames.split(',').map(Number),
+      };
+    });
+    const duration = container.dataset.cycle
+      ? Number(container.dataset.cycle)
+      : frameTimes[frameTimes.length - 1] + lastFrameSeconds;
+    let startTime = null;
+    let currentFrame = -1;
+
+    function showFrame(frame) {
+      if (frame === currentFrame) {
+        return;
+      }
+      currentFrame = frame;
+      rows.forEach((row) => {
+        row.variants.forEach((variant, index) => {
+          variant.classList.toggle('hidden', index !== row.frames[frame]);
+        });
+      });
+    }
+
+    function tick(now) {
+      if (container.classList.contains('hidden')) {
+        // Play the slide from the start every time it's shown.
+        startTime = null;
+      } else {
+        if (startTime === null) {
+          startTime = now;
+        }
+        const elapsed = ((now - startTime) / 1000) % duration;
+        let frame = 0;
+        while (frame + 1 < frameTimes.length && frameTimes[frame + 1] <= elapsed) {
+          frame++;
+        }
+        showFrame(frame);
+      }
+      requestAnimationFrame(tick);
+    }
+
+    requestAnimationFrame(tick);
+  });
+});
@@ -1,8 +1,14 @@
 use crate::{
     MarkdownParser, Resources,
-    code::execute::SnippetExecutor,
-    config::{KeyBindingsConfig, PauseExportPolicy, PdfExportConfig, SnippetsExportPolicy},
-    export::output::{ExportRenderer, OutputFormat},
+    code::{execute::SnippetExecutor, snippet::PosterFrame},
+    config::{
+        AsciinemaExportConfig, AsciinemaHtmlExportPolicy, AsciinemaPosterPolicy, KeyBindingsConfig, PauseExportPolicy,
+        PdfExportConfig, SnippetsExportPolicy,
+    },
+    export::{
+        gif::GifRenderer,
+        output::{ExportRenderer, OutputFormat},
+    },
     markdown::text_style::Color,
     presentation::{
         Presentation,
@@ -11,7 +17,7 @@ use crate::{
     },
     render::{
         RenderError,
-        operation::{AsRenderOperations, PollableState, RenderOperation},
+        operation::{AsRenderOperations, PollableState, RenderAsync, RenderOperation, RenderTimeline, TimelineKind},
         properties::WindowSize,
     },
     theme::{ProcessingThemeError, raw::PresentationTheme},
@@ -57,7 +63,7 @@ impl OutputDirectory {
     }
 }
 
-/// Allows exporting presentations into PDF.
+/// Allows exporting presentations into PDF, HTML and animated GIFs.
 pub struct Exporter<'a> {
     parser: MarkdownParser<'a>,
     default_theme: &'a PresentationTheme,
@@ -68,8 +74,12 @@ pub struct Exporter<'a> {
     dimensions: WindowSize,
     options: PresentationBuilderOptions,
     snippet_policy: SnippetsExportPolicy,
+    asciinema_html_policy: AsciinemaHtmlExportPolicy,
 }
 
+/// The timelines in every slide, indexed by slide.
+type SlideTimelines = Vec<Vec<Rc<dyn RenderTimeline>>>;
+
 impl<'a> Exporter<'a> {
     /// Construct a new exporter.
     #[allow(clippy::too_many_arguments)]
@@ -84,14 +94,21 @@ impl<'a> Exporter<'a> {
         mut dimensions: WindowSize,
         pause_policy: PauseExportPolicy,
         snippet_policy: SnippetsExportPolicy,
+        asciinema: AsciinemaExportConfig,
     ) -> Self {
         // We don't want dynamically highlighted code blocks.
         options.allow_mutations = false;
+        // Animations are exported at their last frame unless the output format can play them.
+        options.freeze_animations = true;
         options.theme_options.font_size_supported = true;
         options.pause_create_new_slide = match pause_policy {
             PauseExportPolicy::Ignore => false,
             PauseExportPolicy::NewSlide => true,
         };
+        options.asciinema_poster = Some(match asciinema.poster {
+            AsciinemaPosterPolicy::Start => PosterFrame::Time(0.0),
+            AsciinemaPosterPolicy::End => PosterFrame::End,
+        });
 
         // Make sure we have a 1:2 aspect ratio.
         let width = (0.5 * dimensions.columns as f64) / (dimensions.rows as f64 / dimensions.height as f64);
@@ -107,15 +124,11 @@ impl<'a> Exporter<'a> {
             options,
             dimensions,
             snippet_policy,
+            asciinema_html_policy: asciinema.html,
         }
     }
 
-    fn build_renderer(
-        &mut self,
-        presentation_path: &Path,
-        output_directory: OutputDirectory,
-        renderer: OutputFormat,
-    ) -> Result<ExportRenderer, ExportError> {
+    fn build_presentation(&mut self, presentation_path: &Path) -> Result<(Presentation, SlideTimelines), ExportError> {
         let mut presentation = PresentationBuilder::new(
             self.default_theme,
             self.resources.clone(),
@@ -130,17 +143,37 @@ impl<'a> Exporter<'a> {
         .build(presentation_path)?;
         Self::validate_theme_colors(&presentation)?;
 
-        let mut render = ExportRenderer::new(self.dimensions, output_directory, renderer);
         Self::log("waiting for images to be generated and code to be executed, if any...")?;
-        match self.snippet_policy {
+        let timelines = match self.snippet_policy {
             SnippetsExportPolicy::Parallel => Self::wait_async_renders_parallel(&mut presentation),
             SnippetsExportPolicy::Sequential => Self::wait_async_renders_sequential(&mut presentation),
         };
+        Ok((presentation, timelines))
+    }
+
+    fn build_renderer(
+        &mut self,
+        presentation_path: &Path,
+        output_directory: OutputDirectory,
+        renderer: OutputFormat,
+    ) -> Result<ExportRenderer, ExportError> {
+        let (presentation, timelines) = self.build_presentation(presentation_path)?;
 
-        for (index, slide) in presentation.into_slides().into_iter().enumerate() {
+        // Only HTML can play back recordings and animations, everything else only gets their poster frame.
+        let animate = matches!(renderer, OutputFormat::Html);
+        let play_recordings = animate && matches!(self.asciinema_html_policy, AsciinemaHtmlExportPolicy::Player);
+        let mut render = ExportRenderer::new(self.dimensions, output_directory, renderer);
+        for ((index, slide), timelines) in presentation.into_slides().into_iter().enumerate().zip(timelines) {
             let index = index + 1;
             Self::log(&format!("processing slide {index}..."))?;
-            render.process_slide(slide)?;
+            let timelines: Vec<_> = timelines
+                .into_iter()
+                .filter(|timeline| match timeline.kind() {
+                    TimelineKind::Recording => play_recordings,
+                    TimelineKind::Animation { .. } => animate,
+                })
+                .collect();
+            render.process_slide(slide, &timelines)?;
         }
         Self::log("invoking weasyprint...")?;
 
@@ -212,7 +245,34 @@ impl<'a> Exporter<'a> {
         Ok(())
     }
 
-    fn wait_async_renders_parallel(presentation: &mut Presentation) {
+    /// Export the given presentation into an animated GIF.
+    pub fn export_gif(mut self, presentation_path: &Path, output_path: Option<&Path>) -> Result<(), ExportError> {
+        println!("exporting using rows={}, columns={}", self.dimensions.rows, self.dimensions.columns);
+
+        let (presentation, timelines) = self.build_presentation(presentation_path)?;
+        let output_path = match output_path {
+            Some(path) => path.to_path_buf(),
+            None => presentation_path.with_extension("gif"),
+        };
+
+        let mut render = GifRenderer::new(self.dimensions, io::BufWriter::new(fs::File::create(&output_path)?))?;
+        for ((index, slide), timelines) in presentation.into_slides().into_iter().enumerate().zip(timelines) {
+            let index = index + 1;
+            Self::log(&format!("processing slide {index}..."))?;
+            render.process_slide(slide, &timelines)?;
+        }
+        render.finish()?;
+
+        execute!(
+            io::stdout(),
+            PrintStyledContent(
+                format!("output file is at {}\n", output_path.display()).stylize().with(Color::Green.into())
+            )
+        )?;
+        Ok(())
+    }
+
+    fn wait_async_renders_parallel(presentation: &mut Presentation) -> SlideTimelines {
         let poller = Poller::launch();
         let mut pollables = Vec::new();
         for (index, slide) in presentation.iter_slides().enumerate() {
@@ -232,20 +292,24 @@ impl<'a> Exporter<'a> {
 
         // Replace render asyncs with new operations that contains the replaced image
         // and any other unmodified operations.
+        let mut timelines = SlideTimelines::new();
         for slide in presentation.iter_slides_mut() {
+            let mut slide_timelines = Vec::new();
             for op in slide.iter_operations_mut() {
                 if let RenderOperation::RenderAsync(inner) = op {
-                    let window_size = WindowSize { rows: 0, columns: 0, width: 0, height: 0 };
-                    let new_operations = inner.as_render_operations(&window_size);
-                    *op = RenderOperation::RenderDynamic(Rc::new(RenderMany(new_operations)));
+                    *op = Self::replace_async_render(inner.as_ref(), &mut slide_timelines);
                 }
             }
+            timelines.push(slide_timelines);
         }
+        timelines
     }
 
-    fn wait_async_renders_sequential(presentation: &mut Presentation) {
+    fn wait_async_renders_sequential(presentation: &mut Presentation) -> SlideTimelines {
         let poller = Poller::launch();
+        let mut timelines = SlideTimelines::new();
         for (index, slide) in presentation.iter_slides_mut().enumerate() {
+            let mut slide_timelines = Vec::new();
             for op in slide.iter_operations_mut() {
                 if let RenderOperation::RenderAsync(inner) = op {
                     // Send a pollable to the poller
@@ -256,12 +320,23 @@ impl<'a> Exporter<'a> {
                     while let PollableState::Unmodified | PollableState::Modified = pollable.poll() {}
 
                     // Replace it with its contents
-                    let window_size = WindowSize { rows: 0, columns: 0, width: 0, height: 0 };
-                    let new_operations = inner.as_render_operations(&window_size);
-                    *op = RenderOperation::RenderDynamic(Rc::new(RenderMany(new_operations)));
+                    *op = Self::replace_async_render(inner.as_ref(), &mut slide_timelines);
                 }
             }
+            timelines.push(slide_timelines);
+        }
+        timelines
+    }
+
+    fn replace_async_render(inner: &dyn RenderAsync, timelines: &mut Vec<Rc<dyn RenderTimeline>>) -> RenderOperation {
+        // Timelines are rendered at export time as they depend on the dimensions being used.
+        if let Some(timeline) = inner.timeline() {
+            timelines.push(timeline.clone());
+            return RenderOperation::RenderDynamic(timeline);
         }
+        let window_size = WindowSize { rows: 0, columns: 0, width: 0, height: 0 };
+        let new_operations = inner.as_render_operations(&window_size);
+        RenderOperation::RenderDynamic(Rc::new(RenderMany(new_operations)))
     }
 
     fn validate_weasyprint_exists() -> Result<(), ExportError> {
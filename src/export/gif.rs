@@ -0,0 +1,410 @@
+use super::{
+    exporter::ExportError,
+    html::color_to_rgb,
+    output::{frame_times, render_grid, timelines_cycle},
+};
+use crate::{
+    markdown::text_style::Color,
+    presentation::Slide,
+    render::{operation::RenderTimeline, properties::WindowSize},
+    terminal::virt::{StyledChar, TerminalGrid},
+};
+use image::{
+    Delay, Frame, Rgba, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+use std::{io::Write, ops::Range, rc::Rc};
+
+// Every terminal cell is drawn as a grid of dots, each of them `DOT_SIZE` pixels wide.
+const CELL_WIDTH: u32 = 6;
+const CELL_HEIGHT: u32 = 12;
+const DOT_SIZE: u32 = 2;
+
+// The row in a cell the top of every glyph is drawn at.
+const GLYPH_TOP: u32 = 2;
+
+// How long slides stay on screen when nothing in them changes anymore.
+const HOLD_SECONDS: f64 = 2.0;
+
+const DEFAULT_FOREGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const DEFAULT_BACKGROUND: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+// A 5x8 font for the printable ASCII characters, starting at the space.
+//
+// Every glyph is a list of columns, the least significant bit being the top row.
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5f, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7f, 0x14, 0x7f, 0x14],
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50],
+    [0x00, 0x08, 0x07, 0x03, 0x00],
+    [0x00, 0x1c, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1c, 0x00],
+    [0x2a, 0x1c, 0x7f, 0x1c, 0x2a],
+    [0x08, 0x08, 0x3e, 0x08, 0x08],
+    [0x00, 0x80, 0x70, 0x30, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x00, 0x60, 0x60, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3e, 0x51, 0x49, 0x45, 0x3e],
+    [0x00, 0x42, 0x7f, 0x40, 0x00],
+    [0x72, 0x49, 0x49, 0x49, 0x46],
+    [0x21, 0x41, 0x49, 0x4d, 0x33],
+    [0x18, 0x14, 0x12, 0x7f, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3c, 0x4a, 0x49, 0x49, 0x31],
+    [0x41, 0x21, 0x11, 0x09, 0x07],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x46, 0x49, 0x49, 0x29, 0x1e],
+    [0x00, 0x00, 0x14, 0x00, 0x00],
+    [0x00, 0x40, 0x34, 0x00, 0x00],
+    [0x00, 0x08, 0x14, 0x22, 0x41],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x59, 0x09, 0x06],
+    [0x3e, 0x41, 0x5d, 0x59, 0x4e],
+    [0x7c, 0x12, 0x11, 0x12, 0x7c],
+    [0x7f, 0x49, 0x49, 0x49, 0x36],
+    [0x3e, 0x41, 0x41, 0x41, 0x22],
+    [0x7f, 0x41, 0x41, 0x41, 0x3e],
+    [0x7f, 0x49, 0x49, 0x49, 0x41],
+    [0x7f, 0x09, 0x09, 0x09, 0x01],
+    [0x3e, 0x41, 0x41, 0x51, 0x73],
+    [0x7f, 0x08, 0x08, 0x08, 0x7f],
+    [0x00, 0x41, 0x7f, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3f, 0x01],
+    [0x7f, 0x08, 0x14, 0x22, 0x41],
+    [0x7f, 0x40, 0x40, 0x40, 0x40],
+    [0x7f, 0x02, 0x1c, 0x02, 0x7f],
+    [0x7f, 0x04, 0x08, 0x10, 0x7f],
+    [0x3e, 0x41, 0x41, 0x41, 0x3e],
+    [0x7f, 0x09, 0x09, 0x09, 0x06],
+    [0x3e, 0x41, 0x51, 0x21, 0x5e],
+    [0x7f, 0x09, 0x19, 0x29, 0x46],
+    [0x26, 0x49, 0x49, 0x49, 0x32],
+    [0x03, 0x01, 0x7f, 0x01, 0x03],
+    [0x3f, 0x40, 0x40, 0x40, 0x3f],
+    [0x1f, 0x20, 0x40, 0x20, 0x1f],
+    [0x3f, 0x40, 0x38, 0x40, 0x3f],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x03, 0x04, 0x78, 0x04, 0x03],
+    [0x61, 0x59, 0x49, 0x4d, 0x43],
+    [0x00, 0x7f, 0x41, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x41, 0x7f],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x03, 0x07, 0x08, 0x00],
+    [0x20, 0x54, 0x54, 0x78, 0x40],
+    [0x7f, 0x28, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x28],
+    [0x38, 0x44, 0x44, 0x28, 0x7f],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x00, 0x08, 0x7e, 0x09, 0x02],
+    [0x18, 0xa4, 0xa4, 0x9c, 0x78],
+    [0x7f, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7d, 0x40, 0x00],
+    [0x20, 0x40, 0x40, 0x3d, 0x00],
+    [0x7f, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7f, 0x40, 0x00],
+    [0x7c, 0x04, 0x78, 0x04, 0x78],
+    [0x7c, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0xfc, 0x18, 0x24, 0x24, 0x18],
+    [0x18, 0x24, 0x24, 0x18, 0xfc],
+    [0x7c, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x24],
+    [0x04, 0x04, 0x3f, 0x44, 0x24],
+    [0x3c, 0x40, 0x40, 0x20, 0x7c],
+    [0x1c, 0x20, 0x40, 0x20, 0x1c],
+    [0x3c, 0x40, 0x30, 0x40, 0x3c],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x4c, 0x90, 0x90, 0x90, 0x7c],
+    [0x44, 0x64, 0x54, 0x4c, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x77, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+struct GifFrame {
+    image: RgbaImage,
+    seconds: f64,
+}
+
+/// Renders presentations into animated GIFs.
+///
+/// Every slide is shown for a couple of seconds, and any animations or recordings in it are
+/// played through once before moving on to the next one.
+///
+/// Frames are encoded as they're rendered. Only the last one is kept around, so it can be shown
+/// for longer if the ones that follow it are identical.
+pub(crate) struct GifRenderer<W: Write> {
+    dimensions: WindowSize,
+    encoder: GifEncoder<W>,
+    last_frame: Option<GifFrame>,
+}
+
+impl<W: Write> GifRenderer<W> {
+    pub(crate) fn new(dimensions: WindowSize, writer: W) -> Result<Self, ExportError> {
+        let mut encoder = GifEncoder::new_with_speed(writer, 10);
+        encoder.set_repeat(Repeat::Infinite)?;
+        Ok(Self { dimensions, encoder, last_frame: None })
+    }
+
+    /// Process a slide, including every frame in the given timelines in it.
+    pub(crate) fn process_slide(
+        &mut self,
+        slide: Slide,
+        timelines: &[Rc<dyn RenderTimeline>],
+    ) -> Result<(), ExportError> {
+        let frame_times = frame_times(timelines);
+        if frame_times.is_empty() {
+            let image = rasterize(&render_grid(&slide, self.dimensions)?);
+            return self.push_frame(image, HOLD_SECONDS);
+        }
+
+        // Looping animations go straight back to their first frame, anything else is held for a while.
+        let end = match timelines_cycle(timelines) {
+            Some(cycle) => cycle,
+            None => frame_times[frame_times.len() - 1] + HOLD_SECONDS,
+        };
+        for (index, time) in frame_times.iter().enumerate() {
+            for timeline in timelines {
+                timeline.seek_to(*time);
+            }
+            let next_time = frame_times.get(index + 1).copied().unwrap_or(end);
+            let image = rasterize(&render_grid(&slide, self.dimensions)?);
+            self.push_frame(image, next_time - time)?;
+        }
+        Ok(())
+    }
+
+    fn push_frame(&mut self, image: RgbaImage, seconds: f64) -> Result<(), ExportError> {
+        if let Some(last) = &mut self.last_frame {
+            if last.image == image {
+                last.seconds += seconds;
+                return Ok(());
+            }
+        }
+        match self.last_frame.replace(GifFrame { image, seconds }) {
+            Some(frame) => self.encode(frame),
+            None => Ok(()),
+        }
+    }
+
+    fn encode(&mut self, frame: GifFrame) -> Result<(), ExportError> {
+        let millis = (frame.seconds * 1000.0).round() as u32;
+        let delay = Delay::from_numer_denom_ms(millis, 1);
+        self.encoder.encode_frame(Frame::from_parts(frame.image, 0, 0, delay))?;
+        Ok(())
+    }
+
+    /// Encode the last frame. The GIF is complete once this renderer is dropped.
+    pub(crate) fn finish(mut self) -> Result<(), ExportError> {
+        match self.last_frame.take() {
+            Some(frame) => self.encode(frame),
+            None => Ok(()),
+        }
+    }
+}
+
+fn to_rgba(color: &Color) -> Rgba<u8> {
+    let (r, g, b) = color_to_rgb(color);
+    Rgba([r, g, b, 255])
+}
+
+/// Draw the contents of a terminal into an image.
+fn rasterize(grid: &TerminalGrid) -> RgbaImage {
+    let columns = grid.rows.first().map(Vec::len).unwrap_or_default() as u32;
+    let rows = grid.rows.len() as u32;
+    let background = grid.background_color.as_ref().map(to_rgba).unwrap_or(DEFAULT_BACKGROUND);
+    let image = RgbaImage::from_pixel(columns * CELL_WIDTH * DOT_SIZE, rows * CELL_HEIGHT * DOT_SIZE, background);
+    let mut canvas = Canvas { image };
+    for (y, row) in grid.rows.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let c = row[x];
+            let cell = CellPosition { column: x as u32, row: y as u32, size: c.style.size.max(1) as u32 };
+            canvas.draw(&cell, &c);
+            x += cell.size as usize;
+        }
+    }
+    canvas.image
+}
+
+struct CellPosition {
+    column: u32,
+    row: u32,
+    size: u32,
+}
+
+struct Canvas {
+    image: RgbaImage,
+}
+
+impl Canvas {
+    fn draw(&mut self, cell: &CellPosition, styled: &StyledChar) {
+        if let Some(color) = &styled.style.colors.background {
+            self.fill(cell, 0..CELL_WIDTH, 0..CELL_HEIGHT, to_rgba(color));
+        }
+        let color = styled.style.colors.foreground.as_ref().map(to_rgba).unwrap_or(DEFAULT_FOREGROUND);
+        match styled.character {
+            ' ' => (),
+            '█' => self.fill(cell, 0..CELL_WIDTH, 0..CELL_HEIGHT, color),
+            '▀' => self.fill(cell, 0..CELL_WIDTH, 0..CELL_HEIGHT / 2, color),
+            '▄' => self.fill(cell, 0..CELL_WIDTH, CELL_HEIGHT / 2..CELL_HEIGHT, color),
+            '▌' => self.fill(cell, 0..CELL_WIDTH / 2, 0..CELL_HEIGHT, color),
+            '▐' => self.fill(cell, CELL_WIDTH / 2..CELL_WIDTH, 0..CELL_HEIGHT, color),
+            '░' => self.shade(cell, color, |x, y| x % 2 == 0 && y % 2 == 0),
+            '▒' => self.shade(cell, color, |x, y| (x + y) % 2 == 0),
+            '▓' => self.shade(cell, color, |x, y| x % 2 == 0 || y % 2 == 0),
+            // These make up most of the FIGlet fonts so they span the entire cell to join their neighbors.
+            '|' => self.fill(cell, 2..3, 0..CELL_HEIGHT, color),
+            '_' => self.fill(cell, 0..CELL_WIDTH, CELL_HEIGHT - 1..CELL_HEIGHT, color),
+            '/' => self.shade(cell, color, |x, y| x == (CELL_HEIGHT - 1 - y) / 2),
+            '\\' => self.shade(cell, color, |x, y| x == y / 2),
+            c if c.is_ascii_graphic() => {
+                let glyph = &FONT[c as usize - ' ' as usize];
+                self.glyph(cell, glyph, color, 0);
+                // Bold text is drawn twice, the second time one dot to the right.
+                if styled.style.is_bold() {
+                    self.glyph(cell, glyph, color, 1);
+                }
+            }
+            c => match Self::box_arms(c) {
+                Some(arms) => self.box_lines(cell, arms, color),
+                // Anything we can't draw is shown as an empty box.
+                None => {
+                    let (left, right, top, bottom) = (0, CELL_WIDTH - 2, GLYPH_TOP, GLYPH_TOP + 7);
+                    self.shade(cell, color, |x, y| {
+                        ((x == left || x == right) && (top..=bottom).contains(&y))
+                            || ((y == top || y == bottom) && (left..=right).contains(&x))
+                    });
+                }
+            },
+        }
+    }
+
+    fn glyph(&mut self, cell: &CellPosition, glyph: &[u8; 5], color: Rgba<u8>, offset: u32) {
+        for (x, column) in glyph.iter().enumerate() {
+            let x = x as u32 + offset;
+            for y in 0..8 {
+                if column >> y & 1 == 1 {
+                    self.fill(cell, x..x + 1, GLYPH_TOP + y..GLYPH_TOP + y + 1, color);
+                }
+            }
+        }
+    }
+
+    /// Get which sides of a box drawing character have a line going into them, as (up, down, left, right).
+    fn box_arms(c: char) -> Option<(bool, bool, bool, bool)> {
+        let arms = match c {
+            '─' | '━' => (false, false, true, true),
+            '│' | '┃' => (true, true, false, false),
+            '┌' | '┏' | '╭' => (false, true, false, true),
+            '┐' | '┓' | '╮' => (false, true, true, false),
+            '└' | '┗' | '╰' => (true, false, false, true),
+            '┘' | '┛' | '╯' => (true, false, true, false),
+            '├' | '┣' => (true, true, false, true),
+            '┤' | '┫' => (true, true, true, false),
+            '┬' | '┳' => (false, true, true, true),
+            '┴' | '┻' => (true, false, true, true),
+            '┼' | '╋' => (true, true, true, true),
+            _ => return None,
+        };
+        Some(arms)
+    }
+
+    fn box_lines(&mut self, cell: &CellPosition, (up, down, left, right): (bool, bool, bool, bool), color: Rgba<u8>) {
+        let (center_x, center_y) = (CELL_WIDTH / 2 - 1, CELL_HEIGHT / 2);
+        if up {
+            self.fill(cell, center_x..center_x + 1, 0..center_y + 1, color);
+        }
+        if down {
+            self.fill(cell, center_x..center_x + 1, center_y..CELL_HEIGHT, color);
+        }
+        if left {
+            self.fill(cell, 0..center_x + 1, center_y..center_y + 1, color);
+        }
+        if right {
+            self.fill(cell, center_x..CELL_WIDTH, center_y..center_y + 1, color);
+        }
+    }
+
+    fn shade<F>(&mut self, cell: &CellPosition, color: Rgba<u8>, lit: F)
+    where
+        F: Fn(u32, u32) -> bool,
+    {
+        for y in 0..CELL_HEIGHT {
+            for x in 0..CELL_WIDTH {
+                if lit(x, y) {
+                    self.fill(cell, x..x + 1, y..y + 1, color);
+                }
+            }
+        }
+    }
+
+    /// Fill the given range of dots within a cell.
+    fn fill(&mut self, cell: &CellPosition, columns: Range<u32>, rows: Range<u32>, color: Rgba<u8>) {
+        let scale = cell.size * DOT_SIZE;
+        let left = cell.column * CELL_WIDTH * DOT_SIZE + columns.start * scale;
+        let right = (cell.column * CELL_WIDTH * DOT_SIZE + columns.end * scale).min(self.image.width());
+        let top = cell.row * CELL_HEIGHT * DOT_SIZE + rows.start * scale;
+        let bottom = (cell.row * CELL_HEIGHT * DOT_SIZE + rows.end * scale).min(self.image.height());
+        for y in top..bottom {
+            for x in left..right {
+                self.image.put_pixel(x, y, color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::text_style::TextStyle;
+    use image::{AnimationDecoder, codecs::gif::GifDecoder};
+    use std::io::Cursor;
+
+    fn grid(row: Vec<StyledChar>) -> TerminalGrid {
+        TerminalGrid { rows: vec![row], background_color: Some(Color::Green), images: Default::default() }
+    }
+
+    #[test]
+    fn rasterize_cells() {
+        let row = vec![
+            StyledChar { character: '█', style: TextStyle::default().fg_color(Color::Red) },
+            StyledChar { character: ' ', style: TextStyle::default().bg_color(Color::Blue) },
+            StyledChar { character: ' ', style: TextStyle::default() },
+        ];
+        let image = rasterize(&grid(row));
+        assert_eq!(image.dimensions(), (3 * CELL_WIDTH * DOT_SIZE, CELL_HEIGHT * DOT_SIZE));
+
+        let cell_width = CELL_WIDTH * DOT_SIZE;
+        assert_eq!(image.get_pixel(0, 0), &to_rgba(&Color::Red));
+        assert_eq!(image.get_pixel(cell_width, 0), &to_rgba(&Color::Blue));
+        assert_eq!(image.get_pixel(cell_width * 2, 0), &to_rgba(&Color::Green));
+    }
+
+    #[test]
+    fn merge_identical_frames() {
+        let dimensions = WindowSize { rows: 1, columns: 1, width: 1, height: 1 };
+        let mut output = Vec::new();
+        let mut renderer = GifRenderer::new(dimensions, &mut output).expect("failed to create renderer");
+        let image = rasterize(&grid(vec![StyledChar::default()]));
+        renderer.push_frame(image.clone(), 0.5).expect("failed to push");
+        renderer.push_frame(image, 1.0).expect("failed to push");
+        let other = rasterize(&grid(vec![StyledChar { character: 'a', style: TextStyle::default() }]));
+        renderer.push_frame(other, 1.0).expect("failed to push");
+        renderer.finish().expect("failed to finish");
+
+        let decoder = GifDecoder::new(Cursor::new(output)).expect("invalid gif");
+        let frames = decoder.into_frames().collect_frames().expect("invalid frames");
+        let delays: Vec<_> = frames.iter().map(|frame| frame.delay().numer_denom_ms()).collect();
+        assert_eq!(delays, &[(1500, 1), (1000, 1)]);
+    }
+}
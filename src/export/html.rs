@@ -66,24 +66,30 @@ impl FontSize {
 }
 
 pub(crate) fn color_to_html(color: &Color) -> String {
+    let (r, g, b) = color_to_rgb(color);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Get the RGB components a color is exported as.
+pub(crate) fn color_to_rgb(color: &Color) -> (u8, u8, u8) {
     match color {
-        Color::Black => "#000000".into(),
-        Color::DarkGrey => "#5a5a5a".into(),
-        Color::Red => "#ff0000".into(),
-        Color::DarkRed => "#8b0000".into(),
-        Color::Green => "#00ff00".into(),
-        Color::DarkGreen => "#006400".into(),
-        Color::Yellow => "#ffff00".into(),
-        Color::DarkYellow => "#8b8000".into(),
-        Color::Blue => "#0000ff".into(),
-        Color::DarkBlue => "#00008b".into(),
-        Color::Magenta => "#ff00ff".into(),
-        Color::DarkMagenta => "#8b008b".into(),
-        Color::Cyan => "#00ffff".into(),
-        Color::DarkCyan => "#008b8b".into(),
-        Color::White => "#ffffff".into(),
-        Color::Grey => "#808080".into(),
-        Color::Rgb { r, g, b } => format!("#{r:02x}{g:02x}{b:02x}"),
+        Color::Black => (0x00, 0x00, 0x00),
+        Color::DarkGrey => (0x5a, 0x5a, 0x5a),
+        Color::Red => (0xff, 0x00, 0x00),
+        Color::DarkRed => (0x8b, 0x00, 0x00),
+        Color::Green => (0x00, 0xff, 0x00),
+        Color::DarkGreen => (0x00, 0x64, 0x00),
+        Color::Yellow => (0xff, 0xff, 0x00),
+        Color::DarkYellow => (0x8b, 0x80, 0x00),
+        Color::Blue => (0x00, 0x00, 0xff),
+        Color::DarkBlue => (0x00, 0x00, 0x8b),
+        Color::Magenta => (0xff, 0x00, 0xff),
+        Color::DarkMagenta => (0x8b, 0x00, 0x8b),
+        Color::Cyan => (0x00, 0xff, 0xff),
+        Color::DarkCyan => (0x00, 0x8b, 0x8b),
+        Color::White => (0xff, 0xff, 0xff),
+        Color::Grey => (0x80, 0x80, 0x80),
+        Color::Rgb { r, g, b } => (*r, *g, *b),
     }
 }
 
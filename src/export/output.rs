@@ -7,7 +7,11 @@ use crate::{
     export::html::HtmlText,
     markdown::text_style::TextStyle,
     presentation::Slide,
-    render::{engine::RenderEngine, properties::WindowSize},
+    render::{
+        engine::RenderEngine,
+        operation::{RenderTimeline, TimelineKind},
+        properties::WindowSize,
+    },
     terminal::{
         image::printer::TerminalImage,
         virt::{TerminalGrid, VirtualTerminal},
@@ -15,8 +19,10 @@ use crate::{
     tools::ThirdPartyTools,
 };
 use std::{
+    fmt::Write,
     fs, io,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
 const FONT_NAME: &str = "presenterm-font";
@@ -38,9 +44,8 @@ struct HtmlSlide {
 impl HtmlSlide {
     fn new(grid: TerminalGrid) -> Result<Self, ExportError> {
         let mut rows = Vec::new();
-        rows.push(String::from("<div class=\"container\">"));
         for (y, row) in grid.rows.into_iter().enumerate() {
-            let mut finalized_row = "<div class=\"content-line\"><pre>".to_string();
+            let mut finalized_row = "<pre>".to_string();
             let mut current_style = row.first().map(|c| c.style).unwrap_or_default();
             let mut current_string = String::new();
             let mut x = 0;
@@ -70,19 +75,105 @@ impl HtmlSlide {
             if !current_string.is_empty() {
                 finalized_row.push_str(&Self::finalize_string(&current_string, &current_style));
             }
-            finalized_row.push_str("</pre></div>");
+            finalized_row.push_str("</pre>");
             rows.push(finalized_row);
         }
-        rows.push(String::from("</div>"));
 
         Ok(HtmlSlide { rows, background_color: grid.background_color.as_ref().map(color_to_html) })
     }
 
+    fn into_html(self) -> String {
+        let mut html = String::from("<div class=\"container\">\n");
+        for row in self.rows {
+            let _ = writeln!(html, "<div class=\"content-line\">{row}</div>");
+        }
+        html.push_str("</div>\n");
+        html
+    }
+
     fn finalize_string(s: &str, style: &TextStyle) -> String {
         HtmlText::new(s, style, FontSize::Pixels(FONT_SIZE)).to_string()
     }
 }
 
+/// A slide that contains recordings or animations, exported along with every frame in them.
+///
+/// Only the rows that change throughout the frames are stored more than once: each of them holds
+/// every distinct version of itself, and the bundled player script picks which one to show.
+struct AnimatedHtmlSlide {
+    poster: HtmlSlide,
+    frame_times: Vec<f64>,
+    frames: Vec<HtmlSlide>,
+    cycle: Option<f64>,
+}
+
+impl AnimatedHtmlSlide {
+    fn into_html(self) -> String {
+        let frame_times: Vec<_> = self.frame_times.iter().map(|time| format!("{time:.3}")).collect();
+        let frame_times = frame_times.join(",");
+        let cycle = self.cycle.map(|cycle| format!(" data-cycle=\"{cycle:.3}\"")).unwrap_or_default();
+        let mut html = format!("<div class=\"container\" data-frame-times=\"{frame_times}\"{cycle}>\n");
+        for (y, poster_row) in self.poster.rows.into_iter().enumerate() {
+            let mut variants = vec![poster_row];
+            let mut frame_variants = Vec::new();
+            for frame in &self.frames {
+                let row = &frame.rows[y];
+                let index = match variants.iter().position(|variant| variant == row) {
+                    Some(index) => index,
+                    None => {
+                        variants.push(row.clone());
+                        variants.len() - 1
+                    }
+                };
+                frame_variants.push(index.to_string());
+            }
+            if variants.len() == 1 {
+                let _ = writeln!(html, "<div class=\"content-line\">{}</div>", variants[0]);
+                continue;
+            }
+            let frame_variants = frame_variants.join(",");
+            let _ = write!(html, "<div class=\"content-line\" data-frames=\"{frame_variants}\">{}", variants[0]);
+            for variant in &variants[1..] {
+                html.push_str(&variant.replacen("<pre>", "<pre class=\"hidden\">", 1));
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</div>\n");
+        html
+    }
+}
+
+/// Render a slide into a virtual terminal of the given dimensions.
+pub(crate) fn render_grid(slide: &Slide, dimensions: WindowSize) -> Result<TerminalGrid, ExportError> {
+    let mut terminal = VirtualTerminal::new(dimensions, Default::default());
+    let engine = RenderEngine::new(&mut terminal, dimensions, Default::default());
+    engine.render(slide.iter_operations())?;
+    Ok(terminal.into_contents())
+}
+
+/// Get every point in time at which any of the given timelines changes, in order.
+pub(crate) fn frame_times(timelines: &[Rc<dyn RenderTimeline>]) -> Vec<f64> {
+    let mut frame_times: Vec<_> = timelines.iter().flat_map(|timeline| timeline.frame_times()).collect();
+    frame_times.sort_by(f64::total_cmp);
+    frame_times.dedup();
+    frame_times
+}
+
+/// Get how long it takes for the given timelines to start over.
+///
+/// This is only known when every timeline is a looping animation; recordings and animations
+/// that play once are instead held at their last frame for a while before being replayed.
+pub(crate) fn timelines_cycle(timelines: &[Rc<dyn RenderTimeline>]) -> Option<f64> {
+    let mut cycle = None;
+    for timeline in timelines {
+        let TimelineKind::Animation { cycle: Some(timeline_cycle) } = timeline.kind() else {
+            return None;
+        };
+        cycle = Some(timeline_cycle.max(cycle.unwrap_or_default()));
+    }
+    cycle
+}
+
 pub(crate) struct ContentManager {
     output_directory: OutputDirectory,
 }
@@ -110,6 +201,7 @@ pub(crate) struct ExportRenderer {
     dimensions: WindowSize,
     html_body: String,
     background_color: Option<String>,
+    animated: bool,
 }
 
 impl ExportRenderer {
@@ -121,29 +213,50 @@ impl ExportRenderer {
             html_body: "".to_string(),
             background_color: None,
             output_format: output_type,
+            animated: false,
         }
     }
 
-    pub(crate) fn process_slide(&mut self, slide: Slide) -> Result<(), ExportError> {
-        let mut terminal = VirtualTerminal::new(self.dimensions, Default::default());
-        let engine = RenderEngine::new(&mut terminal, self.dimensions, Default::default());
-        engine.render(slide.iter_operations())?;
-
-        let grid = terminal.into_contents();
-        let slide = HtmlSlide::new(grid)?;
+    /// Process a slide, including every frame in the given timelines in it.
+    pub(crate) fn process_slide(
+        &mut self,
+        slide: Slide,
+        timelines: &[Rc<dyn RenderTimeline>],
+    ) -> Result<(), ExportError> {
+        let poster = self.render_slide(&slide)?;
         if self.background_color.is_none() {
-            self.background_color.clone_from(&slide.background_color);
+            self.background_color.clone_from(&poster.background_color);
+        }
+        if timelines.is_empty() {
+            self.html_body.push_str(&poster.into_html());
+            return Ok(());
         }
-        for row in slide.rows {
-            self.html_body.push_str(&row);
-            self.html_body.push('\n');
+
+        let frame_times = frame_times(timelines);
+        let mut frames = Vec::new();
+        for time in &frame_times {
+            for timeline in timelines {
+                timeline.seek_to(*time);
+            }
+            frames.push(self.render_slide(&slide)?);
         }
+        let slide = AnimatedHtmlSlide { poster, frame_times, frames, cycle: timelines_cycle(timelines) };
+        self.html_body.push_str(&slide.into_html());
+        self.animated = true;
         Ok(())
     }
 
+    fn render_slide(&self, slide: &Slide) -> Result<HtmlSlide, ExportError> {
+        let grid = render_grid(slide, self.dimensions)?;
+        HtmlSlide::new(grid)
+    }
+
     pub(crate) fn generate(self, output_path: &Path, fonts: &Option<ExportFontsConfig>) -> Result<(), ExportError> {
         let html_body = &self.html_body;
-        let script = include_str!("script.js");
+        let mut script = include_str!("script.js").to_string();
+        if self.animated {
+            script.push_str(include_str!("asciinema.js"));
+        }
         let width = (self.dimensions.columns as f64 * FONT_SIZE as f64 * FONT_SIZE_WIDTH).ceil();
         let height = self.dimensions.rows * LINE_HEIGHT;
         let background_color = self.background_color.unwrap_or_else(|| "black".into());
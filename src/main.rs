@@ -1,6 +1,6 @@
 use crate::{
     code::{
-        banner::{init_figlet_fonts_and_warn, BannerGenerator},
+        banner::{embedded_fonts_listing, init_figlet_fonts_and_warn, BannerGenerator},
         execute::SnippetExecutor,
         highlighting::HighlightThemeSet,
         snippet::{SnippetLanguage, SnippetParser},
@@ -12,7 +12,7 @@ use crate::{
     markdown::parse::MarkdownParser,
     presentation::builder::{CommentCommand, PresentationBuilderOptions, Themes},
     presenter::{PresentMode, Presenter, PresenterOptions},
-    resource::Resources,
+    resource::{ResourceBasePath, Resources},
     markdown::elements::MarkdownElement,
     terminal::{
         GraphicsMode,
@@ -23,7 +23,13 @@ use crate::{
 };
 use anyhow::anyhow;
 use clap::{CommandFactory, Parser, error::ErrorKind};
-use commands::speaker_notes::{SpeakerNotesEventListener, SpeakerNotesEventPublisher};
+use commands::{
+    remote::RemoteControlServer,
+    speaker_notes::{
+        SpeakerNotesCommandListener, SpeakerNotesCommandPublisher, SpeakerNotesEventListener,
+        SpeakerNotesEventPublisher,
+    },
+};
 use comrak::Arena;
 use config::ConfigLoadError;
 use crossterm::{
@@ -81,6 +87,10 @@ struct Cli {
     #[clap(short = 'E', long, group = "export")]
     export_html: bool,
 
+    /// Export the presentation as an animated GIF rather than displaying it.
+    #[clap(long, group = "export")]
+    export_gif: bool,
+
     /// The path in which to store temporary files used when exporting.
     #[clap(long, requires = "export")]
     export_temporary_path: Option<PathBuf>,
@@ -239,7 +249,7 @@ impl CoreComponents {
 
         let default_theme = Self::load_default_theme(&config, &themes, cli);
         let force_default_theme = cli.theme.is_some();
-        let present_mode = match (cli.present, cli.export_pdf) {
+        let present_mode = match (cli.present, cli.export_pdf || cli.export_gif) {
             (true, _) | (_, true) => PresentMode::Presentation,
             (false, false) => PresentMode::Development,
         };
@@ -310,11 +320,13 @@ impl CoreComponents {
             layout_grid: false,
             h1_slide_titles: options.h1_slide_titles.unwrap_or_default(),
             banner_animation_duration_millis: config.snippet.banner.animation_duration_millis,
+            asciinema_poster: None,
+            freeze_animations: false,
         }
     }
 
     fn select_graphics_mode(cli: &Cli, config: &Config) -> GraphicsMode {
-        if cli.export_pdf | cli.export_html {
+        if cli.export_pdf | cli.export_html | cli.export_gif {
             GraphicsMode::Raw
         } else {
             let protocol = cli.image_protocol.as_ref().unwrap_or(&config.defaults.image_protocol);
@@ -342,6 +354,8 @@ impl CoreComponents {
 struct SpeakerNotesComponents {
     events_listener: Option<SpeakerNotesEventListener>,
     events_publisher: Option<SpeakerNotesEventPublisher>,
+    commands_listener: Option<SpeakerNotesCommandListener>,
+    commands_publisher: Option<SpeakerNotesCommandPublisher>,
 }
 
 impl SpeakerNotesComponents {
@@ -349,18 +363,31 @@ impl SpeakerNotesComponents {
         let full_presentation_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
         let publish_speaker_notes =
             cli.publish_speaker_notes || (config.speaker_notes.always_publish && !cli.listen_speaker_notes);
+        let config = &config.speaker_notes;
         let events_publisher = publish_speaker_notes
-            .then(|| {
-                SpeakerNotesEventPublisher::new(config.speaker_notes.publish_address, full_presentation_path.clone())
-            })
+            .then(|| SpeakerNotesEventPublisher::new(config, full_presentation_path.clone()))
             .transpose()
             .map_err(|e| anyhow!("failed to create speaker notes publisher: {e}"))?;
         let events_listener = cli
             .listen_speaker_notes
-            .then(|| SpeakerNotesEventListener::new(config.speaker_notes.listen_address, full_presentation_path))
+            .then(|| SpeakerNotesEventListener::new(config, full_presentation_path.clone()))
             .transpose()
             .map_err(|e| anyhow!("failed to create speaker notes listener: {e}"))?;
-        Ok(Self { events_listener, events_publisher })
+        // Controlling the presentation from the speaker notes window is optional so don't fail if
+        // that can't be set up.
+        let commands_listener = publish_speaker_notes
+            .then(|| SpeakerNotesCommandListener::new(config, full_presentation_path.clone()))
+            .transpose()
+            .unwrap_or_else(|e| {
+                eprintln!("[presenterm] warning: speaker notes can't control the presentation: {e}");
+                None
+            });
+        let commands_publisher = cli
+            .listen_speaker_notes
+            .then(|| SpeakerNotesCommandPublisher::new(config, full_presentation_path))
+            .transpose()
+            .map_err(|e| anyhow!("failed to create speaker notes command publisher: {e}"))?;
+        Ok(Self { events_listener, events_publisher, commands_listener, commands_publisher })
     }
 }
 
@@ -409,7 +436,7 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
     // Disable this so we don't mess things up when generating PDFs
-    if cli.export_pdf {
+    if cli.export_pdf || cli.export_gif {
         TerminalEmulator::disable_capability_detection();
     }
 
@@ -444,15 +471,13 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                 if let MarkdownElement::Snippet { info, code, .. } = el {
                     if let Ok(snippet) = SnippetParser::parse(info.clone(), code.clone()) {
                         if let SnippetLanguage::Banner { font } = snippet.language {
-                            if font.to_lowercase() != "standard" {
-                                requested.insert(font);
-                            }
+                            requested.insert(font);
                         }
                     }
                 }
             }
             for font in requested {
-                if let Err(e) = BannerGenerator::new(&font) {
+                if let Err(e) = BannerGenerator::load(&font, &resources, &ResourceBasePath::Presentation) {
                     invalid_fonts.push(format!("{font} ({e})"));
                 }
             }
@@ -468,6 +493,7 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     }
 
     if cli.validate_fonts {
+        println!("{}", embedded_fonts_listing());
         println!("All requested FIGlet fonts are valid.");
         return Ok(());
     }
@@ -476,7 +502,7 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     if cli.validate_snippets {
         builder_options.validate_snippets = cli.validate_snippets;
     }
-    if cli.export_pdf || cli.export_html {
+    if cli.export_pdf || cli.export_html || cli.export_gif {
         let dimensions = match config.export.dimensions {
             Some(dimensions) => WindowSize {
                 rows: dimensions.rows,
@@ -497,6 +523,7 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             dimensions,
             config.export.pauses,
             config.export.snippets,
+            config.export.asciinema,
         );
         let output_directory = match cli.export_temporary_path {
             Some(path) => OutputDirectory::external(path),
@@ -504,13 +531,27 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         }?;
         if cli.export_pdf {
             exporter.export_pdf(&path, output_directory, cli.export_output.as_deref(), config.export.pdf)?;
+        } else if cli.export_gif {
+            exporter.export_gif(&path, cli.export_output.as_deref())?;
         } else {
             exporter.export_html(&path, output_directory, cli.export_output.as_deref())?;
         }
     } else {
-        let SpeakerNotesComponents { events_listener, events_publisher } =
+        let SpeakerNotesComponents { events_listener, events_publisher, commands_listener, commands_publisher } =
             SpeakerNotesComponents::new(&cli, &config, &path)?;
-        let command_listener = CommandListener::new(config.bindings.clone(), events_listener)?;
+        // The speaker notes window is driven by the main presentation, so only that one is remote controlled.
+        let (remote_commands, remote_state_publisher) = match config.remote.address {
+            Some(address) if !cli.listen_speaker_notes => {
+                let (commands, publisher) = RemoteControlServer::start(address, config.remote.token.clone())
+                    .map_err(|e| anyhow!("failed to start remote control server: {e}"))?;
+                (Some(commands), Some(publisher))
+            }
+            _ => (None, None),
+        };
+        let command_listener = CommandListener::new(config.bindings.clone(), events_listener)?
+            .with_speaker_notes_command_listener(commands_listener)
+            .with_speaker_notes_command_publisher(commands_publisher)
+            .with_remote_commands(remote_commands);
 
         builder_options.print_modal_background = matches!(graphics_mode, GraphicsMode::Kitty { .. });
         let options = PresenterOptions {
@@ -538,6 +579,7 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             printer,
             options,
             events_publisher,
+            remote_state_publisher,
         );
         presenter.present(&path)?;
     }
@@ -1,4 +1,5 @@
 use crate::{
+    config::SlideTransitionOverride,
     markdown::elements::{MarkdownElement, SourcePosition},
     presentation::builder::{BuildResult, LayoutState, PresentationBuilder, error::InvalidPresentation},
     render::operation::{LayoutGrid, RenderOperation},
@@ -82,7 +83,7 @@ impl PresentationBuilder<'_, '_> {
             CommentCommand::NoFooter => {
                 self.slide_state.ignore_footer = true;
             }
-            CommentCommand::SpeakerNote(_) => {}
+            CommentCommand::SpeakerNote(note) => self.slide_state.speaker_notes.push(note),
             CommentCommand::FontSize(size) => {
                 if size == 0 || size > 7 {
                     return Err(self.invalid_presentation(source_position, InvalidPresentation::InvalidFontSize));
@@ -102,6 +103,9 @@ impl PresentationBuilder<'_, '_> {
             CommentCommand::SkipSlide => {
                 self.slide_state.skip_slide = true;
             }
+            CommentCommand::Transition(transition) => {
+                self.slide_state.transition = Some(transition);
+            }
             CommentCommand::ListItemNewlines(count) => {
                 self.slide_state.list_item_newlines = Some(count.into());
             }
@@ -109,6 +113,10 @@ impl PresentationBuilder<'_, '_> {
                 self.process_include(path, source_position)?;
                 return Ok(());
             }
+            CommentCommand::Animate(name) => {
+                let animation = self.animation(&name.as_str().into(), source_position)?;
+                self.slide_state.animation = Some((animation, source_position));
+            }
             CommentCommand::SnippetOutput(id) => {
                 let handle = self.executable_snippets.get(&id).cloned().ok_or_else(|| {
                     self.invalid_presentation(source_position, InvalidPresentation::UndefinedSnippetId(id))
@@ -186,6 +194,7 @@ impl PresentationBuilder<'_, '_> {
 #[serde(rename_all = "snake_case")]
 pub(crate) enum CommentCommand {
     Alignment(CommentCommandAlignment),
+    Animate(String),
     Column(usize),
     EndSlide,
     FontSize(u8),
@@ -205,6 +214,7 @@ pub(crate) enum CommentCommand {
     SkipSlide,
     SpeakerNote(String),
     SnippetOutput(String),
+    Transition(SlideTransitionOverride),
 }
 
 impl CommentCommand {
@@ -231,6 +241,9 @@ impl CommentCommand {
             format!("<!-- include: file.md -->"),
             format!("<!-- speaker_note: Your note here -->"),
             format!("<!-- snippet_output: identifier -->"),
+            format!("<!-- transition: fade -->"),
+            format!("<!-- transition: none -->"),
+            format!("<!-- animate: typewriter -->"),
         ]
     }
 }
@@ -273,7 +286,10 @@ mod tests {
     use std::{fs, io::BufWriter};
 
     use super::*;
-    use crate::presentation::builder::{PresentationBuilderOptions, utils::Test};
+    use crate::presentation::builder::{
+        PresentationBuilderOptions,
+        utils::{RunAsyncRendersPolicy, Test},
+    };
     use image::{DynamicImage, ImageEncoder, codecs::png::PngEncoder};
     use rstest::rstest;
     use tempfile::tempdir;
@@ -290,6 +306,8 @@ mod tests {
     #[case::incremental_lists("newlines: 2", CommentCommand::NewLines(2))]
     #[case::incremental_lists("new_line", CommentCommand::NewLine)]
     #[case::incremental_lists("newline", CommentCommand::NewLine)]
+    #[case::transition("transition: none", CommentCommand::Transition(SlideTransitionOverride::Disabled))]
+    #[case::animate("animate: typewriter", CommentCommand::Animate("typewriter".into()))]
     fn command_formatting(#[case] input: &str, #[case] expected: CommentCommand) {
         let parsed: CommentCommand = input.parse().expect("deserialization failed");
         assert_eq!(parsed, expected);
@@ -628,6 +646,23 @@ hi
         assert_eq!(lines, expected);
     }
 
+    #[test]
+    fn speaker_notes_kept_in_slides() {
+        let input = "
+hi
+
+<!-- speaker_note: first -->
+<!-- pause -->
+<!-- speaker_note: second -->
+<!-- end_slide -->
+
+bye
+";
+        let presentation = Test::new(input).build();
+        let notes: Vec<_> = presentation.iter_slides().map(|slide| slide.speaker_notes().to_vec()).collect();
+        assert_eq!(notes, &[vec!["first".to_string(), "second".to_string()], vec![]]);
+    }
+
     #[test]
     fn speaker_notes_pause() {
         let input = "
@@ -760,4 +795,32 @@ hi
         let err = Test::new(input).resources_path(path).expect_invalid();
         assert!(err.to_string().contains("was already imported"), "{err:?}");
     }
+
+    #[rstest]
+    #[case::started(RunAsyncRendersPolicy::None, &["▌      ", "   •  ▌", "   •  ▌", "bye    "])]
+    #[case::finished(RunAsyncRendersPolicy::All, &["hi     ", "   •  a", "   •  b", "bye    "])]
+    fn animate(#[case] policy: RunAsyncRendersPolicy, #[case] expected: &[&str]) {
+        let input = "
+<!-- animate: typewriter -->
+hi
+
+<!-- animate: typewriter -->
+* a
+* b
+
+bye
+";
+        let options = PresentationBuilderOptions { banner_animation_duration_millis: 1, ..Default::default() };
+        let lines =
+            Test::new(input).options(options).render().rows(7).columns(7).run_async_renders(policy).into_lines();
+        let lines: Vec<_> = lines.into_iter().filter(|line| !line.trim().is_empty()).collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[rstest]
+    #[case::undefined("<!-- animate: potato -->\nhi")]
+    #[case::code("<!-- animate: typewriter -->\n```rust\nfn main() {}\n```")]
+    fn invalid_animate(#[case] input: &str) {
+        Test::new(input).expect_invalid();
+    }
 }
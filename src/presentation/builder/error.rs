@@ -122,6 +122,15 @@ pub(crate) enum InvalidPresentation {
 
     #[error("snippet id '{0}' already exists")]
     SnippetAlreadyExists(String),
+
+    #[error("animation '{0}' is not defined")]
+    UndefinedAnimation(String),
+
+    #[error("invalid animation '{name}': {error}")]
+    InvalidAnimation { name: String, error: String },
+
+    #[error("only headings, paragraphs and lists can be animated")]
+    UnsupportedAnimationTarget,
 }
 
 #[derive(Clone, Debug)]
@@ -230,7 +239,7 @@ mod tests {
     }
 
     fn make_builder<'a>(source_line: &'a str, error: &'a str) -> ErrorContextBuilder<'a> {
-        let mut builder = ErrorContextBuilder::new(source_line.into(), error.into());
+        let mut builder = ErrorContextBuilder::new(source_line, error);
         builder.prefix_style = Default::default();
         builder.error_style = Default::default();
         builder
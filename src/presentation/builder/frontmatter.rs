@@ -1,5 +1,6 @@
 use crate::{
-    config::OptionsConfig,
+    code::animations::AnimationSpec,
+    config::{OptionsConfig, SlideTransitionOverride},
     markdown::{
         elements::{Line, Text},
         parse::MarkdownParser,
@@ -16,6 +17,7 @@ use crate::{
     theme::{AuthorPositioning, ElementType, PresentationTheme},
 };
 use comrak::Arena;
+use std::collections::BTreeMap;
 
 impl PresentationBuilder<'_, '_> {
     pub(crate) fn process_front_matter(&mut self, contents: &str) -> BuildResult {
@@ -33,6 +35,8 @@ impl PresentationBuilder<'_, '_> {
         if let Some(options) = metadata.options.take() {
             self.options.merge(options);
         }
+        self.default_transition = metadata.transition.take();
+        self.banner_animations = std::mem::take(&mut metadata.animations);
 
         {
             let footer_context = &mut self.footer_vars;
@@ -236,19 +240,40 @@ struct StrictPresentationMetadata {
 
     #[serde(default)]
     options: Option<OptionsConfig>,
+
+    #[serde(default)]
+    transition: Option<SlideTransitionOverride>,
+
+    #[serde(default)]
+    animations: BTreeMap<String, AnimationSpec>,
 }
 
 impl From<StrictPresentationMetadata> for PresentationMetadata {
     fn from(strict: StrictPresentationMetadata) -> Self {
-        let StrictPresentationMetadata { title, sub_title, event, location, date, author, authors, theme, options } =
-            strict;
-        Self { title, sub_title, event, location, date, author, authors, theme, options }
+        let StrictPresentationMetadata {
+            title,
+            sub_title,
+            event,
+            location,
+            date,
+            author,
+            authors,
+            theme,
+            options,
+            transition,
+            animations,
+        } = strict;
+        Self { title, sub_title, event, location, date, author, authors, theme, options, transition, animations }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{presentation::builder::utils::Test, theme::raw};
+    use crate::{
+        config::{SlideTransitionOverride, SlideTransitionStyleConfig},
+        presentation::builder::utils::Test,
+        theme::raw,
+    };
 
     #[test]
     fn multiline_centered_title() {
@@ -280,4 +305,61 @@ title: |
         ];
         assert_eq!(lines, expected);
     }
+
+    #[test]
+    fn default_transition() {
+        let input = "---
+transition: fade
+---
+hi
+
+<!-- end_slide -->
+
+<!-- transition: none -->
+bye
+";
+        let presentation = Test::new(input).build();
+        let transitions: Vec<_> = presentation.iter_slides().map(|slide| slide.transition().cloned()).collect();
+        let fade = SlideTransitionOverride::Enabled {
+            animation: Some(SlideTransitionStyleConfig::Fade),
+            duration_millis: None,
+            frames: None,
+            easing: None,
+        };
+        assert_eq!(transitions, &[Some(fade), Some(SlideTransitionOverride::Disabled)]);
+    }
+
+    #[test]
+    fn layered_transition() {
+        let input = "---
+transition:
+  style: fade
+  duration_millis: 500
+---
+<!-- transition:
+  frames: 10
+-->
+hi
+
+<!-- end_slide -->
+
+<!-- transition: slide_vertical -->
+bye
+";
+        let presentation = Test::new(input).build();
+        let transitions: Vec<_> = presentation.iter_slides().map(|slide| slide.transition().cloned()).collect();
+        let fade = SlideTransitionOverride::Enabled {
+            animation: Some(SlideTransitionStyleConfig::Fade),
+            duration_millis: Some(500),
+            frames: Some(10),
+            easing: None,
+        };
+        let vertical = SlideTransitionOverride::Enabled {
+            animation: Some(SlideTransitionStyleConfig::SlideVertical),
+            duration_millis: Some(500),
+            frames: None,
+            easing: None,
+        };
+        assert_eq!(transitions, &[Some(fade), Some(vertical)]);
+    }
 }
@@ -53,7 +53,7 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
             piece.style = piece.style.size(font_size);
         }
         let alignment = self.slide_state.alignment.unwrap_or_default();
-        self.chunk_operations.push(RenderOperation::RenderBlockLine(BlockLine {
+        self.push_animated_text(RenderOperation::RenderBlockLine(BlockLine {
             prefix: prefix.into(),
             right_padding_length: 0,
             repeat_prefix_on_wrap: false,
@@ -1,10 +1,11 @@
 use crate::{
     code::{
+        animations::{AnimatedText, Animation, AnimationSpec},
         execute::SnippetExecutor,
         highlighting::{HighlightThemeSet, SnippetHighlighter},
-        snippet::SnippetLanguage,
+        snippet::{PosterFrame, SnippetLanguage},
     },
-    config::{KeyBindingsConfig, OptionsConfig},
+    config::{KeyBindingsConfig, OptionsConfig, SlideTransitionOverride},
     markdown::{
         elements::{Line, MarkdownElement, SourcePosition, Text},
         parse::MarkdownParser,
@@ -39,7 +40,7 @@ use crate::{
 };
 use image::DynamicImage;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs, io, iter, mem,
     path::Path,
     rc::Rc,
@@ -96,6 +97,10 @@ pub struct PresentationBuilderOptions {
     pub h1_slide_titles: bool,
     // Duration of a full rainbow banner animation cycle in milliseconds
     pub banner_animation_duration_millis: u16,
+    // The frame asciinema recordings are frozen at when they're not going to be played back
+    pub asciinema_poster: Option<PosterFrame>,
+    // Whether animations are rendered at their last frame rather than played
+    pub freeze_animations: bool,
 }
 
 impl PresentationBuilderOptions {
@@ -146,6 +151,8 @@ impl Default for PresentationBuilderOptions {
             layout_grid: false,
             h1_slide_titles: false,
             banner_animation_duration_millis: 1000,
+            asciinema_poster: None,
+            freeze_animations: false,
         }
     }
 }
@@ -166,6 +173,9 @@ pub(crate) struct PresentationBuilder<'a, 'b> {
     resources: Resources,
     third_party: &'a mut ThirdPartyRender,
     slide_state: SlideState,
+    default_transition: Option<SlideTransitionOverride>,
+    banner_animations: BTreeMap<String, AnimationSpec>,
+    element_animation: Option<Arc<dyn Animation>>,
     presentation_state: PresentationState,
     footer_vars: FooterVariables,
     themes: &'a Themes,
@@ -206,6 +216,9 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
             resources,
             third_party,
             slide_state: Default::default(),
+            default_transition: None,
+            banner_animations: Default::default(),
+            element_animation: None,
             presentation_state: Default::default(),
             footer_vars: Default::default(),
             themes,
@@ -282,9 +295,10 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
         }
 
         let bindings = bindings_modal_builder.build(&self.theme, &self.bindings_config);
-        let slide_index = self.index_builder.build(&self.theme, self.presentation_state.clone());
-        let modals = Modals { slide_index, bindings };
-        let presentation = Presentation::new(slides, modals, self.presentation_state);
+        let (slide_index, slide_index_state) = self.index_builder.build(&self.theme);
+        let modals = Modals { slide_index, slide_index_state, bindings };
+        let speaker_notes_style = self.theme.speaker_notes.clone();
+        let presentation = Presentation::new(slides, modals, speaker_notes_style, self.presentation_state);
         Ok(presentation)
     }
 
@@ -361,6 +375,24 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
 
     fn process_element_for_presentation_mode(&mut self, element: MarkdownElement) -> BuildResult {
         let should_clear_last = !matches!(element, MarkdownElement::List(_) | MarkdownElement::Comment { .. });
+        // An `animate` comment applies to the element that follows it.
+        if !matches!(element, MarkdownElement::Comment { .. }) {
+            if let Some((animation, source_position)) = self.slide_state.animation.take() {
+                let animatable = matches!(
+                    element,
+                    MarkdownElement::SetexHeading { .. }
+                        | MarkdownElement::Heading { .. }
+                        | MarkdownElement::Paragraph(_)
+                        | MarkdownElement::List(_)
+                );
+                if !animatable {
+                    return Err(
+                        self.invalid_presentation(source_position, InvalidPresentation::UnsupportedAnimationTarget)
+                    );
+                }
+                self.element_animation = Some(animation);
+            }
+        }
         match element {
             // This one is processed before everything else as it affects how the rest of the
             // elements is rendered.
@@ -386,6 +418,7 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
         if should_clear_last {
             self.slide_state.last_element = LastElement::Other;
         }
+        self.element_animation = None;
         Ok(())
     }
 
@@ -393,11 +426,9 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
         match element {
             MarkdownElement::Comment { comment, source_position } => self.process_comment(comment, source_position)?,
             MarkdownElement::SetexHeading { text } => self.push_slide_title(text)?,
-            MarkdownElement::ThematicBreak => {
-                if self.options.end_slide_shorthand {
-                    self.terminate_slide();
-                    self.slide_state.ignore_element_line_break = true;
-                }
+            MarkdownElement::ThematicBreak if self.options.end_slide_shorthand => {
+                self.terminate_slide();
+                self.slide_state.ignore_element_line_break = true;
             }
             _ => {}
         }
@@ -499,8 +530,27 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
             }
         }
         if !block.0.is_empty() {
-            self.chunk_operations.push(RenderOperation::RenderText { line: WeightedLine::from(block), alignment });
+            self.push_animated_text(RenderOperation::RenderText { line: WeightedLine::from(block), alignment });
+        }
+    }
+
+    /// Push an operation that renders text, animating it if the element being processed is animated.
+    fn push_animated_text(&mut self, operation: RenderOperation) {
+        let Some(animation) = self.element_animation.clone() else {
+            self.chunk_operations.push(operation);
+            return;
+        };
+        let text = AnimatedText::new(
+            vec![operation],
+            animation,
+            self.banner_palette(),
+            self.theme.default_style.style.colors.foreground,
+            self.options.banner_animation_duration_millis as u64,
+        );
+        if self.options.freeze_animations {
+            text.freeze();
         }
+        self.chunk_operations.push(RenderOperation::RenderAsync(Rc::new(text)));
     }
 
     fn push_line_break(&mut self) {
@@ -521,9 +571,14 @@ impl<'a, 'b> PresentationBuilder<'a, 'b> {
         let chunks = mem::take(&mut self.slide_chunks);
 
         if !self.slide_state.skip_slide {
-            let builder = SlideBuilder::default().chunks(chunks);
-            self.index_builder
-                .add_title(self.slide_state.title.take().unwrap_or_else(|| Text::from("<no title>").into()));
+            let transition = match (self.slide_state.transition.take(), &self.default_transition) {
+                (Some(transition), Some(default)) => Some(transition.layer(default)),
+                (transition, default) => transition.or_else(|| default.clone()),
+            };
+            let speaker_notes = mem::take(&mut self.slide_state.speaker_notes);
+            let title = self.slide_state.title.take().unwrap_or_else(|| Text::from("<no title>").into());
+            self.index_builder.add_slide(title, chunks.iter().flat_map(SlideChunk::iter_operations));
+            let builder = SlideBuilder::default().chunks(chunks).transition(transition).speaker_notes(speaker_notes);
 
             if self.slide_state.ignore_footer {
                 self.slides_without_footer.insert(self.slide_builders.len());
@@ -603,6 +658,9 @@ struct SlideState {
     alignment: Option<Alignment>,
     skip_slide: bool,
     last_layout_comment: Option<FileSourcePosition>,
+    transition: Option<SlideTransitionOverride>,
+    animation: Option<(Arc<dyn Animation>, SourcePosition)>,
+    speaker_notes: Vec<String>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -1,21 +1,26 @@
 use super::{BuildError, BuildResult};
 use crate::{
     code::{
-        asciinema::{AsciinemaPlayer, AsciinemaRecording, AsciinemaResumeMutator},
-        banner::{BannerGenerator, MultiBannerContext, MultiBannerLine, MultiBannerLineStatic, MultiBannerMutator, RainbowBannerAnimation},
+        animations::{Animation, AnimationPalette, AnimationSpec, CustomAnimation, get_animation},
+        asciinema::{AsciinemaPlayer, AsciinemaRecording, AsciinemaStepMutator, PlaybackOptions},
+        banner::{
+            BannerArt, BannerGenerator, FittedBannerArt, MultiBannerContext, MultiBannerLine, MultiBannerLineStatic,
+            MultiBannerMutator, RainbowBannerAnimation, StaticBanner, default_fit_fonts,
+        },
         execute::{LanguageSnippetExecutor},
         snippet::{
-            AsciinemaLoop, AsciinemaStart, BannerAnimation, BannerAnimationStyle, ExternalFile, Highlight, HighlightContext, HighlightGroup, HighlightMutator,
-            HighlightedLine, Snippet, SnippetExec, SnippetExecutorSpec, SnippetLanguage, SnippetLine, SnippetParser,
+            AsciinemaLoop, AsciinemaStart, BannerAnimation, BannerAnimationStyle, BannerLayout, ExternalFile, Highlight, HighlightContext, HighlightGroup, HighlightMutator,
+            HighlightedLine, Snippet, SnippetAttributes, SnippetExec, SnippetExecutorSpec, SnippetLanguage, SnippetLine, SnippetParser,
             SnippetRepr, SnippetSplitter,
         },
     },
-    markdown::elements::SourcePosition,
+    markdown::{elements::SourcePosition, text_style::TextStyle},
     presentation::builder::{PresentationBuilder, error::InvalidPresentation},
     render::{
         operation::{AsRenderOperations, RenderAsyncStartPolicy, RenderOperation},
         properties::WindowSize,
     },
+    resource::ResourceBasePath,
     theme::{Alignment, CodeBlockStyle},
     third_party::ThirdPartyRenderRequest,
     ui::execution::{
@@ -26,7 +31,7 @@ use crate::{
     },
 };
 use itertools::Itertools;
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, io, iter, rc::Rc, sync::Arc};
 
 impl PresentationBuilder<'_, '_> {
     pub(crate) fn push_code(&mut self, info: String, code: String, source_position: SourcePosition) -> BuildResult {
@@ -80,6 +85,14 @@ impl PresentationBuilder<'_, '_> {
         };
 
         let block_length = self.push_code_lines(&snippet);
+        if let Some(path) = &snippet.attributes.record {
+            // Replaying a recording doesn't run anything so it doesn't need execution to be enabled.
+            if self.resources.resolve_path(path, &self.resource_base_path()).exists() {
+                let recording = self.load_asciinema(&path.to_string_lossy(), source_position)?;
+                self.push_line_break();
+                return self.push_asciinema_player(recording, &snippet.attributes);
+            }
+        }
         match snippet.attributes.execution.clone() {
             SnippetExec::None => Ok(()),
             SnippetExec::Exec(_) | SnippetExec::AutoExec(_) | SnippetExec::AcquireTerminal(_) if !execution_allowed => {
@@ -278,16 +291,107 @@ impl PresentationBuilder<'_, '_> {
         style
     }
 
+    /// The style for static banners, which use the theme's banner color when it sets one.
+    fn static_banner_style(&self, font_size: u8) -> TextStyle {
+        let style = TextStyle::default().size(font_size);
+        match self.theme.banner.color {
+            Some(color) => style.fg_color(color),
+            None => style,
+        }
+    }
+
+    /// The palette animated banners map their colors onto.
+    pub(crate) fn banner_palette(&self) -> AnimationPalette {
+        AnimationPalette::new(self.theme.banner.gradient.clone())
+    }
+
+    /// Get the animation for a style, looking custom ones up in the front matter and then
+    /// in the animations directory.
+    pub(crate) fn animation(
+        &self,
+        style: &BannerAnimationStyle,
+        source_position: SourcePosition,
+    ) -> Result<Arc<dyn Animation>, BuildError> {
+        let BannerAnimationStyle::Custom(name) = style else {
+            return Ok(get_animation(style).expect("not a built in animation"));
+        };
+        let invalid = |error: String| {
+            self.invalid_presentation(source_position, InvalidPresentation::InvalidAnimation { name: name.clone(), error })
+        };
+        let spec = match self.banner_animations.get(name) {
+            Some(spec) => spec.clone(),
+            None => {
+                let base_path = ResourceBasePath::Custom(self.resources.animations_path());
+                let contents = match self.resources.external_text_file(format!("{name}.yaml"), &base_path) {
+                    Ok(contents) => contents,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        let error = InvalidPresentation::UndefinedAnimation(name.clone());
+                        return Err(self.invalid_presentation(source_position, error));
+                    }
+                    Err(e) => return Err(invalid(e.to_string())),
+                };
+                serde_yaml::from_str::<AnimationSpec>(&contents).map_err(|e| invalid(e.to_string()))?
+            }
+        };
+        let animation = CustomAnimation::new(&spec, &self.theme.palette).map_err(|e| invalid(e.to_string()))?;
+        Ok(Arc::new(animation))
+    }
+
+    /// Load the generators for a banner's font, followed by the ones it falls back to when fitted.
+    fn banner_generators(
+        &self,
+        font: &str,
+        fallback_fonts: &[String],
+        layout: Option<BannerLayout>,
+        source_position: SourcePosition,
+    ) -> Result<Vec<Rc<BannerGenerator>>, BuildError> {
+        let base_path = self.resource_base_path();
+        let mut generators = Vec::new();
+        for font in iter::once(font).chain(fallback_fonts.iter().map(String::as_str)) {
+            let generator = BannerGenerator::load(font, &self.resources, &base_path)
+                .map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))?;
+            generators.push(Rc::new(generator.with_layout(layout)));
+        }
+        Ok(generators)
+    }
+
+    /// Generate the ASCII art for a line in a banner.
+    fn banner_art(
+        &self,
+        line: &str,
+        generators: &[Rc<BannerGenerator>],
+        fitted: bool,
+        source_position: SourcePosition,
+    ) -> Result<BannerArt, BuildError> {
+        let art = if fitted {
+            FittedBannerArt::new(line.to_string(), generators.to_vec()).map(BannerArt::Fitted)
+        } else {
+            generators[0].generate_lines(line).map(BannerArt::Fixed)
+        };
+        art.map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))
+    }
+
+    fn push_banner_animation(&mut self, animation: RainbowBannerAnimation) {
+        if self.options.freeze_animations {
+            animation.freeze();
+        }
+        self.chunk_operations.push(RenderOperation::RenderAsync(Rc::new(animation)));
+    }
+
     fn push_banner(&mut self, snippet: Snippet, font: &str, source_position: SourcePosition) -> BuildResult {
         use crate::markdown::text::{WeightedLine, WeightedText};
         use crate::markdown::elements::Text;
-        use crate::markdown::text_style::TextStyle;
         use crate::render::operation::BlockLine;
-        use std::rc::Rc;
 
-        // Generate ASCII art using the banner generator
-        let generator = BannerGenerator::new(font)
-            .map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))?;
+        // Load the fonts used to generate the ASCII art
+        let fallback_fonts = match snippet.attributes.fit.as_deref() {
+            Some([]) => default_fit_fonts(font),
+            Some(fonts) => fonts.to_vec(),
+            None => Vec::new(),
+        };
+        let layout = snippet.attributes.layout;
+        let generators = self.banner_generators(font, &fallback_fonts, layout, source_position)?;
+        let fitted = snippet.attributes.fit.is_some();
 
         // Split content into lines - each line becomes a separate banner word
         let lines: Vec<String> = snippet.contents.lines().map(|s| s.to_string()).collect();
@@ -305,7 +409,7 @@ impl PresentationBuilder<'_, '_> {
 
         // For multi-line banners, create all animations and use a mutator to cycle through them
         if lines.len() > 1 {
-            use std::sync::{Arc, Mutex};
+            use std::sync::Mutex;
 
             let (style, loop_animation) = match &snippet.attributes.animation {
                 BannerAnimation::None => (BannerAnimationStyle::Rainbow, false), // Used for animated case only
@@ -321,47 +425,39 @@ impl PresentationBuilder<'_, '_> {
             match &snippet.attributes.animation {
                 BannerAnimation::None => {
                     // Static: pre-generate all banners and render without animation
-                    let mut banners: Vec<Vec<String>> = Vec::new();
-                    let mut lengths: Vec<u16> = Vec::new();
+                    let text_style = self.static_banner_style(font_size);
+                    let mut banners = Vec::new();
                     for line in lines.iter() {
-                        let ascii_art = generator.generate(line)
-                            .map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))?;
-                        let ascii_lines: Vec<String> = ascii_art.lines().map(|s| s.to_string()).collect();
-
-                        let max_width = ascii_lines.iter().map(|l| l.len()).max().unwrap_or(0);
-                        let block_length = alignment.adjust_size((max_width * font_size as usize) as u16);
-                        banners.push(ascii_lines);
-                        lengths.push(block_length);
+                        let art = self.banner_art(line, &generators, fitted, source_position)?;
+                        banners.push(StaticBanner::new(art, alignment, text_style));
                     }
 
-                    let multi_banner = MultiBannerLineStatic::new(banners, lengths, alignment, font_size, context.clone());
+                    let multi_banner = MultiBannerLineStatic::new(banners, context.clone());
                     self.chunk_operations.push(RenderOperation::RenderDynamic(Rc::new(multi_banner)));
                 }
                 BannerAnimation::Animated { .. } => {
                     // Animated: generate animations for all banner lines
+                    let animation = self.animation(&style, source_position)?;
                     let mut animations = Vec::new();
                     for line in lines.iter() {
-                        let ascii_art = generator.generate(line)
-                            .map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))?;
-                        let ascii_lines: Vec<String> = ascii_art.lines().map(|s| s.to_string()).collect();
-
-                        let max_width = ascii_lines.iter().map(|l| l.len()).max().unwrap_or(0);
-                        let block_length = alignment.adjust_size((max_width * font_size as usize) as u16);
-
+                        let art = self.banner_art(line, &generators, fitted, source_position)?;
                         let animation = RainbowBannerAnimation::new(
-                            ascii_lines,
-                            block_length,
+                            art,
                             alignment,
                             font_size,
-                            style.clone(),
+                            animation.clone(),
                             loop_animation,
                             duration,
-                        );
+                        )
+                        .with_palette(self.banner_palette());
                         animations.push(animation);
                     }
 
                     // Create MultiBannerLine that renders the current word based on context
                     let multi_banner = MultiBannerLine::new(animations, context.clone());
+                    if self.options.freeze_animations {
+                        multi_banner.freeze();
+                    }
                     self.chunk_operations.push(RenderOperation::RenderAsync(Rc::new(multi_banner)));
                 }
             }
@@ -373,25 +469,21 @@ impl PresentationBuilder<'_, '_> {
         } else {
             // Single line banner - use the original simple approach
             let line = lines.first().unwrap_or(&String::new()).clone();
-
-            // Generate ASCII art for this line
-            let ascii_art = generator.generate(&line)
-                .map_err(|e| self.invalid_presentation(source_position, InvalidPresentation::Snippet(e.to_string())))?;
-            let ascii_lines: Vec<String> = ascii_art.lines().map(|s| s.to_string()).collect();
-
-            // Calculate block length for this banner
-            let max_width = ascii_lines.iter()
-                .map(|line| line.len())
-                .max()
-                .unwrap_or(0);
-            let block_length = (max_width * font_size as usize) as u16;
-            let block_length = alignment.adjust_size(block_length);
+            let art = self.banner_art(&line, &generators, fitted, source_position)?;
 
             // Render based on animation mode
-            match &snippet.attributes.animation {
-                BannerAnimation::None => {
-                    // Static plain rendering - no colors, just use theme defaults
-                    let text_style = TextStyle::default().size(font_size);
+            match (&snippet.attributes.animation, art) {
+                (BannerAnimation::None, BannerArt::Fixed(ascii_lines)) => {
+                    // Static rendering in the theme's banner color, if any
+                    let text_style = self.static_banner_style(font_size);
+
+                    // Calculate block length for this banner
+                    let max_width = ascii_lines.iter()
+                        .map(|line| line.len())
+                        .max()
+                        .unwrap_or(0);
+                    let block_length = (max_width * font_size as usize) as u16;
+                    let block_length = alignment.adjust_size(block_length);
 
                     for ascii_line in &ascii_lines {
                         let text = Text::new(ascii_line, text_style);
@@ -409,18 +501,24 @@ impl PresentationBuilder<'_, '_> {
                         self.chunk_operations.push(RenderOperation::RenderLineBreak);
                     }
                 }
-                BannerAnimation::Animated { style, loop_animation } => {
+                (BannerAnimation::None, art) => {
+                    // Fitted static banners are generated when rendered
+                    let banner = StaticBanner::new(art, alignment, self.static_banner_style(font_size));
+                    self.chunk_operations.push(RenderOperation::RenderDynamic(Rc::new(banner)));
+                }
+                (BannerAnimation::Animated { style, loop_animation }, art) => {
                     // Animated banner
+                    let animation = self.animation(style, source_position)?;
                     let animation = RainbowBannerAnimation::new(
-                        ascii_lines,
-                        block_length,
+                        art,
                         alignment,
                         font_size,
-                        style.clone(),
+                        animation,
                         *loop_animation,
                         duration,
-                    );
-                    self.chunk_operations.push(RenderOperation::RenderAsync(Rc::new(animation)));
+                    )
+                    .with_palette(self.banner_palette());
+                    self.push_banner_animation(animation);
                 }
             }
         }
@@ -429,12 +527,10 @@ impl PresentationBuilder<'_, '_> {
         Ok(())
     }
 
-    fn push_ascii(&mut self, snippet: Snippet, _source_position: SourcePosition) -> BuildResult {
+    fn push_ascii(&mut self, snippet: Snippet, source_position: SourcePosition) -> BuildResult {
         use crate::markdown::text::{WeightedLine, WeightedText};
         use crate::markdown::elements::Text;
-        use crate::markdown::text_style::TextStyle;
         use crate::render::operation::BlockLine;
-        use std::rc::Rc;
 
         // Take the input verbatim as lines
         let ascii_lines: Vec<String> = snippet.contents.lines().map(|s| s.to_string()).collect();
@@ -451,9 +547,9 @@ impl PresentationBuilder<'_, '_> {
         let duration = self.options.banner_animation_duration_millis as u64;
 
         match &snippet.attributes.animation {
-            // Static plain rendering (no animation, no rainbow) - just use theme defaults
+            // Static rendering (no animation, no rainbow) in the theme's banner color, if any
             BannerAnimation::None => {
-                let text_style = TextStyle::default().size(font_size);
+                let text_style = self.static_banner_style(font_size);
                 for line in ascii_lines.iter() {
                     let text = Text::new(line, text_style);
                     let weighted_line = WeightedLine::from(vec![text]);
@@ -472,16 +568,17 @@ impl PresentationBuilder<'_, '_> {
             }
             // Animated – reuse RainbowBannerAnimation with the selected style
             BannerAnimation::Animated { style, loop_animation } => {
+                let animation = self.animation(style, source_position)?;
                 let animation = RainbowBannerAnimation::new(
-                    ascii_lines,
-                    block_length,
+                    BannerArt::Fixed(ascii_lines),
                     alignment,
                     font_size,
-                    style.clone(),
+                    animation,
                     *loop_animation,
                     duration,
-                );
-                self.chunk_operations.push(RenderOperation::RenderAsync(Rc::new(animation)));
+                )
+                .with_palette(self.banner_palette());
+                self.push_banner_animation(animation);
             }
         }
 
@@ -490,65 +587,86 @@ impl PresentationBuilder<'_, '_> {
     }
 
     fn push_asciinema(&mut self, snippet: Snippet, source_position: SourcePosition) -> BuildResult {
-        use std::rc::Rc;
+        // The snippet contents are either the cast itself or a path to a .cast file
+        let recording = self.load_asciinema(snippet.contents.trim(), source_position)?;
+        self.push_asciinema_player(recording, &snippet.attributes)
+    }
 
-        // The snippet contents should be a path to a .cast file
-        let cast_path = snippet.contents.trim();
-        let base_path = self.resource_base_path();
+    fn load_asciinema(
+        &self,
+        contents: &str,
+        source_position: SourcePosition,
+    ) -> Result<AsciinemaRecording, BuildError> {
+        let (cast_content, cast_name) = if contents.starts_with('{') {
+            (contents.to_string(), "<inline>")
+        } else {
+            let base_path = self.resource_base_path();
+            let cast_content = if contents.ends_with(".gz") {
+                self.resources.external_gzip_text_file(contents, &base_path)
+            } else {
+                self.resources.external_text_file(contents, &base_path)
+            };
+            let cast_content = cast_content.map_err(|e| {
+                self.invalid_presentation(
+                    source_position,
+                    InvalidPresentation::Snippet(format!("failed to load asciinema file '{contents}': {e}")),
+                )
+            })?;
+            (cast_content, contents)
+        };
 
-        // Load the cast file
-        let cast_content = self.resources.external_text_file(cast_path, &base_path)
-            .map_err(|e| self.invalid_presentation(
+        AsciinemaRecording::from_cast(&cast_content).map_err(|e| {
+            self.invalid_presentation(
                 source_position,
-                InvalidPresentation::Snippet(format!("failed to load asciinema file '{}': {}", cast_path, e))
-            ))?;
+                InvalidPresentation::Snippet(format!("failed to parse asciinema file '{cast_name}': {e}")),
+            )
+        })
+    }
 
-        // Parse the recording
-        let recording = AsciinemaRecording::from_cast(&cast_content)
-            .map_err(|e| self.invalid_presentation(
-                source_position,
-                InvalidPresentation::Snippet(format!("failed to parse asciinema file '{}': {}", cast_path, e))
-            ))?;
+    fn push_asciinema_player(
+        &mut self,
+        mut recording: AsciinemaRecording,
+        attributes: &SnippetAttributes,
+    ) -> BuildResult {
+        // Apply the idle time limit before trimming so the range matches what asciinema shows
+        let timing = &attributes.asciinema_timing;
+        if let Some(limit) = timing.idle_limit.or(recording.idle_time_limit()) {
+            recording.limit_idle_time(limit);
+        }
+        if let Some(range) = &timing.range {
+            recording.trim(range.start, range.end);
+        }
 
         // Get style settings
         let font_size = self.slide_font_size();
-        let alignment = self.theme.code.alignment;
-
-        // Calculate block length based on terminal width
-        // Add 2 for the frame borders (│ on each side)
-        let content_width_chars = recording.width() as usize + 2;
-        let block_length = alignment.adjust_size((content_width_chars * font_size as usize) as u16);
+        let style = self.theme.asciinema.clone();
 
         // Get playback settings from attributes
-        let loop_playback = matches!(snippet.attributes.asciinema_loop, AsciinemaLoop::Loop);
-        let is_wait_mode = matches!(snippet.attributes.asciinema_start, AsciinemaStart::Wait);
+        let loop_playback = matches!(attributes.asciinema_loop, AsciinemaLoop::Loop);
+        let is_wait_mode = matches!(attributes.asciinema_start, AsciinemaStart::Wait);
+        let has_markers = !recording.markers().is_empty();
 
-        // Wait mode uses OnDemand (starts when slide shown, but paused)
+        // Wait mode and recordings with markers use OnDemand (starts when slide shown, possibly paused)
         // Auto mode uses Automatic (starts immediately)
-        let start_policy = if is_wait_mode {
+        let start_policy = if is_wait_mode || has_markers {
             RenderAsyncStartPolicy::OnDemand
         } else {
             RenderAsyncStartPolicy::Automatic
         };
 
-        // Playback speed (could be made configurable via attributes)
-        let speed = 1.0;
-
-        // Create the player - pass is_wait_mode separately
-        let player = Rc::new(AsciinemaPlayer::new(
-            recording,
-            block_length,
-            alignment,
-            font_size,
+        let options = PlaybackOptions {
             loop_playback,
-            speed,
+            speed: timing.speed.unwrap_or(1.0),
             start_policy,
-            is_wait_mode,  // Pass wait mode flag
-        ));
-
-        // For wait mode, add a chunk mutator to resume playback
-        if is_wait_mode {
-            self.chunk_mutators.push(Box::new(AsciinemaResumeMutator::new(player.clone())));
+            wait: is_wait_mode,
+            show_progress: attributes.asciinema_progress,
+        };
+        let player = Rc::new(AsciinemaPlayer::new(recording, style, font_size, options));
+        if let Some(poster) = self.options.asciinema_poster {
+            player.freeze(timing.poster.unwrap_or(poster));
+        } else if player.steps() > 0 {
+            // Starting the recording and playing past each marker are done when moving forward
+            self.chunk_mutators.push(Box::new(AsciinemaStepMutator::new(player.clone())));
         }
 
         // Add to render operations
@@ -591,13 +709,18 @@ impl PresentationBuilder<'_, '_> {
     ) -> BuildResult {
         let executor = self.snippet_executor.language_executor(&snippet.language, spec)?;
         let block_length = self.theme.code.alignment.adjust_size(block_length);
-        let operation = RunAcquireTerminalSnippet::new(
+        let base_path = self.resource_base_path();
+        let record = snippet.attributes.record.as_ref().map(|path| self.resources.resolve_path(path, &base_path));
+        let mut operation = RunAcquireTerminalSnippet::new(
             snippet,
             executor,
             self.theme.execution_output.status,
             block_length,
             self.slide_font_size(),
         );
+        if let Some(path) = record {
+            operation = operation.record(path);
+        }
         let operation = RenderOperation::RenderAsync(Rc::new(operation));
         self.chunk_operations.push(operation);
         Ok(())
@@ -668,12 +791,19 @@ impl AsRenderOperations for Differ {
 mod tests {
     use super::*;
     use crate::{
+        code::{
+            banner::{art_width, single_row_font},
+            snippet::PosterFrame,
+        },
         markdown::text_style::Color,
-        presentation::builder::utils::{RunAsyncRendersPolicy, Test},
+        presentation::builder::{
+            PresentationBuilderOptions,
+            utils::{RunAsyncRendersPolicy, Test},
+        },
         theme::raw,
     };
     use rstest::rstest;
-    use std::fs;
+    use std::{fs, io::Write};
 
     #[rstest]
     #[case::no_filters(None, None, &["a", "b", "c", "d", "e"])]
@@ -1116,4 +1246,167 @@ echo "{qr}"
         let expected: Vec<_> = [empty.as_str()].into_iter().chain(qr.lines()).chain([empty.as_str()]).collect();
         assert_eq!(lines, expected);
     }
+
+    #[test]
+    fn inline_asciinema() {
+        let input = r#"
+```asciinema
+{"version": 3, "term": {"cols": 4, "rows": 1}}
+[0.0, "o", "hi"]
+[1.0, "o", "!"]
+```"#;
+        let lines =
+            Test::new(input).render().run_async_renders(RunAsyncRendersPolicy::None).rows(4).columns(8).into_lines();
+        let expected = &["        ", "┌────┐  ", "│hi  │  ", "└────┘  "];
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn gzip_asciinema() {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+
+        let temp = tempfile::Builder::new().suffix(".cast.gz").tempfile().expect("failed to create tempfile");
+        let mut encoder = GzEncoder::new(fs::File::create(temp.path()).unwrap(), Compression::default());
+        encoder.write_all(b"{\"version\": 2, \"width\": 4, \"height\": 1}\n[0.0, \"o\", \"hi\"]\n").unwrap();
+        encoder.finish().unwrap();
+
+        let path = temp.path().to_string_lossy();
+        let input = format!("```asciinema\n{path}\n```");
+        let lines =
+            Test::new(input).render().run_async_renders(RunAsyncRendersPolicy::None).rows(4).columns(8).into_lines();
+        let expected = &["        ", "┌────┐  ", "│hi  │  ", "└────┘  "];
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn asciinema_theme() {
+        let theme = r"
+asciinema:
+  alignment: center
+  border:
+    style: rounded
+  title_bar:
+    enabled: true
+  padding:
+    horizontal: 1
+";
+        let theme: raw::PresentationTheme = serde_yaml::from_str(theme).expect("invalid theme");
+        let input = r#"
+```asciinema
+{"version": 2, "width": 4, "height": 1, "title": "demo"}
+[0.0, "o", "hi"]
+```"#;
+        let lines = Test::new(input)
+            .theme(theme)
+            .render()
+            .run_async_renders(RunAsyncRendersPolicy::None)
+            .rows(6)
+            .columns(12)
+            .into_lines();
+        let expected =
+            &["            ", "  ╭──────╮  ", "  │ demo │  ", "  ├──────┤  ", "  │ hi   │  ", "  ╰──────╯  "];
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn replay_recording() {
+        let mut temp = tempfile::Builder::new().suffix(".cast").tempfile().expect("failed to create tempfile");
+        writeln!(temp, "{{\"version\": 2, \"width\": 4, \"height\": 1}}\n[0.0, \"o\", \"hi\"]").unwrap();
+
+        let path = temp.path().to_string_lossy();
+        let input = format!("```bash +acquire_terminal +record:{path}\necho hi\n```");
+        let lines = Test::new(input).render().rows(8).columns(16).into_lines();
+        assert!(lines.iter().any(|line| line.contains("│hi  │")), "{lines:#?}");
+    }
+
+    #[rstest]
+    #[case::default("", "hi! ")]
+    #[case::explicit(" +poster:0.5", "hi  ")]
+    fn asciinema_poster(#[case] attributes: &str, #[case] expected: &str) {
+        let input = format!(
+            r#"
+```asciinema +start:wait{attributes}
+{{"version": 2, "width": 4, "height": 1}}
+[0.0, "o", "hi"]
+[1.0, "o", "!"]
+```"#
+        );
+        let options = PresentationBuilderOptions { asciinema_poster: Some(PosterFrame::End), ..Default::default() };
+        let lines = Test::new(input).options(options).render().rows(4).columns(8).into_lines();
+        assert_eq!(lines[2], format!("│{expected}│  "));
+    }
+
+    #[test]
+    fn front_matter_banner_animation() {
+        let input = r#"---
+animations:
+  stars:
+    color:
+      hsl: { hue: 0 }
+    glyphs: { when: 1, characters: "*" }
+---
+```ascii +animate:stars
+ab
+```"#;
+        let lines =
+            Test::new(input).render().run_async_renders(RunAsyncRendersPolicy::None).rows(2).columns(6).into_lines();
+        assert_eq!(lines, &["      ", "  **  "]);
+    }
+
+    #[test]
+    fn themes_directory_banner_animation() {
+        let directory = tempfile::tempdir().expect("failed to create tempdir");
+        fs::create_dir(directory.path().join("animations")).unwrap();
+        let spec = "color: { gradient: { stops: [ff0000], position: 0 } }\nglyphs: { when: 1, table: { a: '#' } }";
+        fs::write(directory.path().join("animations/hashes.yaml"), spec).unwrap();
+
+        let input = "```ascii +animate:hashes\nab\n```";
+        let lines = Test::new(input)
+            .resources_path(directory.path())
+            .render()
+            .run_async_renders(RunAsyncRendersPolicy::None)
+            .rows(2)
+            .columns(6)
+            .into_lines();
+        assert_eq!(lines, &["      ", "  #b  "]);
+    }
+
+    #[rstest]
+    #[case::undefined("")]
+    #[case::invalid_expression("animations: { bad: { color: { hsl: { hue: 'hue +' } } } }")]
+    #[case::invalid_color("animations: { bad: { color: { gradient: { stops: [red], position: 0 } } } }")]
+    fn invalid_banner_animation(#[case] front_matter: &str) {
+        let input = format!("---\n{front_matter}\n---\n```ascii +animate:bad\nab\n```");
+        Test::new(input).expect_invalid();
+    }
+
+    #[rstest]
+    #[case::wide(40, "hello world")]
+    #[case::narrow(8, "hello")]
+    fn fitted_banner(#[case] columns: u16, #[case] first_line: &str) {
+        let directory = tempfile::tempdir().expect("failed to create tempdir");
+        fs::create_dir(directory.path().join("fonts")).unwrap();
+        fs::write(directory.path().join("fonts/single.flf"), single_row_font()).unwrap();
+
+        let input = "```banner +fit:single\nhello world\n```";
+        let lines = Test::new(input).resources_path(directory.path()).render().rows(3).columns(columns).into_lines();
+        assert_eq!(lines[1].trim(), first_line);
+    }
+
+    #[test]
+    fn fitted_banner_default_fonts() {
+        // Only the narrowest of the default fonts fits in this width
+        let mini = BannerGenerator::new("presenterm-mini").unwrap().generate_lines("hello").unwrap();
+        let columns = art_width(&mini) as u16;
+        let render = |input: &str| Test::new(input).render().rows(mini.len() as u16 + 2).columns(columns).into_lines();
+        let fitted = render("```banner +fit\nhello\n```");
+        assert_eq!(fitted, render("```banner:presenterm-mini\nhello\n```"));
+    }
+
+    #[test]
+    fn fitted_banner_missing_font() {
+        let input = "```banner +fit:missing\nhello\n```";
+        Test::new(input).expect_invalid();
+    }
 }
@@ -48,7 +48,7 @@ impl PresentationBuilder<'_, '_> {
                 flattened_row.0.push(Text::from(" │ "));
             }
             let text_length = text.width();
-            flattened_row.0.extend(text.0.into_iter());
+            flattened_row.0.extend(text.0);
 
             let cell_width = widths[column];
             if text_length < cell_width {
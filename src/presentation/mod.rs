@@ -1,7 +1,14 @@
-use crate::{config::OptionsConfig, render::operation::RenderOperation};
+use crate::{
+    code::animations::AnimationSpec,
+    config::{OptionsConfig, SlideTransitionOverride},
+    render::operation::RenderOperation,
+    theme::SpeakerNotesStyle,
+    ui::modals::SlideIndexState,
+};
 use serde::Deserialize;
 use std::{
     cell::RefCell,
+    collections::BTreeMap,
     fmt::Debug,
     ops::Deref,
     rc::Rc,
@@ -15,6 +22,7 @@ pub(crate) mod poller;
 #[derive(Debug)]
 pub(crate) struct Modals {
     pub(crate) slide_index: Vec<RenderOperation>,
+    pub(crate) slide_index_state: SlideIndexState,
     pub(crate) bindings: Vec<RenderOperation>,
 }
 
@@ -23,13 +31,19 @@ pub(crate) struct Modals {
 pub(crate) struct Presentation {
     slides: Vec<Slide>,
     modals: Modals,
+    speaker_notes_style: SpeakerNotesStyle,
     pub(crate) state: PresentationState,
 }
 
 impl Presentation {
     /// Construct a new presentation.
-    pub(crate) fn new(slides: Vec<Slide>, modals: Modals, state: PresentationState) -> Self {
-        Self { slides, modals, state }
+    pub(crate) fn new(
+        slides: Vec<Slide>,
+        modals: Modals,
+        speaker_notes_style: SpeakerNotesStyle,
+        state: PresentationState,
+    ) -> Self {
+        Self { slides, modals, speaker_notes_style, state }
     }
 
     /// Iterate the slides in this presentation.
@@ -47,11 +61,21 @@ impl Presentation {
         self.modals.slide_index.iter()
     }
 
+    /// The state of the slide index modal.
+    pub(crate) fn slide_index_state(&self) -> &SlideIndexState {
+        &self.modals.slide_index_state
+    }
+
     /// Iterate the operations that render the key bindings modal.
     pub(crate) fn iter_bindings_operations(&self) -> impl Iterator<Item = &RenderOperation> {
         self.modals.bindings.iter()
     }
 
+    /// The style for the presenter view in the speaker notes window.
+    pub(crate) fn speaker_notes_style(&self) -> &SpeakerNotesStyle {
+        &self.speaker_notes_style
+    }
+
     /// Consume this presentation and return its slides.
     pub(crate) fn into_slides(self) -> Vec<Slide> {
         self.slides
@@ -167,8 +191,8 @@ impl Presentation {
 
 impl From<Vec<Slide>> for Presentation {
     fn from(slides: Vec<Slide>) -> Self {
-        let modals = Modals { slide_index: vec![], bindings: vec![] };
-        Self::new(slides, modals, Default::default())
+        let modals = Modals { slide_index: vec![], slide_index_state: Default::default(), bindings: vec![] };
+        Self::new(slides, modals, Default::default(), Default::default())
     }
 }
 
@@ -210,6 +234,8 @@ impl PresentationState {
 pub(crate) struct SlideBuilder {
     chunks: Vec<SlideChunk>,
     footer: Vec<RenderOperation>,
+    transition: Option<SlideTransitionOverride>,
+    speaker_notes: Vec<String>,
 }
 
 impl SlideBuilder {
@@ -223,8 +249,18 @@ impl SlideBuilder {
         self
     }
 
+    pub(crate) fn transition(mut self, transition: Option<SlideTransitionOverride>) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    pub(crate) fn speaker_notes(mut self, speaker_notes: Vec<String>) -> Self {
+        self.speaker_notes = speaker_notes;
+        self
+    }
+
     pub(crate) fn build(self) -> Slide {
-        Slide::new(self.chunks, self.footer)
+        Slide { transition: self.transition, speaker_notes: self.speaker_notes, ..Slide::new(self.chunks, self.footer) }
     }
 }
 
@@ -237,11 +273,24 @@ pub(crate) struct Slide {
     chunks: Vec<SlideChunk>,
     footer: Vec<RenderOperation>,
     visible_chunks: usize,
+    transition: Option<SlideTransitionOverride>,
+    speaker_notes: Vec<String>,
 }
 
 impl Slide {
     pub(crate) fn new(chunks: Vec<SlideChunk>, footer: Vec<RenderOperation>) -> Self {
-        Self { chunks, footer, visible_chunks: 1 }
+        Self { chunks, footer, visible_chunks: 1, transition: None, speaker_notes: Vec::new() }
+    }
+
+    /// The speaker notes defined in this slide.
+    pub(crate) fn speaker_notes(&self) -> &[String] {
+        &self.speaker_notes
+    }
+
+    /// The transition used when moving between this slide and the one before it, if it overrides
+    /// the configured one.
+    pub(crate) fn transition(&self) -> Option<&SlideTransitionOverride> {
+        self.transition.as_ref()
     }
 
     pub(crate) fn iter_operations(&self) -> impl Iterator<Item = &RenderOperation> + Clone {
@@ -425,6 +474,14 @@ pub(crate) struct PresentationMetadata {
     /// The presentation's options.
     #[serde(default)]
     pub(crate) options: Option<OptionsConfig>,
+
+    /// The transition used by every slide that doesn't set its own.
+    #[serde(default)]
+    pub(crate) transition: Option<SlideTransitionOverride>,
+
+    /// The custom banner animations, by name.
+    #[serde(default)]
+    pub(crate) animations: BTreeMap<String, AnimationSpec>,
 }
 
 impl PresentationMetadata {
@@ -1,10 +1,12 @@
 use crate::{
     code::execute::SnippetExecutor,
     commands::{
+        keyboard::{InputFocus, ModalKey},
         listener::{Command, CommandListener},
+        remote::{RemoteState, RemoteStatePublisher},
         speaker_notes::{SpeakerNotesEvent, SpeakerNotesEventPublisher},
     },
-    config::{KeyBindingsConfig, SlideTransitionConfig, SlideTransitionStyleConfig},
+    config::{KeyBindingsConfig, SlideTransitionConfig, SlideTransitionOverride, SlideTransitionStyleConfig},
     markdown::parse::MarkdownParser,
     presentation::{
         Presentation, Slide,
@@ -16,7 +18,7 @@ use crate::{
         ErrorSource, RenderError, RenderResult, TerminalDrawer, TerminalDrawerOptions,
         ascii_scaler::AsciiScaler,
         engine::{MaxSize, RenderEngine, RenderEngineOptions},
-        operation::{Pollable, RenderAsyncStartPolicy, RenderOperation},
+        operation::{PlaybackCommand, PlaybackControl, Pollable, RenderAsyncStartPolicy, RenderOperation},
         properties::WindowSize,
         validate::OverflowValidator,
     },
@@ -29,10 +31,20 @@ use crate::{
     theme::{ProcessingThemeError, raw::PresentationTheme},
     third_party::ThirdPartyRender,
     transitions::{
-        AnimateTransition, AnimationFrame, LinesFrame, TransitionDirection,
-        collapse_horizontal::CollapseHorizontalAnimation, fade::FadeAnimation,
+        AnimateTransition, AnimationFrame, ImagePlacement, LinesFrame, TransitionDirection,
+        circle_reveal::CircleRevealAnimation,
+        collapse_horizontal::CollapseHorizontalAnimation,
+        cube::CubeAnimation,
+        dissolve::DissolveAnimation,
+        fade::FadeAnimation,
+        images::PlacementRenderer,
+        push::PushAnimation,
         slide_horizontal::SlideHorizontalAnimation,
+        slide_vertical::SlideVerticalAnimation,
+        timing::{FrameScheduler, ScheduledFrame},
+        wipe::{WipeAnimation, WipeDirection},
     },
+    ui::speaker_notes::{PresenterView, wall_clock},
 };
 use std::{
     fmt::Display,
@@ -40,10 +52,14 @@ use std::{
     mem,
     ops::Deref,
     path::Path,
+    rc::Rc,
     sync::Arc,
-    time::{Duration, Instant},
+    time::Instant,
 };
 
+/// The number of seconds to move a recording by when seeking through it.
+const PLAYBACK_SEEK_SECONDS: f64 = 5.0;
+
 pub struct PresenterOptions {
     pub mode: PresentMode,
     pub builder_options: PresentationBuilderOptions,
@@ -69,6 +85,8 @@ pub struct Presenter<'a> {
     themes: Themes,
     options: PresenterOptions,
     speaker_notes_event_publisher: Option<SpeakerNotesEventPublisher>,
+    presenter_view: Option<PresenterViewState>,
+    remote_state_publisher: Option<RemoteStatePublisher>,
     poller: Poller,
 }
 
@@ -86,6 +104,7 @@ impl<'a> Presenter<'a> {
         image_printer: Arc<ImagePrinter>,
         options: PresenterOptions,
         speaker_notes_event_publisher: Option<SpeakerNotesEventPublisher>,
+        remote_state_publisher: Option<RemoteStatePublisher>,
     ) -> Self {
         Self {
             default_theme,
@@ -99,6 +118,8 @@ impl<'a> Presenter<'a> {
             themes,
             options,
             speaker_notes_event_publisher,
+            presenter_view: None,
+            remote_state_publisher,
             poller: Poller::launch(),
         }
     }
@@ -108,6 +129,9 @@ impl<'a> Presenter<'a> {
         if matches!(self.options.mode, PresentMode::Development) {
             self.resources.watch_presentation_file(path.to_path_buf());
         }
+        if self.options.builder_options.render_speaker_notes_only {
+            self.presenter_view = Some(PresenterViewState::new());
+        }
         self.state = PresenterState::Presenting(Presentation::from(vec![]));
         self.try_reload(path, true)?;
 
@@ -117,14 +141,19 @@ impl<'a> Presenter<'a> {
         };
         let mut drawer = TerminalDrawer::new(self.image_printer.clone(), drawer_options)?;
         loop {
+            self.publish_remote_state();
             // Poll async renders once before we draw just in case.
             self.render(&mut drawer)?;
 
             loop {
-                if self.process_poller_effects()? {
+                if self.process_poller_effects()? || self.tick_presenter_view() {
                     self.render(&mut drawer)?;
                 }
+                if let Some(publisher) = &mut self.speaker_notes_event_publisher {
+                    publisher.poll()?;
+                }
 
+                self.listener.set_input_focus(self.input_focus());
                 let command = match self.listener.try_next_command()? {
                     Some(command) => command,
                     _ => match self.resources.resources_modified() {
@@ -204,13 +233,28 @@ impl<'a> Presenter<'a> {
         Ok(needs_render)
     }
 
-    fn publish_event(&self, event: SpeakerNotesEvent) -> io::Result<()> {
-        if let Some(publisher) = &self.speaker_notes_event_publisher {
+    fn publish_event(&mut self, event: SpeakerNotesEvent) -> io::Result<()> {
+        if let Some(publisher) = &mut self.speaker_notes_event_publisher {
             publisher.send(event)?;
         }
         Ok(())
     }
 
+    fn publish_remote_state(&self) {
+        let Some(publisher) = &self.remote_state_publisher else {
+            return;
+        };
+        let presentation = self.state.presentation();
+        let slide = presentation.current_slide();
+        publisher.publish(&RemoteState {
+            slide: presentation.current_slide_index() + 1,
+            chunk: slide.current_chunk_index(),
+            total_slides: presentation.iter_slides().count(),
+            total_chunks: slide.iter_chunks().count(),
+            notes: slide.speaker_notes().to_vec(),
+        });
+    }
+
     fn check_async_error(&mut self) -> bool {
         let error_holder = self.state.presentation().state.async_error_holder();
         let error_holder = error_holder.lock().unwrap();
@@ -229,17 +273,28 @@ impl<'a> Presenter<'a> {
         }
     }
 
+    /// Check whether the presenter view's clock needs to be rendered again.
+    fn tick_presenter_view(&mut self) -> bool {
+        let Some(view) = &mut self.presenter_view else {
+            return false;
+        };
+        let seconds = view.started.elapsed().as_secs();
+        let changed = seconds != view.last_tick;
+        view.last_tick = seconds;
+        changed && matches!(self.state, PresenterState::Presenting(_))
+    }
+
     fn render(&mut self, drawer: &mut TerminalDrawer) -> RenderResult {
         let result = match &self.state {
             PresenterState::Presenting(presentation) => {
-                drawer.render_operations(presentation.current_slide().iter_visible_operations())
+                Self::render_slide(drawer, presentation, self.presenter_view.as_mut())
             }
             PresenterState::SlideIndex(presentation) => {
-                drawer.render_operations(presentation.current_slide().iter_visible_operations())?;
+                Self::render_slide(drawer, presentation, self.presenter_view.as_mut())?;
                 drawer.render_operations(presentation.iter_slide_index_operations())
             }
             PresenterState::KeyBindings(presentation) => {
-                drawer.render_operations(presentation.current_slide().iter_visible_operations())?;
+                Self::render_slide(drawer, presentation, self.presenter_view.as_mut())?;
                 drawer.render_operations(presentation.iter_bindings_operations())
             }
             PresenterState::Failure { error, source, .. } => drawer.render_error(error, source),
@@ -250,6 +305,20 @@ impl<'a> Presenter<'a> {
         if matches!(result, Err(RenderError::TerminalTooSmall)) { Ok(()) } else { result }
     }
 
+    fn render_slide(
+        drawer: &mut TerminalDrawer,
+        presentation: &Presentation,
+        presenter_view: Option<&mut PresenterViewState>,
+    ) -> RenderResult {
+        match presenter_view {
+            Some(view) => {
+                let view = view.build(presentation);
+                drawer.render_operations([RenderOperation::RenderDynamic(Rc::new(view))].iter())
+            }
+            None => drawer.render_operations(presentation.current_slide().iter_visible_operations()),
+        }
+    }
+
     fn apply_command(&mut self, command: Command) -> CommandSideEffect {
         // These ones always happens no matter our state.
         match command {
@@ -314,6 +383,9 @@ impl<'a> Presenter<'a> {
             Command::LastSlide => presentation.jump_last_slide(),
             Command::GoToSlide(number) => presentation.go_to_slide(number.saturating_sub(1) as usize),
             Command::GoToSlideChunk { slide, chunk } => {
+                if let Some(view) = &mut self.presenter_view {
+                    view.presented = Some((slide.saturating_sub(1) as usize, chunk as usize));
+                }
                 presentation.go_to_slide(slide.saturating_sub(1) as usize);
                 presentation.current_slide_mut().jump_chunk(chunk as usize);
                 true
@@ -346,6 +418,14 @@ impl<'a> Presenter<'a> {
                 presentation.show_all_slide_chunks();
                 true
             }
+            Command::TogglePlayback => return self.control_playback(PlaybackCommand::TogglePause),
+            Command::SeekPlaybackForward => return self.control_playback(PlaybackCommand::Seek(PLAYBACK_SEEK_SECONDS)),
+            Command::SeekPlaybackBackward => {
+                return self.control_playback(PlaybackCommand::Seek(-PLAYBACK_SEEK_SECONDS));
+            }
+            Command::SpeedUpPlayback => return self.control_playback(PlaybackCommand::SpeedUp),
+            Command::SlowDownPlayback => return self.control_playback(PlaybackCommand::SlowDown),
+            Command::ModalInput(key) => self.apply_modal_key(key),
             // These are handled above as they don't require the presentation
             Command::Reload
             | Command::HardReload
@@ -381,7 +461,17 @@ impl<'a> Presenter<'a> {
         }
         self.poller.send(PollerCommand::Reset);
         self.resources.clear_watches();
-        match self.load_presentation(path) {
+        if self.presenter_view.is_some() {
+            let options =
+                PresentationBuilderOptions { render_speaker_notes_only: false, ..self.options.builder_options.clone() };
+            // The notes window keeps working even if the full presentation can't be built, it just
+            // won't be able to show what's coming next.
+            let preview = self.load_presentation(path, options).ok();
+            if let Some(view) = &mut self.presenter_view {
+                view.preview = preview;
+            }
+        }
+        match self.load_presentation(path, self.options.builder_options.clone()) {
             Ok(mut presentation) => {
                 let current = self.state.presentation();
                 if let Some(modification) = PresentationDiffer::find_first_modification(current, &presentation) {
@@ -413,7 +503,7 @@ impl<'a> Presenter<'a> {
     }
 
     fn try_scale_transition_images(&self) -> RenderResult {
-        if self.options.transition.is_none() {
+        if !self.has_transitions() || !matches!(self.transition_image_behavior(), ImageBehavior::PrintAscii) {
             return Ok(());
         }
         let options = RenderEngineOptions { max_size: self.options.max_size.clone(), ..Default::default() };
@@ -449,6 +539,27 @@ impl<'a> Presenter<'a> {
         pollables
     }
 
+    fn control_playback(&mut self, command: PlaybackCommand) -> CommandSideEffect {
+        let presentation = self.state.presentation_mut();
+        let slide = presentation.current_slide_index();
+        // Target the last visible recording, which is the one that was most recently revealed.
+        let operations: Vec<_> = presentation.current_slide_mut().iter_visible_operations_mut().collect();
+        for operation in operations.into_iter().rev() {
+            let RenderOperation::RenderAsync(operation) = operation else {
+                continue;
+            };
+            match operation.control_playback(command) {
+                PlaybackControl::Unsupported => continue,
+                PlaybackControl::Applied => (),
+                PlaybackControl::Restart => {
+                    self.poller.send(PollerCommand::Poll { pollable: operation.pollable(), slide })
+                }
+            };
+            return CommandSideEffect::Redraw;
+        }
+        CommandSideEffect::None
+    }
+
     fn is_displaying_other_error(&self) -> bool {
         matches!(self.state, PresenterState::Failure { mode: FailureMode::Other, .. })
     }
@@ -470,7 +581,11 @@ impl<'a> Presenter<'a> {
         }
     }
 
-    fn load_presentation(&mut self, path: &Path) -> Result<Presentation, LoadPresentationError> {
+    fn load_presentation(
+        &mut self,
+        path: &Path,
+        options: PresentationBuilderOptions,
+    ) -> Result<Presentation, LoadPresentationError> {
         let presentation = PresentationBuilder::new(
             self.default_theme,
             self.resources.clone(),
@@ -480,16 +595,60 @@ impl<'a> Presenter<'a> {
             ImageRegistry::new(self.image_printer.clone()),
             self.options.bindings.clone(),
             &self.parser,
-            self.options.builder_options.clone(),
+            options,
         )?
         .build(path)?;
         Ok(presentation)
     }
 
+    fn apply_modal_key(&mut self, key: ModalKey) -> bool {
+        // The slide index is the only modal that takes input.
+        let PresenterState::SlideIndex(presentation) = &mut self.state else {
+            return false;
+        };
+        let index = presentation.slide_index_state();
+        match key {
+            ModalKey::Up => index.select_previous(),
+            ModalKey::Down => index.select_next(),
+            ModalKey::Enter => {
+                let Some(slide) = index.selected_slide() else {
+                    return false;
+                };
+                let mut presentation = mem::take(&mut self.state).into_presentation();
+                // Speaker notes instances forward this so the main presentation moves along with them.
+                self.state = match self.listener.forward(Command::GoToSlide(slide as u32 + 1)) {
+                    Ok(Some(_)) => {
+                        presentation.go_to_slide(slide);
+                        PresenterState::Presenting(presentation)
+                    }
+                    Ok(None) => PresenterState::Presenting(presentation),
+                    Err(e) => PresenterState::failure(e, presentation, ErrorSource::Presentation, FailureMode::Other),
+                };
+            }
+            ModalKey::Char(c) if index.is_searching() => index.push_search_char(c),
+            ModalKey::Char('/') => index.start_search(),
+            ModalKey::Backspace => index.pop_search_char(),
+            ModalKey::Escape => index.cancel_search(),
+            ModalKey::Char(_) => return false,
+        };
+        true
+    }
+
+    fn input_focus(&self) -> InputFocus {
+        match &self.state {
+            PresenterState::SlideIndex(presentation) if presentation.slide_index_state().is_searching() => {
+                InputFocus::Text
+            }
+            PresenterState::SlideIndex(_) => InputFocus::Modal,
+            _ => InputFocus::Presentation,
+        }
+    }
+
     fn toggle_slide_index(&mut self) {
         let state = mem::take(&mut self.state);
         match state {
             PresenterState::Presenting(presentation) | PresenterState::KeyBindings(presentation) => {
+                presentation.slide_index_state().open(presentation.current_slide_index());
                 self.state = PresenterState::SlideIndex(presentation)
             }
             PresenterState::SlideIndex(presentation) => self.state = PresenterState::Presenting(presentation),
@@ -517,18 +676,37 @@ impl<'a> Presenter<'a> {
         }
     }
 
+    fn has_transitions(&self) -> bool {
+        self.options.transition.is_some()
+            || self
+                .state
+                .presentation()
+                .iter_slides()
+                .any(|slide| matches!(slide.transition(), Some(SlideTransitionOverride::Enabled { .. })))
+    }
+
+    /// Get the transition to use when moving between the given slide and the one before it.
+    fn slide_transition(&self, slide_index: usize) -> Option<SlideTransitionConfig> {
+        let slide = self.state.presentation().iter_slides().nth(slide_index)?;
+        match slide.transition() {
+            Some(transition) => transition.apply(self.options.transition.as_ref()),
+            None => self.options.transition.clone(),
+        }
+    }
+
     fn animate_next_slide(&mut self, drawer: &mut TerminalDrawer) -> RenderResult {
-        let Some(config) = self.options.transition.clone() else {
+        let Some(config) = self.slide_transition(self.state.presentation().current_slide_index()) else {
             return Ok(());
         };
 
         let options = drawer.render_engine_options();
+        let image_behavior = self.transition_image_behavior();
         let presentation = self.state.presentation_mut();
         let dimensions = WindowSize::current(self.options.font_size_fallback)?;
         presentation.jump_previous();
-        let left = Self::virtual_render(presentation.current_slide(), dimensions, &options)?;
+        let left = Self::virtual_render(presentation.current_slide(), dimensions, &options, image_behavior.clone())?;
         presentation.jump_next();
-        let right = Self::virtual_render(presentation.current_slide(), dimensions, &options)?;
+        let right = Self::virtual_render(presentation.current_slide(), dimensions, &options, image_behavior.clone())?;
         let direction = TransitionDirection::Next;
         let result = self.animate_transition(drawer, left, right, direction, dimensions, config);
         // After transitioning into the new slide, start any on-demand async renders
@@ -544,11 +722,13 @@ impl<'a> Presenter<'a> {
     }
 
     fn animate_previous_slide(&mut self, drawer: &mut TerminalDrawer) -> RenderResult {
-        let Some(config) = self.options.transition.clone() else {
+        // Going back plays the transition of the slide being left, in reverse.
+        let Some(config) = self.slide_transition(self.state.presentation().current_slide_index() + 1) else {
             return Ok(());
         };
 
         let options = drawer.render_engine_options();
+        let image_behavior = self.transition_image_behavior();
         let presentation = self.state.presentation_mut();
         let dimensions = WindowSize::current(self.options.font_size_fallback)?;
         presentation.jump_next();
@@ -556,9 +736,9 @@ impl<'a> Presenter<'a> {
         // Re-borrow to avoid calling fns above while mutably borrowing
         let presentation = self.state.presentation_mut();
 
-        let right = Self::virtual_render(presentation.current_slide(), dimensions, &options)?;
+        let right = Self::virtual_render(presentation.current_slide(), dimensions, &options, image_behavior.clone())?;
         presentation.jump_previous();
-        let left = Self::virtual_render(presentation.current_slide(), dimensions, &options)?;
+        let left = Self::virtual_render(presentation.current_slide(), dimensions, &options, image_behavior.clone())?;
         let direction = TransitionDirection::Previous;
         let result = self.animate_transition(drawer, left, right, direction, dimensions, config);
         // After transitioning into the new slide, start any on-demand async renders
@@ -599,6 +779,37 @@ impl<'a> Presenter<'a> {
             SlideTransitionStyleConfig::CollapseHorizontal => {
                 self.run_animation(drawer, first, CollapseHorizontalAnimation::new(left, right, direction), config)
             }
+            SlideTransitionStyleConfig::SlideVertical => {
+                self.run_animation(drawer, first, SlideVerticalAnimation::new(left, right, direction), config)
+            }
+            SlideTransitionStyleConfig::WipeLeft => {
+                let animation = WipeAnimation::new(left, right, WipeDirection::Left, direction);
+                self.run_animation(drawer, first, animation, config)
+            }
+            SlideTransitionStyleConfig::WipeRight => {
+                let animation = WipeAnimation::new(left, right, WipeDirection::Right, direction);
+                self.run_animation(drawer, first, animation, config)
+            }
+            SlideTransitionStyleConfig::WipeUp => {
+                let animation = WipeAnimation::new(left, right, WipeDirection::Up, direction);
+                self.run_animation(drawer, first, animation, config)
+            }
+            SlideTransitionStyleConfig::WipeDown => {
+                let animation = WipeAnimation::new(left, right, WipeDirection::Down, direction);
+                self.run_animation(drawer, first, animation, config)
+            }
+            SlideTransitionStyleConfig::Dissolve => {
+                self.run_animation(drawer, first, DissolveAnimation::new(left, right, direction), config)
+            }
+            SlideTransitionStyleConfig::CircleReveal => {
+                self.run_animation(drawer, first, CircleRevealAnimation::new(left, right, direction), config)
+            }
+            SlideTransitionStyleConfig::Cube => {
+                self.run_animation(drawer, first, CubeAnimation::new(left, right, direction), config)
+            }
+            SlideTransitionStyleConfig::Push => {
+                self.run_animation(drawer, first, PushAnimation::new(left, right, direction), config)
+            }
         }
     }
 
@@ -612,47 +823,70 @@ impl<'a> Presenter<'a> {
     where
         T: AnimateTransition,
     {
-        let total_time = Duration::from_millis(config.duration_millis as u64);
-        let frames: usize = config.frames;
-        let total_frames = animation.total_frames();
-        let step = total_time / (frames as u32 * 2);
-        let mut last_frame_index = 0;
-        let mut frame_index = 1;
-        // Render the first frame as text to have images as ascii
-        Self::render_frame(&LinesFrame::from(&first).build_commands(), drawer)?;
-        while frame_index < total_frames {
-            let start = Instant::now();
-            let frame = animation.build_frame(frame_index, last_frame_index);
-            let commands = frame.build_commands();
-            Self::render_frame(&commands, drawer)?;
-
-            let elapsed = start.elapsed();
-            let sleep_needed = step.saturating_sub(elapsed);
-            if sleep_needed.as_millis() > 0 {
-                std::thread::sleep(step);
-            }
-            last_frame_index = frame_index;
-            frame_index += total_frames.div_ceil(frames);
+        let mut scheduler = FrameScheduler::new(&config, animation.total_frames());
+        let image_printer = self.image_printer.clone();
+        let mut images = match image_printer.as_ref() {
+            ImagePrinter::Kitty(printer) if printer.supports_placements() => Some(PlacementRenderer::new(printer)),
+            _ => None,
+        };
+        let first = LinesFrame::from(&first);
+        Self::render_frame(&first.build_commands(), &animation.image_placements(0), &mut images, drawer)?;
+        let start = Instant::now();
+        loop {
+            match scheduler.poll(start.elapsed()) {
+                ScheduledFrame::Render { frame: index, previous } => {
+                    let frame = animation.build_frame(index, previous);
+                    let placements = animation.image_placements(index);
+                    Self::render_frame(&frame.build_commands(), &placements, &mut images, drawer)?;
+                }
+                ScheduledFrame::Unchanged => (),
+                ScheduledFrame::Done => break,
+            };
+            std::thread::sleep(scheduler.time_until_next_tick(start.elapsed()));
+        }
+        if let Some(images) = images {
+            // This isn't flushed so it goes out along with the slide being drawn right after this.
+            images.finish(&mut drawer.terminal)?;
         }
         Ok(())
     }
 
-    fn render_frame(commands: &[TerminalCommand<'_>], drawer: &mut TerminalDrawer) -> RenderResult {
+    fn render_frame(
+        commands: &[TerminalCommand<'_>],
+        placements: &[ImagePlacement],
+        images: &mut Option<PlacementRenderer>,
+        drawer: &mut TerminalDrawer,
+    ) -> RenderResult {
         drawer.terminal.execute(&TerminalCommand::BeginUpdate)?;
         for command in commands {
             drawer.terminal.execute(command)?;
         }
+        if let Some(images) = images {
+            images.render(placements, &mut drawer.terminal)?;
+        }
         drawer.terminal.execute(&TerminalCommand::EndUpdate)?;
         drawer.terminal.execute(&TerminalCommand::Flush)?;
         Ok(())
     }
 
+    /// How to handle images when rendering slides for a transition.
+    ///
+    /// Images are only turned into ASCII if that's how they're displayed anyway. Otherwise they're
+    /// kept as they are so they can either be moved around or hidden during the transition.
+    fn transition_image_behavior(&self) -> ImageBehavior {
+        match self.image_printer.as_ref() {
+            ImagePrinter::Ascii(_) => ImageBehavior::PrintAscii,
+            _ => ImageBehavior::Store,
+        }
+    }
+
     fn virtual_render(
         slide: &Slide,
         dimensions: WindowSize,
         options: &RenderEngineOptions,
+        image_behavior: ImageBehavior,
     ) -> Result<TerminalGrid, RenderError> {
-        let mut term = VirtualTerminal::new(dimensions, ImageBehavior::PrintAscii);
+        let mut term = VirtualTerminal::new(dimensions, image_behavior);
         let engine = RenderEngine::new(&mut term, dimensions, options.clone());
         engine.render(slide.iter_visible_operations())?;
         Ok(term.into_contents())
@@ -672,6 +906,49 @@ impl<'a> Presenter<'a> {
     }
 }
 
+/// The state behind the presenter view shown in the speaker notes window.
+struct PresenterViewState {
+    /// The full presentation, used to preview what comes next.
+    preview: Option<Presentation>,
+
+    /// The slide and chunk the presentation is at, as last reported by it.
+    presented: Option<(usize, usize)>,
+
+    started: Instant,
+    last_tick: u64,
+}
+
+impl PresenterViewState {
+    fn new() -> Self {
+        Self { preview: None, presented: None, started: Instant::now(), last_tick: 0 }
+    }
+
+    fn build(&mut self, notes: &Presentation) -> PresenterView {
+        let slide_index = notes.current_slide_index();
+        let presented = self.presented;
+        let next = self.preview.as_mut().and_then(|preview| {
+            // Chunks in the notes only account for pauses, so prefer the one the presentation is
+            // actually at whenever it's on this same slide.
+            let chunk = match presented {
+                Some((slide, chunk)) if slide == slide_index => chunk,
+                _ => notes.current_chunk(),
+            };
+            preview.go_to_slide(slide_index);
+            preview.jump_chunk(chunk);
+            preview.jump_next().then(|| preview.current_slide().iter_visible_operations().cloned().collect())
+        });
+        PresenterView {
+            notes: notes.current_slide().iter_visible_operations().cloned().collect(),
+            next,
+            slide: slide_index + 1,
+            total_slides: notes.iter_slides().count(),
+            elapsed: self.started.elapsed(),
+            clock: wall_clock(),
+            style: notes.speaker_notes_style().clone(),
+        }
+    }
+}
+
 enum CommandSideEffect {
     Exit,
     Suspend,
@@ -182,6 +182,73 @@ pub(crate) trait RenderAsync: AsRenderOperations {
     fn start_policy(&self) -> RenderAsyncStartPolicy {
         RenderAsyncStartPolicy::OnDemand
     }
+
+    /// Apply a playback command to this render.
+    ///
+    /// Only renders that play back some media, like terminal recordings, are expected to handle
+    /// these.
+    fn control_playback(&self, _command: PlaybackCommand) -> PlaybackControl {
+        PlaybackControl::Unsupported
+    }
+
+    /// Get a timeline that allows rendering this at any point in time.
+    ///
+    /// This is used when exporting, where there's no playback to follow.
+    fn timeline(&self) -> Option<Rc<dyn RenderTimeline>> {
+        None
+    }
+}
+
+/// Something whose contents change over time and that can be rendered at any point within it.
+pub(crate) trait RenderTimeline: AsRenderOperations {
+    /// The points in time, in seconds, at which the contents need to be rendered to capture how
+    /// they change.
+    fn frame_times(&self) -> Vec<f64>;
+
+    /// Move to the given point in time, in seconds.
+    fn seek_to(&self, time: f64);
+
+    /// What this timeline plays back.
+    fn kind(&self) -> TimelineKind;
+}
+
+/// What a [RenderTimeline] plays back.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum TimelineKind {
+    /// A terminal recording.
+    Recording,
+
+    /// An animation, along with the number of seconds after which it starts over, if it loops.
+    Animation { cycle: Option<f64> },
+}
+
+/// A command that controls the playback of an async render.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum PlaybackCommand {
+    /// Pause the playback if it's running, resume it otherwise.
+    TogglePause,
+
+    /// Move the playback by the given number of seconds.
+    Seek(f64),
+
+    /// Play back faster.
+    SpeedUp,
+
+    /// Play back slower.
+    SlowDown,
+}
+
+/// The result of applying a [PlaybackCommand].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PlaybackControl {
+    /// This render doesn't support playback commands.
+    Unsupported,
+
+    /// The command was applied.
+    Applied,
+
+    /// The command was applied and the render needs to be polled again.
+    Restart,
 }
 
 /// The start policy for an async render.
@@ -194,6 +261,7 @@ pub(crate) enum RenderAsyncStartPolicy {
     OnDemand,
 
     /// Start manually (only when explicitly triggered by keypress).
+    #[allow(dead_code)]
     Manual,
 }
 
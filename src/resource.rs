@@ -5,10 +5,13 @@ use crate::{
     },
     theme::{raw::PresentationTheme, registry::LoadThemeError},
 };
+use flate2::read::GzDecoder;
 use std::{
     cell::RefCell,
     collections::HashMap,
-    fs, io, mem,
+    fs::{self, File},
+    io::{self, Read},
+    mem,
     path::{Path, PathBuf},
     rc::Rc,
     sync::{
@@ -111,13 +114,56 @@ impl Resources {
         path: P,
         base_path: &ResourceBasePath,
     ) -> io::Result<String> {
+        self.load_external_text_file(path, base_path, |path| fs::read_to_string(path))
+    }
+
+    /// Get the contents of a gzip compressed text file.
+    pub(crate) fn external_gzip_text_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        base_path: &ResourceBasePath,
+    ) -> io::Result<String> {
+        self.load_external_text_file(path, base_path, |path| {
+            let mut contents = String::new();
+            GzDecoder::new(File::open(path)?).read_to_string(&mut contents)?;
+            Ok(contents)
+        })
+    }
+
+    /// Get the contents of the font file at the given path.
+    ///
+    /// Fonts that can't be found relative to the given base path are looked up in the fonts
+    /// directory.
+    pub(crate) fn font_file<P: AsRef<Path>>(&self, path: P, base_path: &ResourceBasePath) -> io::Result<String> {
+        match self.external_text_file(&path, base_path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            result => return result,
+        };
+        self.external_text_file(path, &ResourceBasePath::Custom(self.fonts_path()))
+    }
+
+    /// The directory custom fonts are loaded from.
+    pub(crate) fn fonts_path(&self) -> PathBuf {
+        self.inner.borrow().themes_path.join("fonts")
+    }
+
+    /// The directory custom banner animations are loaded from.
+    pub(crate) fn animations_path(&self) -> PathBuf {
+        self.inner.borrow().themes_path.join("animations")
+    }
+
+    fn load_external_text_file<P, F>(&self, path: P, base_path: &ResourceBasePath, load: F) -> io::Result<String>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&Path) -> io::Result<String>,
+    {
         let path = self.resolve_path(path, base_path);
         let mut inner = self.inner.borrow_mut();
         if let Some(contents) = inner.external_text_files.get(&path) {
             return Ok(contents.clone());
         }
 
-        let contents = fs::read_to_string(&path)?;
+        let contents = load(&path)?;
         inner.watcher.send(WatchEvent::WatchFile { path: path.clone(), watch_forever: false });
         inner.external_text_files.insert(path, contents.clone());
         Ok(contents)
@@ -109,10 +109,7 @@ impl Handler {
 
     fn update_style(&self, mut codes: ParamsIter) -> TextStyle {
         let mut style = self.style;
-        loop {
-            let Some(&[next]) = codes.next() else {
-                break;
-            };
+        while let Some(&[next]) = codes.next() {
             match next {
                 0 => style = Default::default(),
                 1 => style = style.bold(),
@@ -55,6 +55,11 @@ impl Image {
     pub(crate) fn image(&self) -> &TerminalImage {
         &self.inner.image
     }
+
+    /// Whether both images share the same underlying resource.
+    pub(crate) fn is_same_resource(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
 }
 
 impl PartialEq for Image {
@@ -373,6 +373,83 @@ impl KittyPrinter {
         Ok(())
     }
 
+    /// Whether images can be transmitted once and then placed and moved around by id.
+    ///
+    /// This isn't the case under tmux as images there are drawn through unicode placeholders.
+    pub(crate) fn supports_placements(&self) -> bool {
+        !self.tmux
+    }
+
+    /// Transmit an image without displaying it so it can later be placed via [Self::place].
+    ///
+    /// Only the first frame of animated images is transmitted.
+    pub(crate) fn transmit<T>(&self, image: &KittyImage, image_id: u32, terminal: &mut T) -> Result<(), PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        let buffer = match &image.resource {
+            GenericResource::Image(buffer) => buffer,
+            GenericResource::Gif(frames) => &frames[0].buffer,
+        };
+        let options = vec![
+            ControlOption::Format(ImageFormat::Rgba),
+            ControlOption::Action(Action::Transmit),
+            ControlOption::ImageId(image_id),
+            ControlOption::Width(image.dimensions.0),
+            ControlOption::Height(image.dimensions.1),
+            ControlOption::Quiet(2),
+        ];
+        match buffer {
+            KittyBuffer::Filesystem(path) => self.print_local(options, path, terminal),
+            KittyBuffer::Memory(buffer) => self.print_remote(options, buffer, terminal, false),
+        }
+    }
+
+    /// Place a previously transmitted image at the cursor position.
+    ///
+    /// Placing an image again using the same placement id moves it rather than creating a copy.
+    pub(crate) fn place<T>(
+        &self,
+        image_id: u32,
+        placement_id: u32,
+        placement: &KittyPlacement,
+        terminal: &mut T,
+    ) -> Result<(), PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        let options = &[
+            ControlOption::Action(Action::Place),
+            ControlOption::ImageId(image_id),
+            ControlOption::PlacementId(placement_id),
+            ControlOption::SourceX(placement.source.0),
+            ControlOption::SourceY(placement.source.1),
+            ControlOption::SourceWidth(placement.source.2),
+            ControlOption::SourceHeight(placement.source.3),
+            ControlOption::Columns(placement.columns),
+            ControlOption::Rows(placement.rows),
+            ControlOption::ZIndex(placement.z_index),
+            ControlOption::CursorMovement(1),
+            ControlOption::Quiet(2),
+        ];
+        let command = self.make_command(options, "").to_string();
+        terminal.execute(&TerminalCommand::PrintText { content: &command, style: Default::default() })?;
+        Ok(())
+    }
+
+    /// Delete every placement of an image, also freeing its data if `free` is set.
+    pub(crate) fn delete<T>(&self, image_id: u32, free: bool, terminal: &mut T) -> Result<(), PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        let target = if free { DeleteTarget::ImageData } else { DeleteTarget::ImagePlacements };
+        let options =
+            &[ControlOption::Action(Action::Delete), ControlOption::Delete(target), ControlOption::ImageId(image_id)];
+        let command = self.make_command(options, "").to_string();
+        terminal.execute(&TerminalCommand::PrintText { content: &command, style: Default::default() })?;
+        Ok(())
+    }
+
     fn load_raw_resource(path: &Path) -> Result<RawResource, RegisterImageError> {
         let file = File::open(path)?;
         if path.extension().unwrap_or_default() == "gif" {
@@ -419,6 +496,16 @@ impl PrintImage for KittyPrinter {
     }
 }
 
+/// Where and how much of an image to draw when placing it.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct KittyPlacement {
+    /// The rectangle within the image to draw, in pixels: `(x, y, width, height)`.
+    pub(crate) source: (u32, u32, u32, u32),
+    pub(crate) columns: u16,
+    pub(crate) rows: u16,
+    pub(crate) z_index: i32,
+}
+
 #[derive(Clone, Debug)]
 pub enum KittyMode {
     Local,
@@ -471,6 +558,13 @@ pub(crate) enum ControlOption {
     Quiet(u32),
     ZIndex(i32),
     UnicodePlaceholder,
+    PlacementId(u32),
+    SourceX(u32),
+    SourceY(u32),
+    SourceWidth(u32),
+    SourceHeight(u32),
+    CursorMovement(u32),
+    Delete(DeleteTarget),
 }
 
 impl fmt::Display for ControlOption {
@@ -494,6 +588,13 @@ impl fmt::Display for ControlOption {
             Quiet(option) => write!(f, "q={option}"),
             ZIndex(index) => write!(f, "z={index}"),
             UnicodePlaceholder => write!(f, "U=1"),
+            PlacementId(id) => write!(f, "p={id}"),
+            SourceX(x) => write!(f, "x={x}"),
+            SourceY(y) => write!(f, "y={y}"),
+            SourceWidth(width) => write!(f, "w={width}"),
+            SourceHeight(height) => write!(f, "h={height}"),
+            CursorMovement(policy) => write!(f, "C={policy}"),
+            Delete(target) => write!(f, "d={target}"),
         }
     }
 }
@@ -533,8 +634,11 @@ impl fmt::Display for TransmissionMedium {
 #[derive(Debug, Clone)]
 pub(crate) enum Action {
     Animate,
+    Transmit,
     TransmitAndDisplay,
     TransmitFrame,
+    Place,
+    Delete,
     Query,
 }
 
@@ -543,10 +647,30 @@ impl fmt::Display for Action {
         use Action::*;
         let value = match self {
             Animate => 'a',
+            Transmit => 't',
             TransmitAndDisplay => 'T',
             TransmitFrame => 'f',
+            Place => 'p',
+            Delete => 'd',
             Query => 'q',
         };
         write!(f, "{value}")
     }
 }
+
+#[derive(Debug, Clone)]
+pub(crate) enum DeleteTarget {
+    ImagePlacements,
+    ImageData,
+}
+
+impl fmt::Display for DeleteTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DeleteTarget::*;
+        let value = match self {
+            ImagePlacements => 'i',
+            ImageData => 'I',
+        };
+        write!(f, "{value}")
+    }
+}
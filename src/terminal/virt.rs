@@ -20,6 +20,8 @@ use std::{collections::HashMap, io};
 pub(crate) struct PrintedImage {
     pub(crate) image: Image,
     pub(crate) width_columns: u16,
+    pub(crate) height_rows: u16,
+    pub(crate) z_index: i32,
 }
 
 pub(crate) struct TerminalRowIterator<'a> {
@@ -184,7 +186,12 @@ impl VirtualTerminal {
         match &self.image_behavior {
             ImageBehavior::Store => {
                 let key = (self.row, self.column);
-                let image = PrintedImage { image: image.clone(), width_columns: options.columns };
+                let image = PrintedImage {
+                    image: image.clone(),
+                    width_columns: options.columns,
+                    height_rows: options.rows,
+                    z_index: options.z_index,
+                };
                 self.images.insert(key, image);
             }
             ImageBehavior::PrintAscii => {
@@ -18,6 +18,7 @@ const DEFAULT_TYPST_VERTICAL_MARGIN: u16 = 7;
 const DEFAULT_MERMAID_THEME: &str = "default";
 const DEFAULT_MERMAID_BACKGROUND: &str = "transparent";
 const DEFAULT_D2_THEME: u32 = 0;
+const DEFAULT_SPEAKER_NOTES_PREVIEW_SIZE: u8 = 50;
 
 #[derive(Clone, Debug, Default)]
 pub(crate) struct ThemeOptions {
@@ -35,6 +36,9 @@ pub(crate) struct PresentationTheme {
     pub(crate) slide_title: SlideTitleStyle,
     pub(crate) code: CodeBlockStyle,
     pub(crate) execution_output: ExecutionOutputBlockStyle,
+    pub(crate) asciinema: AsciinemaStyle,
+    pub(crate) banner: BannerStyle,
+    pub(crate) speaker_notes: SpeakerNotesStyle,
     pub(crate) inline_code: ModifierStyle,
     pub(crate) bold: ModifierStyle,
     pub(crate) italics: ModifierStyle,
@@ -63,6 +67,9 @@ impl PresentationTheme {
             slide_title,
             code,
             execution_output,
+            asciinema,
+            banner,
+            speaker_notes,
             inline_code,
             bold,
             italics,
@@ -88,6 +95,9 @@ impl PresentationTheme {
             slide_title: SlideTitleStyle::new(slide_title, &palette, options)?,
             code: CodeBlockStyle::new(code),
             execution_output: ExecutionOutputBlockStyle::new(execution_output, &palette)?,
+            asciinema: AsciinemaStyle::new(asciinema, &palette)?,
+            banner: BannerStyle::new(banner, &palette)?,
+            speaker_notes: SpeakerNotesStyle::new(speaker_notes, &default_style, &palette)?,
             inline_code: ModifierStyle::new(inline_code, &palette)?,
             bold: ModifierStyle::new(bold, &palette)?,
             italics: ModifierStyle::new(italics, &palette)?,
@@ -140,6 +150,12 @@ pub(crate) enum ProcessingThemeError {
 
     #[error("invalid footer image: {0}")]
     FooterImage(RegisterImageError),
+
+    #[error("banner gradient colors must be RGB colors")]
+    BannerGradientColor,
+
+    #[error("banner can't use both a gradient and the palette gradient")]
+    BannerGradientConflict,
 }
 
 #[derive(Clone, Debug)]
@@ -585,6 +601,153 @@ impl CodeBlockStyle {
     }
 }
 
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AsciinemaStyle {
+    pub(crate) alignment: Alignment,
+    pub(crate) border: Option<BorderCharacters>,
+    pub(crate) border_style: TextStyle,
+    pub(crate) style: TextStyle,
+    pub(crate) title_bar_style: Option<TextStyle>,
+    pub(crate) padding: PaddingRect,
+}
+
+impl AsciinemaStyle {
+    fn new(raw: &raw::AsciinemaStyle, palette: &ColorPalette) -> Result<Self, ProcessingThemeError> {
+        let raw::AsciinemaStyle { alignment, border, colors, title_bar, padding } = raw;
+        let padding = PaddingRect {
+            horizontal: padding.horizontal.unwrap_or_default(),
+            vertical: padding.vertical.unwrap_or_default(),
+        };
+        let title_bar_style = match title_bar.enabled.unwrap_or(false) {
+            true => Some(TextStyle::colored(title_bar.colors.resolve(palette)?)),
+            false => None,
+        };
+        Ok(Self {
+            alignment: alignment.clone().unwrap_or_default().into(),
+            border: BorderCharacters::new(border.style.unwrap_or_default()),
+            border_style: TextStyle::colored(border.colors.resolve(palette)?),
+            style: TextStyle::colored(colors.resolve(palette)?),
+            title_bar_style,
+            padding,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BannerStyle {
+    pub(crate) color: Option<Color>,
+    pub(crate) gradient: Vec<(u8, u8, u8)>,
+}
+
+impl BannerStyle {
+    fn new(raw: &raw::BannerStyle, palette: &ColorPalette) -> Result<Self, ProcessingThemeError> {
+        let raw::BannerStyle { color, gradient, palette_gradient } = raw;
+        let color = color.as_ref().map(|color| color.resolve(palette)).transpose()?.flatten();
+        let gradient = match palette_gradient.unwrap_or(false) {
+            true if !gradient.is_empty() => return Err(ProcessingThemeError::BannerGradientConflict),
+            // Palette colors that aren't RGB can't be blended so they're left out.
+            true => palette.colors.values().filter_map(Color::as_rgb).collect(),
+            false => {
+                let mut stops = Vec::new();
+                for color in gradient {
+                    let color = color.resolve(palette)?.and_then(|color| color.as_rgb());
+                    stops.push(color.ok_or(ProcessingThemeError::BannerGradientColor)?);
+                }
+                stops
+            }
+        };
+        Ok(Self { color, gradient })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct SpeakerNotesStyle {
+    pub(crate) layout: raw::SpeakerNotesLayout,
+    pub(crate) style: TextStyle,
+    pub(crate) preview_size: u8,
+    pub(crate) preview_border: Option<BorderCharacters>,
+    pub(crate) preview_style: TextStyle,
+    pub(crate) status_bar_style: TextStyle,
+}
+
+impl SpeakerNotesStyle {
+    fn new(
+        raw: &raw::SpeakerNotesStyle,
+        default_style: &DefaultStyle,
+        palette: &ColorPalette,
+    ) -> Result<Self, ProcessingThemeError> {
+        let raw::SpeakerNotesStyle { layout, preview, status_bar } = raw;
+        let style = default_style.style;
+        let mut preview_style = style;
+        preview_style.merge(&TextStyle::colored(preview.colors.resolve(palette)?));
+
+        // Unless told otherwise, the status bar uses the default colors the other way around so it stands out.
+        let status_bar_colors = status_bar.colors.resolve(palette)?;
+        let status_bar_style = match status_bar_colors {
+            Colors { foreground: None, background: None } => {
+                let Colors { foreground, background } = style.colors;
+                TextStyle::colored(Colors { foreground: background, background: foreground })
+            }
+            colors => {
+                let mut status_bar_style = style;
+                status_bar_style.merge(&TextStyle::colored(colors));
+                status_bar_style
+            }
+        };
+        Ok(Self {
+            layout: layout.unwrap_or_default(),
+            style,
+            preview_size: preview.size.unwrap_or(DEFAULT_SPEAKER_NOTES_PREVIEW_SIZE).clamp(10, 90),
+            preview_border: BorderCharacters::new(preview.border.unwrap_or(raw::BorderStyle::Rounded)),
+            preview_style,
+            status_bar_style,
+        })
+    }
+}
+
+impl Default for SpeakerNotesStyle {
+    fn default() -> Self {
+        let raw = raw::SpeakerNotesStyle::default();
+        Self::new(&raw, &DefaultStyle::default(), &ColorPalette::default()).expect("default style is valid")
+    }
+}
+
+/// The characters used to draw a border.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BorderCharacters {
+    pub(crate) horizontal: char,
+    pub(crate) vertical: char,
+    pub(crate) top_left: char,
+    pub(crate) top_right: char,
+    pub(crate) bottom_left: char,
+    pub(crate) bottom_right: char,
+    pub(crate) left_junction: char,
+    pub(crate) right_junction: char,
+}
+
+impl BorderCharacters {
+    fn new(style: raw::BorderStyle) -> Option<Self> {
+        let (horizontal, vertical, corners, junctions) = match style {
+            raw::BorderStyle::None => return None,
+            raw::BorderStyle::Single => ('─', '│', ['┌', '┐', '└', '┘'], ['├', '┤']),
+            raw::BorderStyle::Rounded => ('─', '│', ['╭', '╮', '╰', '╯'], ['├', '┤']),
+            raw::BorderStyle::Double => ('═', '║', ['╔', '╗', '╚', '╝'], ['╠', '╣']),
+        };
+        let [top_left, top_right, bottom_left, bottom_right] = corners;
+        let [left_junction, right_junction] = junctions;
+        Some(Self {
+            horizontal,
+            vertical,
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            left_junction,
+            right_junction,
+        })
+    }
+}
+
 /// Vertical/horizontal padding.
 #[derive(Clone, Copy, Debug, Default)]
 pub(crate) struct PaddingRect {
@@ -790,3 +953,29 @@ impl TryFrom<&raw::ColorPalette> for ColorPalette {
         Ok(Self { colors, classes })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::none("{}", &[])]
+    #[case::explicit("gradient: [ff0000, 'palette:blue']", &[(255, 0, 0), (0, 0, 255)])]
+    #[case::palette("palette_gradient: true", &[(0, 0, 255), (0, 255, 0)])]
+    fn banner_gradient(#[case] input: &str, #[case] expected: &[(u8, u8, u8)]) {
+        let raw_palette = serde_yaml::from_str("colors: { green: 00ff00, blue: 0000ff, red: red }").unwrap();
+        let palette = ColorPalette::try_from(&raw_palette).unwrap();
+        let raw: raw::BannerStyle = serde_yaml::from_str(input).unwrap();
+        let style = BannerStyle::new(&raw, &palette).expect("invalid style");
+        assert_eq!(style.gradient, expected);
+    }
+
+    #[rstest]
+    #[case::not_rgb("gradient: [red]")]
+    #[case::both("{ gradient: [ff0000], palette_gradient: true }")]
+    fn invalid_banner_gradient(#[case] input: &str) {
+        let raw: raw::BannerStyle = serde_yaml::from_str(input).unwrap();
+        assert!(BannerStyle::new(&raw, &ColorPalette::default()).is_err());
+    }
+}
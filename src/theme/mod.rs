@@ -3,4 +3,4 @@ pub(crate) mod raw;
 pub(crate) mod registry;
 
 pub(crate) use clean::*;
-pub(crate) use raw::{AuthorPositioning, FooterTemplate, FooterTemplateChunk, Margin};
+pub(crate) use raw::{AuthorPositioning, FooterTemplate, FooterTemplateChunk, Margin, SpeakerNotesLayout};
@@ -31,6 +31,18 @@ pub struct PresentationTheme {
     #[serde(default)]
     pub(crate) execution_output: ExecutionOutputBlockStyle,
 
+    /// The style for asciinema recordings.
+    #[serde(default)]
+    pub(crate) asciinema: AsciinemaStyle,
+
+    /// The style for banners.
+    #[serde(default)]
+    pub(crate) banner: BannerStyle,
+
+    /// The style for the presenter view in the speaker notes window.
+    #[serde(default)]
+    pub(crate) speaker_notes: SpeakerNotesStyle,
+
     /// The style for inline code.
     #[serde(default)]
     pub(crate) inline_code: ModifierStyle,
@@ -668,6 +680,146 @@ pub(crate) struct CodeBlockStyle {
     pub(crate) background: Option<bool>,
 }
 
+/// The style for an asciinema recording.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct AsciinemaStyle {
+    /// The alignment.
+    #[serde(flatten)]
+    pub(crate) alignment: Option<Alignment>,
+
+    /// The border around the recording.
+    #[serde(default)]
+    pub(crate) border: AsciinemaBorderStyle,
+
+    /// The colors for the recording itself, used wherever it doesn't set its own.
+    #[serde(default)]
+    pub(crate) colors: RawColors,
+
+    /// The bar that shows the recording's title.
+    #[serde(default)]
+    pub(crate) title_bar: AsciinemaTitleBarStyle,
+
+    /// The padding between the border and the recording.
+    #[serde(default)]
+    pub(crate) padding: PaddingRect,
+}
+
+/// The style for the border around an asciinema recording.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AsciinemaBorderStyle {
+    /// The kind of border to use.
+    #[serde(default)]
+    pub(crate) style: Option<BorderStyle>,
+
+    /// The colors for the border.
+    #[serde(default)]
+    pub(crate) colors: RawColors,
+}
+
+/// The kind of border to draw around an element.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BorderStyle {
+    /// No border.
+    None,
+
+    /// A single line border with square corners.
+    #[default]
+    Single,
+
+    /// A single line border with rounded corners.
+    Rounded,
+
+    /// A double line border.
+    Double,
+}
+
+/// The style for the title bar in an asciinema recording.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AsciinemaTitleBarStyle {
+    /// Whether to show the title bar for recordings that have a title.
+    #[serde(default)]
+    pub(crate) enabled: Option<bool>,
+
+    /// The colors for the title bar.
+    #[serde(default)]
+    pub(crate) colors: RawColors,
+}
+
+/// The style for banners.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct BannerStyle {
+    /// The color to use for static banners.
+    #[serde(default)]
+    pub(crate) color: Option<RawColor>,
+
+    /// The colors that animated banners cycle through.
+    #[serde(default)]
+    pub(crate) gradient: Vec<RawColor>,
+
+    /// Whether to use the palette's colors as the gradient.
+    #[serde(default)]
+    pub(crate) palette_gradient: Option<bool>,
+}
+
+/// The style for the presenter view in the speaker notes window.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SpeakerNotesStyle {
+    /// Where the next slide's preview is placed relative to the notes.
+    #[serde(default)]
+    pub(crate) layout: Option<SpeakerNotesLayout>,
+
+    /// The next slide's preview.
+    #[serde(default)]
+    pub(crate) preview: SpeakerNotesPreviewStyle,
+
+    /// The bar that shows the current slide and the time.
+    #[serde(default)]
+    pub(crate) status_bar: SpeakerNotesStatusBarStyle,
+}
+
+/// Where the next slide's preview is placed in the speaker notes window.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SpeakerNotesLayout {
+    /// The notes on the left and the preview on the right.
+    #[default]
+    SideBySide,
+
+    /// The notes at the top and the preview below them.
+    Stacked,
+}
+
+/// The style for the next slide's preview in the speaker notes window.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SpeakerNotesPreviewStyle {
+    /// The percentage of the window the preview takes up: its width when side by side, its height when stacked.
+    #[serde(default)]
+    pub(crate) size: Option<u8>,
+
+    /// The kind of border around the preview.
+    #[serde(default)]
+    pub(crate) border: Option<BorderStyle>,
+
+    /// The colors for the border and its label.
+    #[serde(default)]
+    pub(crate) colors: RawColors,
+}
+
+/// The style for the status bar in the speaker notes window.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SpeakerNotesStatusBarStyle {
+    /// The colors for the status bar.
+    #[serde(default)]
+    pub(crate) colors: RawColors,
+}
+
 /// The style for the output of a code execution block.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub(crate) struct ExecutionOutputBlockStyle {
@@ -756,30 +908,6 @@ impl Default for Margin {
     }
 }
 
-/// An element type.
-#[derive(Clone, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub(crate) enum ElementType {
-    SlideTitle,
-    Heading1,
-    Heading2,
-    Heading3,
-    Heading4,
-    Heading5,
-    Heading6,
-    Paragraph,
-    List,
-    Code,
-    PresentationTitle,
-    PresentationSubTitle,
-    PresentationEvent,
-    PresentationLocation,
-    PresentationDate,
-    PresentationAuthor,
-    Table,
-    BlockQuote,
-}
-
 /// Where to position the author's name in the intro slide.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
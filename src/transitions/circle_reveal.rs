@@ -0,0 +1,91 @@
+use super::{AnimateTransition, LinesFrame, TransitionDirection};
+use crate::terminal::virt::TerminalGrid;
+
+/// Reveal the new slide through a circle that expands from the center of the screen.
+pub(crate) struct CircleRevealAnimation {
+    left: TerminalGrid,
+    right: TerminalGrid,
+    /// The distance between each cell and the center of the screen.
+    distances: Vec<Vec<usize>>,
+    direction: TransitionDirection,
+}
+
+impl CircleRevealAnimation {
+    pub(crate) fn new(left: TerminalGrid, right: TerminalGrid, direction: TransitionDirection) -> Self {
+        let rows = left.rows.len();
+        let columns = left.rows.first().map(Vec::len).unwrap_or(0);
+        let center_row = (rows as f64 - 1.0) / 2.0;
+        let center_column = (columns as f64 - 1.0) / 2.0;
+        let distances = (0..rows)
+            .map(|row| {
+                // Cells are roughly twice as tall as they are wide so stretch rows to keep this round.
+                let y = (row as f64 - center_row) * 2.0;
+                (0..columns)
+                    .map(|column| {
+                        let x = column as f64 - center_column;
+                        (x * x + y * y).sqrt().ceil() as usize
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { left, right, distances, direction }
+    }
+}
+
+impl AnimateTransition for CircleRevealAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        // Going back shrinks the circle until the previous slide covers the screen again.
+        let radius = match &self.direction {
+            TransitionDirection::Next => frame,
+            TransitionDirection::Previous => self.total_frames().saturating_sub(frame),
+        };
+        let mut rows = Vec::new();
+        for ((left, right), distances) in self.left.rows.iter().zip(&self.right.rows).zip(&self.distances) {
+            let row = left
+                .iter()
+                .zip(right)
+                .zip(distances)
+                .map(|((left, right), distance)| if *distance < radius { *right } else { *left })
+                .collect();
+            rows.push(row);
+        }
+        let grid = TerminalGrid { rows, background_color: self.left.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        self.distances.iter().flatten().max().map(|distance| distance + 1).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::next_frame0(TransitionDirection::Next, 0, &["ABCDE", "FGHIJ", "KLMNO"])]
+    #[case::next_frame1(TransitionDirection::Next, 1, &["ABCDE", "FG3IJ", "KLMNO"])]
+    #[case::next_frame2(TransitionDirection::Next, 2, &["ABCDE", "F234J", "KLMNO"])]
+    #[case::next_frame3(TransitionDirection::Next, 3, &["AB3DE", "12345", "KL3NO"])]
+    #[case::next_frame4(TransitionDirection::Next, 4, &["12345", "12345", "12345"])]
+    #[case::previous_frame0(TransitionDirection::Previous, 0, &["12345", "12345", "12345"])]
+    #[case::previous_frame1(TransitionDirection::Previous, 1, &["AB3DE", "12345", "KL3NO"])]
+    #[case::previous_frame4(TransitionDirection::Previous, 4, &["ABCDE", "FGHIJ", "KLMNO"])]
+    fn build_frame(#[case] direction: TransitionDirection, #[case] frame: usize, #[case] expected: &[&str]) {
+        let left = build_grid(&["ABCDE", "FGHIJ", "KLMNO"]);
+        let right = build_grid(&["12345", "12345", "12345"]);
+        let transition = CircleRevealAnimation::new(left, right, direction);
+        assert_eq!(transition.total_frames(), 4);
+
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+}
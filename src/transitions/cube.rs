@@ -0,0 +1,74 @@
+use super::{AnimateTransition, LinesFrame, TransitionDirection};
+use crate::terminal::virt::{StyledChar, TerminalGrid};
+
+/// Rotate the slides as if they were the sides of a cube.
+///
+/// The current slide gets squeezed towards the left while the new one grows from the right.
+pub(crate) struct CubeAnimation {
+    left: TerminalGrid,
+    right: TerminalGrid,
+    direction: TransitionDirection,
+}
+
+impl CubeAnimation {
+    pub(crate) fn new(left: TerminalGrid, right: TerminalGrid, direction: TransitionDirection) -> Self {
+        Self { left, right, direction }
+    }
+
+    fn squeeze(row: &[StyledChar], width: usize) -> impl Iterator<Item = StyledChar> + '_ {
+        (0..width).map(move |index| row[index * row.len() / width])
+    }
+}
+
+impl AnimateTransition for CubeAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        let total = self.total_frames();
+        let frame = frame.min(total);
+        let right_width = match &self.direction {
+            TransitionDirection::Next => frame,
+            TransitionDirection::Previous => total - frame,
+        };
+        let mut rows = Vec::new();
+        for (left, right) in self.left.rows.iter().zip(&self.right.rows) {
+            let row = Self::squeeze(left, total - right_width).chain(Self::squeeze(right, right_width)).collect();
+            rows.push(row);
+        }
+        let grid = TerminalGrid { rows, background_color: self.left.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        self.left.rows.first().map(Vec::len).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::next_frame0(TransitionDirection::Next, 0, &["ABCD", "EFGH"])]
+    #[case::next_frame1(TransitionDirection::Next, 1, &["ABC1", "EFG5"])]
+    #[case::next_frame2(TransitionDirection::Next, 2, &["AC13", "EG57"])]
+    #[case::next_frame3(TransitionDirection::Next, 3, &["A123", "E567"])]
+    #[case::next_frame4(TransitionDirection::Next, 4, &["1234", "5678"])]
+    #[case::next_way_past(TransitionDirection::Next, 100, &["1234", "5678"])]
+    #[case::previous_frame0(TransitionDirection::Previous, 0, &["1234", "5678"])]
+    #[case::previous_frame1(TransitionDirection::Previous, 1, &["A123", "E567"])]
+    #[case::previous_frame4(TransitionDirection::Previous, 4, &["ABCD", "EFGH"])]
+    fn build_frame(#[case] direction: TransitionDirection, #[case] frame: usize, #[case] expected: &[&str]) {
+        let left = build_grid(&["ABCD", "EFGH"]);
+        let right = build_grid(&["1234", "5678"]);
+        let transition = CubeAnimation::new(left, right, direction);
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+}
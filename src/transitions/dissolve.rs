@@ -0,0 +1,116 @@
+use super::{AnimateTransition, LinesFrame, TransitionDirection};
+use crate::terminal::virt::{StyledChar, TerminalGrid};
+
+/// The number of frames in a dissolve.
+const TOTAL_FRAMES: usize = 60;
+
+/// The number of frames a cell shows noise for right before it turns into the new slide's cell.
+const NOISE_FRAMES: usize = 4;
+
+/// The character shown while a cell is dissolving.
+const NOISE_CHARACTER: char = '▒';
+
+/// Dissolve the current slide into the new one one cell at a time, in random order.
+pub(crate) struct DissolveAnimation {
+    from: TerminalGrid,
+    to: TerminalGrid,
+    /// The frame at which each cell shows the new slide's contents.
+    thresholds: Vec<Vec<usize>>,
+}
+
+impl DissolveAnimation {
+    pub(crate) fn new(left: TerminalGrid, right: TerminalGrid, direction: TransitionDirection) -> Self {
+        Self::with_rng(left, right, direction, fastrand::Rng::new())
+    }
+
+    fn with_rng(
+        left: TerminalGrid,
+        right: TerminalGrid,
+        direction: TransitionDirection,
+        mut rng: fastrand::Rng,
+    ) -> Self {
+        let (from, to) = match direction {
+            TransitionDirection::Next => (left, right),
+            TransitionDirection::Previous => (right, left),
+        };
+        let thresholds = from
+            .rows
+            .iter()
+            .zip(&to.rows)
+            .map(|(from, to)| {
+                from.iter()
+                    .zip(to)
+                    .map(|(from, to)| if from == to { 0 } else { rng.usize(NOISE_FRAMES + 1..=TOTAL_FRAMES) })
+                    .collect()
+            })
+            .collect();
+        Self { from, to, thresholds }
+    }
+}
+
+impl AnimateTransition for DissolveAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        let mut rows = Vec::new();
+        for ((from, to), thresholds) in self.from.rows.iter().zip(&self.to.rows).zip(&self.thresholds) {
+            let row = from
+                .iter()
+                .zip(to)
+                .zip(thresholds)
+                .map(|((from, to), threshold)| {
+                    if frame >= *threshold {
+                        *to
+                    } else if frame + NOISE_FRAMES >= *threshold {
+                        StyledChar { character: NOISE_CHARACTER, style: from.style }
+                    } else {
+                        *from
+                    }
+                })
+                .collect();
+            rows.push(row);
+        }
+        let grid = TerminalGrid { rows, background_color: self.from.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        TOTAL_FRAMES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::next_start(TransitionDirection::Next, 0, &["ABC", "DEF"])]
+    #[case::next_middle(TransitionDirection::Next, 30, &["A▒C", "4E6"])]
+    #[case::next_end(TransitionDirection::Next, TOTAL_FRAMES, &["123", "456"])]
+    #[case::previous_start(TransitionDirection::Previous, 0, &["123", "456"])]
+    #[case::previous_end(TransitionDirection::Previous, TOTAL_FRAMES, &["ABC", "DEF"])]
+    fn build_frame(#[case] direction: TransitionDirection, #[case] frame: usize, #[case] expected: &[&str]) {
+        let left = build_grid(&["ABC", "DEF"]);
+        let right = build_grid(&["123", "456"]);
+        let transition = DissolveAnimation::with_rng(left, right, direction, fastrand::Rng::with_seed(42));
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn unchanged_cells() {
+        let left = build_grid(&["A B"]);
+        let right = build_grid(&["1 B"]);
+        let transition = DissolveAnimation::new(left, right, TransitionDirection::Next);
+        for frame in 0..=TOTAL_FRAMES {
+            let line = as_text(transition.build_frame(frame, 0).lines.remove(0));
+            assert_eq!(&line[line.len() - 2..], " B", "frame {frame}");
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::ImagePlacement;
+use crate::terminal::{
+    image::{
+        Image,
+        printer::{ImageProperties, PrintImageError, TerminalImage},
+        protocols::kitty::{KittyPlacement, KittyPrinter},
+    },
+    printer::{TerminalCommand, TerminalIo},
+};
+
+/// Draws the images in transition frames by moving kitty placements around.
+///
+/// Every image is transmitted once, the first time it shows up, and is then placed again on every
+/// frame with a stable placement id so the terminal moves it rather than drawing it from scratch.
+pub(crate) struct PlacementRenderer<'a> {
+    printer: &'a KittyPrinter,
+    images: Vec<(Image, u32)>,
+}
+
+impl<'a> PlacementRenderer<'a> {
+    pub(crate) fn new(printer: &'a KittyPrinter) -> Self {
+        Self { printer, images: Vec::new() }
+    }
+
+    /// Draw the given placements, removing any that were drawn for a previous frame.
+    pub(crate) fn render<T>(&mut self, placements: &[ImagePlacement], terminal: &mut T) -> Result<(), PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        for (_, image_id) in &self.images {
+            self.printer.delete(*image_id, false, terminal)?;
+        }
+        for (index, placement) in placements.iter().enumerate() {
+            let TerminalImage::Kitty(image) = placement.image.image() else {
+                continue;
+            };
+            let Some(area) = placement.visible_area() else {
+                continue;
+            };
+            let image_id = self.image_id(&placement.image, terminal)?;
+            let (width, height) = image.dimensions();
+            // Scale cells into pixels using the size the image was originally drawn at.
+            let to_pixels = |cells: u16, total_cells: u16, total_pixels: u32| {
+                (cells as u64 * total_pixels as u64 / total_cells.max(1) as u64) as u32
+            };
+            let source = (
+                to_pixels(area.skip_columns, placement.columns, width),
+                to_pixels(area.skip_rows, placement.rows, height),
+                to_pixels(area.columns, placement.columns, width),
+                to_pixels(area.rows, placement.rows, height),
+            );
+            let kitty_placement =
+                KittyPlacement { source, columns: area.columns, rows: area.rows, z_index: placement.z_index };
+            terminal.execute(&TerminalCommand::MoveTo { column: area.column, row: area.row })?;
+            self.printer.place(image_id, index as u32 + 1, &kitty_placement, terminal)?;
+        }
+        Ok(())
+    }
+
+    /// Delete every image transmitted during the transition.
+    pub(crate) fn finish<T>(self, terminal: &mut T) -> Result<(), PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        for (_, image_id) in self.images {
+            self.printer.delete(image_id, true, terminal)?;
+        }
+        Ok(())
+    }
+
+    fn image_id<T>(&mut self, image: &Image, terminal: &mut T) -> Result<u32, PrintImageError>
+    where
+        T: TerminalIo,
+    {
+        if let Some((_, image_id)) = self.images.iter().find(|(known, _)| known.is_same_resource(image)) {
+            return Ok(*image_id);
+        }
+        let TerminalImage::Kitty(kitty_image) = image.image() else {
+            return Err(PrintImageError::Unsupported);
+        };
+        let image_id = fastrand::u32(1..u32::MAX);
+        self.printer.transmit(kitty_image, image_id, terminal)?;
+        self.images.push((image.clone(), image_id));
+        Ok(image_id)
+    }
+}
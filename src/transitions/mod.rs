@@ -1,16 +1,25 @@
 use crate::{
     markdown::{elements::Line, text_style::Color},
     terminal::{
+        image::Image,
         printer::TerminalCommand,
-        virt::{TerminalGrid, TerminalRowIterator},
+        virt::{PrintedImage, TerminalGrid, TerminalRowIterator},
     },
 };
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug, ops::Range};
 use unicode_width::UnicodeWidthStr;
 
+pub(crate) mod circle_reveal;
 pub(crate) mod collapse_horizontal;
+pub(crate) mod cube;
+pub(crate) mod dissolve;
 pub(crate) mod fade;
+pub(crate) mod images;
+pub(crate) mod push;
 pub(crate) mod slide_horizontal;
+pub(crate) mod slide_vertical;
+pub(crate) mod timing;
+pub(crate) mod wipe;
 
 #[derive(Clone, Debug)]
 pub(crate) enum TransitionDirection {
@@ -23,10 +32,18 @@ pub(crate) trait AnimateTransition {
 
     fn build_frame(&self, frame: usize, previous_frame: usize) -> Self::Frame;
     fn total_frames(&self) -> usize;
+
+    /// The images to draw on top of the given frame.
+    ///
+    /// Animations that can't keep images in place leave them out, which hides them until the
+    /// transition is over and the slide is drawn again.
+    fn image_placements(&self, _frame: usize) -> Vec<ImagePlacement> {
+        Vec::new()
+    }
 }
 
 pub(crate) trait AnimationFrame {
-    fn build_commands(&self) -> Vec<TerminalCommand>;
+    fn build_commands(&self) -> Vec<TerminalCommand<'_>>;
 }
 
 #[derive(Debug)]
@@ -64,7 +81,7 @@ impl From<&TerminalGrid> for LinesFrame {
 }
 
 impl AnimationFrame for LinesFrame {
-    fn build_commands(&self) -> Vec<TerminalCommand> {
+    fn build_commands(&self) -> Vec<TerminalCommand<'_>> {
         use TerminalCommand::*;
         let mut commands = vec![];
         if let Some(color) = self.background_color {
@@ -110,9 +127,89 @@ impl AnimationFrame for LinesFrame {
     }
 }
 
+/// An image drawn on top of a transition frame.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ImagePlacement {
+    pub(crate) image: Image,
+    /// The position of the image's top left corner, which can be outside of the screen.
+    pub(crate) row: i32,
+    pub(crate) column: i32,
+    pub(crate) rows: u16,
+    pub(crate) columns: u16,
+    pub(crate) z_index: i32,
+    /// The part of the screen the image can be seen in. Anything outside of it is cut off.
+    pub(crate) visible_rows: Range<i32>,
+    pub(crate) visible_columns: Range<i32>,
+}
+
+impl ImagePlacement {
+    /// Place the given images after moving them by some offset, sorted by their original position.
+    pub(crate) fn from_images(
+        images: &HashMap<(u16, u16), PrintedImage>,
+        offset: (i32, i32),
+        visible_rows: Range<i32>,
+        visible_columns: Range<i32>,
+    ) -> Vec<Self> {
+        let mut positions: Vec<_> = images.keys().collect();
+        positions.sort();
+        positions
+            .into_iter()
+            .map(|position| {
+                let image = &images[position];
+                Self {
+                    image: image.image.clone(),
+                    row: position.0 as i32 + offset.0,
+                    column: position.1 as i32 + offset.1,
+                    rows: image.height_rows,
+                    columns: image.width_columns,
+                    z_index: image.z_index,
+                    visible_rows: visible_rows.clone(),
+                    visible_columns: visible_columns.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// The part of the image that can be seen, if any.
+    pub(crate) fn visible_area(&self) -> Option<VisibleArea> {
+        let rows = Self::clip(self.row, self.rows, &self.visible_rows)?;
+        let columns = Self::clip(self.column, self.columns, &self.visible_columns)?;
+        Some(VisibleArea {
+            row: rows.start as u16,
+            column: columns.start as u16,
+            rows: rows.len() as u16,
+            columns: columns.len() as u16,
+            skip_rows: (rows.start - self.row) as u16,
+            skip_columns: (columns.start - self.column) as u16,
+        })
+    }
+
+    fn clip(start: i32, length: u16, visible: &Range<i32>) -> Option<Range<i32>> {
+        let clipped = start.max(visible.start).max(0)..(start + length as i32).min(visible.end);
+        if clipped.is_empty() { None } else { Some(clipped) }
+    }
+}
+
+/// The part of an image that can be seen within a frame.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct VisibleArea {
+    /// The position on the screen where the visible part starts.
+    pub(crate) row: u16,
+    pub(crate) column: u16,
+    pub(crate) rows: u16,
+    pub(crate) columns: u16,
+    /// How many of the image's rows and columns are cut off at the top and left.
+    pub(crate) skip_rows: u16,
+    pub(crate) skip_columns: u16,
+}
+
 #[cfg(test)]
 mod utils {
-    use crate::terminal::virt::{StyledChar, TerminalGrid};
+    use crate::terminal::{
+        image::{Image, ImageSource, printer::TerminalImage},
+        virt::{PrintedImage, StyledChar, TerminalGrid},
+    };
+    use image::{ColorType, DynamicImage};
 
     pub(crate) fn build_grid(rows: &[&str]) -> TerminalGrid {
         let rows = rows
@@ -121,12 +218,19 @@ mod utils {
             .collect();
         TerminalGrid { rows, background_color: None, images: Default::default() }
     }
+
+    pub(crate) fn build_image(columns: u16, rows: u16) -> PrintedImage {
+        let image = DynamicImage::new(columns as u32, rows as u32, ColorType::Rgba8);
+        let image = Image::new(TerminalImage::Ascii(image.into()), ImageSource::Generated);
+        PrintedImage { image, width_columns: columns, height_rows: rows, z_index: 0 }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::markdown::elements::Text;
+    use rstest::rstest;
 
     #[test]
     fn commands() {
@@ -157,4 +261,25 @@ mod tests {
         ];
         assert_eq!(commands, expected);
     }
+
+    #[rstest]
+    #[case::inside((1, 2), 0..10, 0..10, Some((1, 2, 3, 4, 0, 0)))]
+    #[case::cut_left((1, -2), 0..10, 0..10, Some((1, 0, 3, 2, 0, 2)))]
+    #[case::cut_bottom((8, 2), 0..10, 0..10, Some((8, 2, 2, 4, 0, 0)))]
+    #[case::cut_by_bounds((1, 2), 2..10, 0..4, Some((2, 2, 2, 2, 1, 0)))]
+    #[case::outside((1, 12), 0..10, 0..10, None)]
+    #[case::empty_bounds((1, 2), 0..10, 3..3, None)]
+    fn visible_area(
+        #[case] position: (i32, i32),
+        #[case] visible_rows: Range<i32>,
+        #[case] visible_columns: Range<i32>,
+        #[case] expected: Option<(u16, u16, u16, u16, u16, u16)>,
+    ) {
+        let images = HashMap::from([((0, 0), utils::build_image(4, 3))]);
+        let placement = ImagePlacement::from_images(&images, position, visible_rows, visible_columns).remove(0);
+        let area = placement
+            .visible_area()
+            .map(|area| (area.row, area.column, area.rows, area.columns, area.skip_rows, area.skip_columns));
+        assert_eq!(area, expected);
+    }
 }
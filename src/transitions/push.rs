@@ -0,0 +1,83 @@
+use super::{AnimateTransition, ImagePlacement, LinesFrame, TransitionDirection};
+use crate::terminal::virt::TerminalGrid;
+
+/// Push the new slide in from the right, on top of the current one which stays in place.
+pub(crate) struct PushAnimation {
+    left: TerminalGrid,
+    right: TerminalGrid,
+    direction: TransitionDirection,
+}
+
+impl PushAnimation {
+    pub(crate) fn new(left: TerminalGrid, right: TerminalGrid, direction: TransitionDirection) -> Self {
+        Self { left, right, direction }
+    }
+
+    fn edge(&self, frame: usize) -> usize {
+        let total = self.total_frames();
+        let frame = frame.min(total);
+        match &self.direction {
+            TransitionDirection::Next => total - frame,
+            TransitionDirection::Previous => frame,
+        }
+    }
+}
+
+impl AnimateTransition for PushAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        let total = self.total_frames();
+        // The column where the new slide's left edge is at.
+        let edge = self.edge(frame);
+        let mut rows = Vec::new();
+        for (left, right) in self.left.rows.iter().zip(&self.right.rows) {
+            let row = left[..edge].iter().chain(&right[..total - edge]).copied().collect();
+            rows.push(row);
+        }
+        let grid = TerminalGrid { rows, background_color: self.left.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        self.left.rows.first().map(Vec::len).unwrap_or(0)
+    }
+
+    fn image_placements(&self, frame: usize) -> Vec<ImagePlacement> {
+        let edge = self.edge(frame) as i32;
+        let rows = 0..self.left.rows.len() as i32;
+        let columns = self.total_frames() as i32;
+        // The slide that stays in place is covered by the one coming in.
+        let mut placements = ImagePlacement::from_images(&self.left.images, (0, 0), rows.clone(), 0..edge);
+        placements.extend(ImagePlacement::from_images(&self.right.images, (0, edge), rows, edge..columns));
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::next_frame0(TransitionDirection::Next, 0, &["ABCD", "EFGH"])]
+    #[case::next_frame1(TransitionDirection::Next, 1, &["ABC1", "EFG5"])]
+    #[case::next_frame2(TransitionDirection::Next, 2, &["AB12", "EF56"])]
+    #[case::next_frame4(TransitionDirection::Next, 4, &["1234", "5678"])]
+    #[case::next_way_past(TransitionDirection::Next, 100, &["1234", "5678"])]
+    #[case::previous_frame0(TransitionDirection::Previous, 0, &["1234", "5678"])]
+    #[case::previous_frame1(TransitionDirection::Previous, 1, &["A123", "E567"])]
+    #[case::previous_frame3(TransitionDirection::Previous, 3, &["ABC1", "EFG5"])]
+    fn build_frame(#[case] direction: TransitionDirection, #[case] frame: usize, #[case] expected: &[&str]) {
+        let left = build_grid(&["ABCD", "EFGH"]);
+        let right = build_grid(&["1234", "5678"]);
+        let transition = PushAnimation::new(left, right, direction);
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+}
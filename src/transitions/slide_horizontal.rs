@@ -1,14 +1,17 @@
-use super::{AnimateTransition, LinesFrame, TransitionDirection};
+use super::{AnimateTransition, ImagePlacement, LinesFrame, TransitionDirection};
 use crate::{
     WindowSize,
     markdown::elements::Line,
-    terminal::virt::{TerminalGrid, TerminalRowIterator},
+    terminal::virt::{PrintedImage, TerminalGrid, TerminalRowIterator},
 };
+use std::collections::HashMap;
 
 pub(crate) struct SlideHorizontalAnimation {
     grid: TerminalGrid,
     dimensions: WindowSize,
     direction: TransitionDirection,
+    left_images: HashMap<(u16, u16), PrintedImage>,
+    right_images: HashMap<(u16, u16), PrintedImage>,
 }
 
 impl SlideHorizontalAnimation {
@@ -24,7 +27,16 @@ impl SlideHorizontalAnimation {
             rows.push(row);
         }
         let grid = TerminalGrid { rows, background_color: left.background_color, images: Default::default() };
-        Self { grid, dimensions, direction }
+        Self { grid, dimensions, direction, left_images: left.images, right_images: right.images }
+    }
+
+    fn first_column(&self, frame: usize) -> usize {
+        let total = self.total_frames();
+        let frame = frame.min(total);
+        match &self.direction {
+            TransitionDirection::Next => frame,
+            TransitionDirection::Previous => total.saturating_sub(frame),
+        }
     }
 }
 
@@ -32,12 +44,7 @@ impl AnimateTransition for SlideHorizontalAnimation {
     type Frame = LinesFrame;
 
     fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
-        let total = self.total_frames();
-        let frame = frame.min(total);
-        let index = match &self.direction {
-            TransitionDirection::Next => frame,
-            TransitionDirection::Previous => total.saturating_sub(frame),
-        };
+        let index = self.first_column(frame);
         let mut lines = Vec::new();
         for row in &self.grid.rows {
             let row = &row[index..index + self.dimensions.columns as usize];
@@ -64,6 +71,15 @@ impl AnimateTransition for SlideHorizontalAnimation {
     fn total_frames(&self) -> usize {
         self.grid.rows[0].len().saturating_sub(self.dimensions.columns as usize)
     }
+
+    fn image_placements(&self, frame: usize) -> Vec<ImagePlacement> {
+        let index = self.first_column(frame) as i32;
+        let columns = self.dimensions.columns as i32;
+        let rows = 0..self.grid.rows.len() as i32;
+        let mut placements = ImagePlacement::from_images(&self.left_images, (0, -index), rows.clone(), 0..columns);
+        placements.extend(ImagePlacement::from_images(&self.right_images, (0, columns - index), rows, 0..columns));
+        placements
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +110,22 @@ mod tests {
         let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
         assert_eq!(lines, expected);
     }
+
+    #[rstest]
+    #[case::next_frame0(0, TransitionDirection::Next, &[1, 4])]
+    #[case::next_frame1(1, TransitionDirection::Next, &[0, 3])]
+    #[case::next_frame3(3, TransitionDirection::Next, &[-2, 1])]
+    #[case::previous_frame1(1, TransitionDirection::Previous, &[-1, 2])]
+    fn image_placements(#[case] frame: usize, #[case] direction: TransitionDirection, #[case] expected: &[i32]) {
+        use crate::transitions::utils::{build_grid, build_image};
+
+        let mut left = build_grid(&["ABC", "DEF"]);
+        left.images.insert((0, 1), build_image(1, 1));
+        let mut right = build_grid(&["GHI", "JKL"]);
+        right.images.insert((1, 1), build_image(1, 1));
+        let dimensions = WindowSize { rows: 2, columns: 3, height: 0, width: 0 };
+        let transition = SlideHorizontalAnimation::new(left, right, dimensions, direction);
+        let columns: Vec<_> = transition.image_placements(frame).into_iter().map(|p| p.column).collect();
+        assert_eq!(columns, expected);
+    }
 }
@@ -0,0 +1,81 @@
+use super::{AnimateTransition, ImagePlacement, LinesFrame, TransitionDirection};
+use crate::terminal::virt::{PrintedImage, TerminalGrid};
+use std::collections::HashMap;
+
+pub(crate) struct SlideVerticalAnimation {
+    grid: TerminalGrid,
+    height: usize,
+    direction: TransitionDirection,
+    top_images: HashMap<(u16, u16), PrintedImage>,
+    bottom_images: HashMap<(u16, u16), PrintedImage>,
+}
+
+impl SlideVerticalAnimation {
+    pub(crate) fn new(top: TerminalGrid, bottom: TerminalGrid, direction: TransitionDirection) -> Self {
+        let height = top.rows.len();
+        let background_color = top.background_color;
+        let rows = top.rows.into_iter().chain(bottom.rows).collect();
+        let grid = TerminalGrid { rows, background_color, images: Default::default() };
+        Self { grid, height, direction, top_images: top.images, bottom_images: bottom.images }
+    }
+
+    fn first_row(&self, frame: usize) -> usize {
+        let total = self.total_frames();
+        let frame = frame.min(total);
+        match &self.direction {
+            TransitionDirection::Next => frame,
+            TransitionDirection::Previous => total - frame,
+        }
+    }
+}
+
+impl AnimateTransition for SlideVerticalAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        let index = self.first_row(frame);
+        let rows = self.grid.rows[index..index + self.height].to_vec();
+        let grid = TerminalGrid { rows, background_color: self.grid.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        self.height
+    }
+
+    fn image_placements(&self, frame: usize) -> Vec<ImagePlacement> {
+        let index = self.first_row(frame) as i32;
+        let height = self.height as i32;
+        let columns = 0..self.grid.rows.first().map(Vec::len).unwrap_or(0) as i32;
+        let mut placements = ImagePlacement::from_images(&self.top_images, (-index, 0), 0..height, columns.clone());
+        placements.extend(ImagePlacement::from_images(&self.bottom_images, (height - index, 0), 0..height, columns));
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::next_frame0(0, TransitionDirection::Next, &["AB", "CD"])]
+    #[case::next_frame1(1, TransitionDirection::Next, &["CD", "EF"])]
+    #[case::next_frame2(2, TransitionDirection::Next, &["EF", "GH"])]
+    #[case::next_way_past(100, TransitionDirection::Next, &["EF", "GH"])]
+    #[case::previous_frame0(0, TransitionDirection::Previous, &["EF", "GH"])]
+    #[case::previous_frame1(1, TransitionDirection::Previous, &["CD", "EF"])]
+    #[case::previous_frame2(2, TransitionDirection::Previous, &["AB", "CD"])]
+    fn build_frame(#[case] frame: usize, #[case] direction: TransitionDirection, #[case] expected: &[&str]) {
+        let top = build_grid(&["AB", "CD"]);
+        let bottom = build_grid(&["EF", "GH"]);
+        let transition = SlideVerticalAnimation::new(top, bottom, direction);
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::config::{SlideTransitionConfig, TransitionEasing};
+use std::time::Duration;
+
+/// The number of bisection steps used to invert a bézier curve's `x` coordinate.
+const BEZIER_ITERATIONS: usize = 32;
+
+impl TransitionEasing {
+    /// Map a linear progress in the `[0, 1]` range into the eased progress.
+    pub(crate) fn apply(&self, progress: f64) -> f64 {
+        let progress = progress.clamp(0.0, 1.0);
+        // These match the curves CSS uses for the same names.
+        let [x1, y1, x2, y2] = match self {
+            Self::Linear => return progress,
+            Self::EaseIn => [0.42, 0.0, 1.0, 1.0],
+            Self::EaseOut => [0.0, 0.0, 0.58, 1.0],
+            Self::EaseInOut => [0.42, 0.0, 0.58, 1.0],
+            Self::CubicBezier(points) => *points,
+        };
+        cubic_bezier(progress, x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2)
+    }
+}
+
+/// Evaluate the `y` coordinate of a bézier curve going from `(0, 0)` to `(1, 1)` at the given `x`.
+///
+/// Clamping the control points' `x` coordinates into `[0, 1]` makes `x` monotonic in `t`, so it
+/// can be inverted via bisection.
+fn cubic_bezier(x: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    let sample = |t: f64, p1: f64, p2: f64| {
+        let inverse = 1.0 - t;
+        3.0 * inverse * inverse * t * p1 + 3.0 * inverse * t * t * p2 + t * t * t
+    };
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..BEZIER_ITERATIONS {
+        let middle = (low + high) / 2.0;
+        if sample(middle, x1, x2) < x {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    sample((low + high) / 2.0, y1, y2)
+}
+
+/// What to do at a point in time during a transition.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ScheduledFrame {
+    /// Render the given frame, which follows `previous`.
+    Render { frame: usize, previous: usize },
+
+    /// The frame on screen is still the right one.
+    Unchanged,
+
+    /// The transition is over.
+    Done,
+}
+
+/// Decides which frame of a transition should be on screen based on how long it's been running.
+///
+/// Frames are picked from the wall clock rather than by counting them, so if rendering falls
+/// behind, the frames in between are dropped and the transition still takes as long as configured.
+pub(crate) struct FrameScheduler {
+    duration: Duration,
+    interval: Duration,
+    easing: TransitionEasing,
+    total_frames: usize,
+    last_frame: usize,
+}
+
+impl FrameScheduler {
+    /// Construct a scheduler for an animation with the given number of frames, where frame 0 is
+    /// assumed to already be on screen.
+    pub(crate) fn new(config: &SlideTransitionConfig, total_frames: usize) -> Self {
+        let duration = Duration::from_millis(config.duration_millis as u64);
+        let interval = duration / config.frames.max(1) as u32;
+        Self { duration, interval, easing: config.easing.clone(), total_frames, last_frame: 0 }
+    }
+
+    /// Get the frame that should be on screen after the given time has elapsed.
+    pub(crate) fn poll(&mut self, elapsed: Duration) -> ScheduledFrame {
+        let last_index = self.total_frames.saturating_sub(1);
+        if self.last_frame >= last_index {
+            return ScheduledFrame::Done;
+        }
+        let frame = if elapsed >= self.duration {
+            last_index
+        } else {
+            let progress = self.easing.apply(elapsed.as_secs_f64() / self.duration.as_secs_f64());
+            // Animations build frames incrementally so never go back even if the curve does. The
+            // epsilon keeps rounding errors from landing right below a frame boundary.
+            ((progress * self.total_frames as f64 + 1e-9) as usize).clamp(self.last_frame, last_index)
+        };
+        if frame == self.last_frame {
+            return ScheduledFrame::Unchanged;
+        }
+        let previous = self.last_frame;
+        self.last_frame = frame;
+        ScheduledFrame::Render { frame, previous }
+    }
+
+    /// The time to wait, given the elapsed time, until the next frame tick.
+    pub(crate) fn time_until_next_tick(&self, elapsed: Duration) -> Duration {
+        if self.interval.is_zero() || elapsed >= self.duration {
+            return Duration::ZERO;
+        }
+        let ticks = elapsed.as_nanos() / self.interval.as_nanos() + 1;
+        let next_tick = self.interval.saturating_mul(ticks as u32).min(self.duration);
+        next_tick.saturating_sub(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SlideTransitionStyleConfig;
+    use rstest::rstest;
+
+    fn scheduler(duration_millis: u16, frames: usize, total_frames: usize) -> FrameScheduler {
+        let config = SlideTransitionConfig {
+            duration_millis,
+            frames,
+            animation: SlideTransitionStyleConfig::Fade,
+            easing: TransitionEasing::Linear,
+        };
+        FrameScheduler::new(&config, total_frames)
+    }
+
+    #[rstest]
+    #[case::linear(TransitionEasing::Linear)]
+    #[case::ease_in(TransitionEasing::EaseIn)]
+    #[case::ease_out(TransitionEasing::EaseOut)]
+    #[case::ease_in_out(TransitionEasing::EaseInOut)]
+    #[case::bezier(TransitionEasing::CubicBezier([0.2, 0.8, 0.6, 0.1]))]
+    fn easing_endpoints(#[case] easing: TransitionEasing) {
+        assert!(easing.apply(0.0).abs() < 1e-6);
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[rstest]
+    #[case::linear(TransitionEasing::Linear, 0.25, 0.25)]
+    #[case::ease_in(TransitionEasing::EaseIn, 0.25, 0.093)]
+    #[case::ease_out(TransitionEasing::EaseOut, 0.75, 0.907)]
+    #[case::ease_in_out(TransitionEasing::EaseInOut, 0.5, 0.5)]
+    #[case::out_of_range(TransitionEasing::EaseIn, 1.5, 1.0)]
+    fn easing_values(#[case] easing: TransitionEasing, #[case] progress: f64, #[case] expected: f64) {
+        let value = easing.apply(progress);
+        assert!((value - expected).abs() < 1e-3, "got {value}");
+    }
+
+    #[test]
+    fn every_frame_on_time() {
+        let mut scheduler = scheduler(100, 10, 10);
+        for frame in 1..10 {
+            let elapsed = Duration::from_millis(frame as u64 * 10);
+            assert_eq!(scheduler.poll(elapsed), ScheduledFrame::Render { frame, previous: frame - 1 });
+            assert_eq!(scheduler.time_until_next_tick(elapsed), Duration::from_millis(10));
+        }
+        assert_eq!(scheduler.poll(Duration::from_millis(100)), ScheduledFrame::Done);
+    }
+
+    #[test]
+    fn drop_frames_when_late() {
+        let mut scheduler = scheduler(100, 10, 10);
+        assert_eq!(scheduler.poll(Duration::from_millis(10)), ScheduledFrame::Render { frame: 1, previous: 0 });
+        assert_eq!(scheduler.poll(Duration::from_millis(55)), ScheduledFrame::Render { frame: 5, previous: 1 });
+        assert_eq!(scheduler.time_until_next_tick(Duration::from_millis(55)), Duration::from_millis(5));
+        assert_eq!(scheduler.poll(Duration::from_millis(55)), ScheduledFrame::Unchanged);
+        assert_eq!(scheduler.poll(Duration::from_millis(250)), ScheduledFrame::Render { frame: 9, previous: 5 });
+        assert_eq!(scheduler.poll(Duration::from_millis(250)), ScheduledFrame::Done);
+    }
+
+    #[test]
+    fn fewer_ticks_than_frames() {
+        let mut scheduler = scheduler(100, 4, 40);
+        assert_eq!(scheduler.poll(Duration::from_millis(25)), ScheduledFrame::Render { frame: 10, previous: 0 });
+        assert_eq!(scheduler.time_until_next_tick(Duration::from_millis(25)), Duration::from_millis(25));
+        assert_eq!(scheduler.poll(Duration::from_millis(100)), ScheduledFrame::Render { frame: 39, previous: 10 });
+    }
+}
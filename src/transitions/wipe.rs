@@ -0,0 +1,175 @@
+use super::{AnimateTransition, ImagePlacement, LinesFrame, TransitionDirection};
+use crate::terminal::virt::TerminalGrid;
+use std::ops::Range;
+
+/// The direction the edge of a wipe moves towards.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum WipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl WipeDirection {
+    fn reverse(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+/// Reveal the new slide behind an edge that sweeps across the screen.
+pub(crate) struct WipeAnimation {
+    from: TerminalGrid,
+    to: TerminalGrid,
+    wipe: WipeDirection,
+}
+
+impl WipeAnimation {
+    pub(crate) fn new(
+        left: TerminalGrid,
+        right: TerminalGrid,
+        wipe: WipeDirection,
+        direction: TransitionDirection,
+    ) -> Self {
+        // Going back undoes the wipe, so the edge moves the other way.
+        match direction {
+            TransitionDirection::Next => Self { from: left, to: right, wipe },
+            TransitionDirection::Previous => Self { from: right, to: left, wipe: wipe.reverse() },
+        }
+    }
+
+    fn is_revealed(&self, row: usize, column: usize, frame: usize) -> bool {
+        let rows = self.from.rows.len();
+        let columns = self.columns();
+        match self.wipe {
+            WipeDirection::Right => column < frame,
+            WipeDirection::Left => column >= columns.saturating_sub(frame),
+            WipeDirection::Down => row < frame,
+            WipeDirection::Up => row >= rows.saturating_sub(frame),
+        }
+    }
+
+    /// The rows and columns the new slide is visible in, along with the ones the old one still is.
+    fn areas(&self, frame: usize) -> [(Range<i32>, Range<i32>); 2] {
+        let rows = self.from.rows.len() as i32;
+        let columns = self.columns() as i32;
+        let frame = (frame as i32).min(self.total_frames() as i32);
+        match self.wipe {
+            WipeDirection::Right => [(0..rows, 0..frame), (0..rows, frame..columns)],
+            WipeDirection::Left => [(0..rows, columns - frame..columns), (0..rows, 0..columns - frame)],
+            WipeDirection::Down => [(0..frame, 0..columns), (frame..rows, 0..columns)],
+            WipeDirection::Up => [(rows - frame..rows, 0..columns), (0..rows - frame, 0..columns)],
+        }
+    }
+
+    fn columns(&self) -> usize {
+        self.from.rows.first().map(Vec::len).unwrap_or(0)
+    }
+}
+
+impl AnimateTransition for WipeAnimation {
+    type Frame = LinesFrame;
+
+    fn build_frame(&self, frame: usize, _previous_frame: usize) -> Self::Frame {
+        let mut rows = Vec::new();
+        for (row_index, (from, to)) in self.from.rows.iter().zip(&self.to.rows).enumerate() {
+            let row = from
+                .iter()
+                .zip(to)
+                .enumerate()
+                .map(|(column, (from, to))| if self.is_revealed(row_index, column, frame) { *to } else { *from })
+                .collect();
+            rows.push(row);
+        }
+        let grid = TerminalGrid { rows, background_color: self.from.background_color, images: Default::default() };
+        LinesFrame::from(&grid)
+    }
+
+    fn total_frames(&self) -> usize {
+        match self.wipe {
+            WipeDirection::Left | WipeDirection::Right => self.columns(),
+            WipeDirection::Up | WipeDirection::Down => self.from.rows.len(),
+        }
+    }
+
+    fn image_placements(&self, frame: usize) -> Vec<ImagePlacement> {
+        let [(to_rows, to_columns), (from_rows, from_columns)] = self.areas(frame);
+        let mut placements = ImagePlacement::from_images(&self.from.images, (0, 0), from_rows, from_columns);
+        placements.extend(ImagePlacement::from_images(&self.to.images, (0, 0), to_rows, to_columns));
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{markdown::elements::Line, transitions::utils::build_grid};
+    use rstest::rstest;
+
+    fn as_text(line: Line) -> String {
+        line.0.into_iter().map(|l| l.content).collect()
+    }
+
+    #[rstest]
+    #[case::right_frame0(WipeDirection::Right, TransitionDirection::Next, 0, &["ABC", "DEF"])]
+    #[case::right_frame1(WipeDirection::Right, TransitionDirection::Next, 1, &["1BC", "4EF"])]
+    #[case::right_frame3(WipeDirection::Right, TransitionDirection::Next, 3, &["123", "456"])]
+    #[case::left_frame1(WipeDirection::Left, TransitionDirection::Next, 1, &["AB3", "DE6"])]
+    #[case::left_frame2(WipeDirection::Left, TransitionDirection::Next, 2, &["A23", "D56"])]
+    #[case::down_frame1(WipeDirection::Down, TransitionDirection::Next, 1, &["123", "DEF"])]
+    #[case::up_frame1(WipeDirection::Up, TransitionDirection::Next, 1, &["ABC", "456"])]
+    #[case::up_frame2(WipeDirection::Up, TransitionDirection::Next, 2, &["123", "456"])]
+    #[case::right_previous_frame0(WipeDirection::Right, TransitionDirection::Previous, 0, &["123", "456"])]
+    #[case::right_previous_frame1(WipeDirection::Right, TransitionDirection::Previous, 1, &["12C", "45F"])]
+    #[case::right_previous_frame3(WipeDirection::Right, TransitionDirection::Previous, 3, &["ABC", "DEF"])]
+    fn build_frame(
+        #[case] wipe: WipeDirection,
+        #[case] direction: TransitionDirection,
+        #[case] frame: usize,
+        #[case] expected: &[&str],
+    ) {
+        let left = build_grid(&["ABC", "DEF"]);
+        let right = build_grid(&["123", "456"]);
+        let transition = WipeAnimation::new(left, right, wipe, direction);
+        let lines: Vec<_> = transition.build_frame(frame, 0).lines.into_iter().map(as_text).collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[rstest]
+    #[case::horizontal(WipeDirection::Left, 3)]
+    #[case::vertical(WipeDirection::Down, 2)]
+    fn total_frames(#[case] wipe: WipeDirection, #[case] expected: usize) {
+        let left = build_grid(&["ABC", "DEF"]);
+        let right = build_grid(&["123", "456"]);
+        let transition = WipeAnimation::new(left, right, wipe, TransitionDirection::Next);
+        assert_eq!(transition.total_frames(), expected);
+    }
+
+    #[rstest]
+    #[case::right_frame0(WipeDirection::Right, 0, &[Some((0, 2)), None])]
+    #[case::right_frame1(WipeDirection::Right, 1, &[Some((1, 1)), None])]
+    #[case::right_frame2(WipeDirection::Right, 2, &[None, Some((1, 1))])]
+    #[case::right_frame3(WipeDirection::Right, 3, &[None, Some((1, 2))])]
+    #[case::down_frame1(WipeDirection::Down, 1, &[None, None])]
+    #[case::down_frame2(WipeDirection::Down, 2, &[None, Some((1, 2))])]
+    fn image_placements(#[case] wipe: WipeDirection, #[case] frame: usize, #[case] expected: &[Option<(u16, u16)>]) {
+        use crate::transitions::utils::build_image;
+
+        let mut left = build_grid(&["ABC", "DEF"]);
+        left.images.insert((0, 0), build_image(2, 1));
+        let mut right = build_grid(&["123", "456"]);
+        right.images.insert((1, 1), build_image(2, 1));
+        let transition = WipeAnimation::new(left, right, wipe, TransitionDirection::Next);
+        let areas: Vec<_> = transition
+            .image_placements(frame)
+            .into_iter()
+            .map(|placement| placement.visible_area().map(|area| (area.column, area.columns)))
+            .collect();
+        assert_eq!(areas, expected);
+    }
+}
@@ -11,11 +11,12 @@ use crate::{
 };
 use crossterm::{
     ExecutableCommand, cursor,
-    terminal::{self, disable_raw_mode, enable_raw_mode},
+    terminal::{self, ClearType, disable_raw_mode, enable_raw_mode},
 };
 use std::{
     io::{self},
     ops::Deref,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::{Arc, Mutex},
 };
@@ -30,6 +31,7 @@ pub(crate) struct RunAcquireTerminalSnippet {
     colors: ExecutionStatusBlockStyle,
     state: Arc<Mutex<State>>,
     font_size: u8,
+    record: Option<PathBuf>,
 }
 
 impl RunAcquireTerminalSnippet {
@@ -40,18 +42,26 @@ impl RunAcquireTerminalSnippet {
         block_length: u16,
         font_size: u8,
     ) -> Self {
-        Self { snippet, block_length, executor, colors, state: Default::default(), font_size }
+        Self { snippet, block_length, executor, colors, state: Default::default(), font_size, record: None }
+    }
+
+    /// Record the execution into an asciicast file at the given path.
+    pub(crate) fn record(mut self, path: PathBuf) -> Self {
+        self.record = Some(path);
+        self
     }
 
     fn invoke(&self) -> Result<(), String> {
         let mut stdout = io::stdout();
-        stdout
-            .execute(terminal::LeaveAlternateScreen)
-            .and_then(|_| disable_raw_mode())
-            .map_err(|e| format!("failed to deinit terminal: {e}"))?;
+        stdout.execute(terminal::LeaveAlternateScreen).map_err(|e| format!("failed to deinit terminal: {e}"))?;
 
         // save result for later, but first reinit the terminal
-        let result = self.executor.execute_sync(&self.snippet).map_err(|e| format!("failed to run snippet: {e}"));
+        let result = match &self.record {
+            Some(path) => self.invoke_recorded(path),
+            None => disable_raw_mode().map_err(|e| format!("failed to deinit terminal: {e}")).and_then(|_| {
+                self.executor.execute_sync(&self.snippet).map_err(|e| format!("failed to run snippet: {e}"))
+            }),
+        };
 
         stdout
             .execute(terminal::EnterAlternateScreen)
@@ -62,6 +72,19 @@ impl RunAcquireTerminalSnippet {
         }
         result
     }
+
+    fn invoke_recorded(&self, path: &Path) -> Result<(), String> {
+        // The command's terminal handles everything, including echoing input, so keep ours in raw
+        // mode and start with a clean screen like the one the recording will be played in.
+        let mut stdout = io::stdout();
+        stdout
+            .execute(terminal::Clear(ClearType::All))
+            .and_then(|stdout| stdout.execute(cursor::MoveTo(0, 0)))
+            .and_then(|stdout| stdout.execute(cursor::Show))
+            .map_err(|e| format!("failed to deinit terminal: {e}"))?;
+        let size = terminal::size().map_err(|e| format!("failed to get terminal size: {e}"))?;
+        self.executor.execute_recorded(&self.snippet, path, size).map_err(|e| format!("failed to record snippet: {e}"))
+    }
 }
 
 impl AsRenderOperations for RunAcquireTerminalSnippet {
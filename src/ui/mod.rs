@@ -2,3 +2,4 @@ pub(crate) mod execution;
 pub(crate) mod footer;
 pub(crate) mod modals;
 pub(crate) mod separator;
+pub(crate) mod speaker_notes;
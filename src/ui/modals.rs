@@ -7,7 +7,6 @@ use crate::{
         text::WeightedLine,
         text_style::TextStyle,
     },
-    presentation::PresentationState,
     render::{
         operation::{
             AsRenderOperations, ImagePosition, ImageRenderProperties, ImageSize, MarginProperties, RenderOperation,
@@ -17,30 +16,51 @@ use crate::{
     terminal::image::Image,
     theme::{Margin, PresentationTheme},
 };
-use std::{iter, rc::Rc};
-use unicode_width::UnicodeWidthStr;
+use std::{cell::RefCell, cmp::Reverse, iter, rc::Rc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 static MODAL_Z_INDEX: i32 = -1;
 
+/// The number of lines of each slide shown as a preview in the slide index.
+const PREVIEW_LINES: usize = 4;
+
 #[derive(Default)]
 pub(crate) struct IndexBuilder {
     titles: Vec<Line>,
+    previews: Vec<Vec<String>>,
     background: Option<Image>,
 }
 
 impl IndexBuilder {
-    pub(crate) fn add_title(&mut self, title: Line) {
+    pub(crate) fn add_slide<'a>(&mut self, title: Line, operations: impl Iterator<Item = &'a RenderOperation>) {
+        let plain_title = plain_text(&title.0);
+        let preview = operations
+            .filter_map(|operation| match operation {
+                RenderOperation::RenderText { line, .. } => Some(plain_text(line.iter_texts().map(|t| t.text()))),
+                RenderOperation::RenderBlockLine(block) => {
+                    let prefix = block.prefix.text().content.clone();
+                    Some(prefix + &plain_text(block.text.iter_texts().map(|t| t.text())))
+                }
+                _ => None,
+            })
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty() && line != plain_title.trim())
+            .take(PREVIEW_LINES)
+            .collect();
         self.titles.push(title);
+        self.previews.push(preview);
     }
 
     pub(crate) fn set_background(&mut self, background: Image) {
         self.background = Some(background);
     }
 
-    pub(crate) fn build(self, theme: &PresentationTheme, state: PresentationState) -> Vec<RenderOperation> {
+    pub(crate) fn build(self, theme: &PresentationTheme) -> (Vec<RenderOperation>, SlideIndexState) {
         let mut builder = ModalBuilder::new("Slides");
         let padder = NumberPadder::new(self.titles.len());
+        let mut titles = Vec::new();
         for (index, mut title) in self.titles.into_iter().enumerate() {
+            titles.push(plain_text(&title.0));
             let index = padder.pad_right(index + 1);
             title.0.insert(0, format!("{index}: ").into());
             builder.content.push(title);
@@ -48,17 +68,167 @@ impl IndexBuilder {
         let base_style = theme.modals.style;
         let selection_style = theme.modals.selection_style;
         let ModalContent { prefix, content, suffix, content_width } = builder.build(base_style);
+        let state = SlideIndexState::new(titles);
         let drawer = IndexDrawer {
             prefix,
             rows: content,
             suffix,
-            state,
+            previews: self.previews,
+            state: state.clone(),
             content_width,
             selection_style,
             background: self.background,
         };
-        vec![RenderOperation::RenderDynamic(Rc::new(drawer))]
+        (vec![RenderOperation::RenderDynamic(Rc::new(drawer))], state)
+    }
+}
+
+/// The state of the slide index modal: the selected slide and the search being typed, if any.
+///
+/// This is shared between the presenter, which feeds it the keys pressed while the index is open,
+/// and the operations that draw the index.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SlideIndexState {
+    inner: Rc<RefCell<SlideIndexStateInner>>,
+}
+
+#[derive(Debug, Default)]
+struct SlideIndexStateInner {
+    titles: Vec<String>,
+    query: Option<String>,
+    matches: Vec<usize>,
+    cursor: usize,
+}
+
+impl SlideIndexState {
+    fn new(titles: Vec<String>) -> Self {
+        let matches = (0..titles.len()).collect();
+        let inner = SlideIndexStateInner { titles, query: None, matches, cursor: 0 };
+        Self { inner: Rc::new(RefCell::new(inner)) }
+    }
+
+    /// Reset the index so it lists every slide and has the given one selected.
+    pub(crate) fn open(&self, slide_index: usize) {
+        let mut inner = self.inner.borrow_mut();
+        inner.query = None;
+        inner.matches = (0..inner.titles.len()).collect();
+        inner.cursor = slide_index.min(inner.matches.len().saturating_sub(1));
+    }
+
+    /// The index of the selected slide, if any slide matches the search.
+    pub(crate) fn selected_slide(&self) -> Option<usize> {
+        let inner = self.inner.borrow();
+        inner.matches.get(inner.cursor).copied()
+    }
+
+    pub(crate) fn select_previous(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.cursor = inner.cursor.saturating_sub(1);
+    }
+
+    pub(crate) fn select_next(&self) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.cursor + 1 < inner.matches.len() {
+            inner.cursor += 1;
+        }
+    }
+
+    pub(crate) fn is_searching(&self) -> bool {
+        self.inner.borrow().query.is_some()
+    }
+
+    pub(crate) fn start_search(&self) {
+        self.update_query(String::new());
+    }
+
+    /// Stop searching, listing every slide again but keeping the selected one.
+    pub(crate) fn cancel_search(&self) {
+        let selected = self.selected_slide();
+        let mut inner = self.inner.borrow_mut();
+        inner.query = None;
+        inner.matches = (0..inner.titles.len()).collect();
+        inner.cursor = selected.unwrap_or_default();
     }
+
+    pub(crate) fn push_search_char(&self, c: char) {
+        let query = self.inner.borrow().query.clone().unwrap_or_default();
+        self.update_query(query + &c.to_string());
+    }
+
+    /// Remove the last character in the search, or stop searching if it's already empty.
+    pub(crate) fn pop_search_char(&self) {
+        let mut query = self.inner.borrow().query.clone().unwrap_or_default();
+        match query.pop() {
+            Some(_) => self.update_query(query),
+            None => self.cancel_search(),
+        }
+    }
+
+    fn update_query(&self, query: String) {
+        let mut inner = self.inner.borrow_mut();
+        let mut scores: Vec<_> = inner
+            .titles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, title)| fuzzy_score(&query, title).map(|score| (index, score)))
+            .collect();
+        // This is a stable sort so slides that score the same stay in presentation order.
+        scores.sort_by_key(|(_, score)| Reverse(*score));
+        inner.matches = scores.into_iter().map(|(index, _)| index).collect();
+        inner.query = Some(query);
+        inner.cursor = 0;
+    }
+}
+
+/// Scores how well a query fuzzy matches a piece of text, or returns `None` if it doesn't match.
+///
+/// Every character in the query must appear in the text in the same order, ignoring case.
+/// Characters matched next to each other or at the beginning of a word score higher, and gaps
+/// between matched characters lower the score.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous = None;
+    for needle in query.chars().flat_map(char::to_lowercase) {
+        let gap = text.get(position..)?.iter().position(|c| *c == needle)?;
+        let index = position + gap;
+        score += 1;
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        match previous {
+            Some(previous) if previous + 1 == index => score += 5,
+            Some(_) => score -= gap as i64,
+            None => (),
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+fn plain_text<'a>(texts: impl IntoIterator<Item = &'a Text>) -> String {
+    texts.into_iter().map(|text| text.content.as_str()).collect()
+}
+
+/// Truncate some text so it fits in the given width.
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let mut output = String::new();
+    // Leave room for the ellipsis.
+    let mut width = 1;
+    for c in text.chars() {
+        width += c.width().unwrap_or(0);
+        if width > max_width {
+            break;
+        }
+        output.push(c);
+    }
+    output.push('…');
+    output
 }
 
 #[derive(Debug)]
@@ -66,34 +236,85 @@ struct IndexDrawer {
     prefix: Vec<RenderOperation>,
     rows: Vec<ContentRow>,
     suffix: Vec<RenderOperation>,
+    previews: Vec<Vec<String>>,
+    state: SlideIndexState,
     content_width: u16,
-    state: PresentationState,
     selection_style: TextStyle,
     background: Option<Image>,
 }
 
+impl IndexDrawer {
+    fn max_text_width(&self) -> usize {
+        // The text goes between "|  " and "  |".
+        self.content_width.saturating_sub(6) as usize
+    }
+
+    fn text_row(&self, text: &str) -> ContentRow {
+        let text = truncate(text, self.max_text_width());
+        ModalBuilder::build_line(vec![Text::from(text)], self.content_width)
+    }
+
+    fn search_row(&self, query: &str) -> ContentRow {
+        // If the query doesn't fit, show its end since that's where it's being typed.
+        let mut query = query;
+        while !query.is_empty() && query.width() + 1 > self.max_text_width() {
+            let mut chars = query.chars();
+            chars.next();
+            query = chars.as_str();
+        }
+        let text = Text::from(format!("/{query}"));
+        ModalBuilder::build_line(vec![text], self.content_width).with_style(self.selection_style)
+    }
+}
+
 impl AsRenderOperations for IndexDrawer {
     fn as_render_operations(&self, dimensions: &WindowSize) -> Vec<RenderOperation> {
-        let current_slide_index = self.state.current_slide_index();
-        let max_rows = (dimensions.rows as f64 * 0.8) as u16;
-        let (skip, take) = match self.rows.len() as u16 > max_rows {
-            true => {
-                let start = (current_slide_index as u16).saturating_sub(max_rows / 2);
-                let start = start.min(self.rows.len() as u16 - max_rows);
-                (start as usize, max_rows as usize)
-            }
-            false => (0, self.rows.len()),
+        let state = self.state.inner.borrow();
+        let preview_height = self.previews.iter().map(Vec::len).max().unwrap_or(0);
+        // The search line and the preview, along with the separator above it.
+        let search_rows = usize::from(state.query.is_some());
+        let preview_rows = if preview_height > 0 { preview_height + 1 } else { 0 };
+        let extra_rows = search_rows + preview_rows;
+        let max_rows = ((dimensions.rows as f64 * 0.8) as usize).saturating_sub(extra_rows).max(1);
+        // Keep the list the same height while searching so the modal doesn't move around.
+        let list_height = self.rows.len().min(max_rows);
+        let skip = match state.matches.len() > list_height {
+            true => state.cursor.saturating_sub(list_height / 2).min(state.matches.len() - list_height),
+            false => 0,
         };
-        let visible_rows = self.rows.iter().enumerate().skip(skip).take(take);
-        let mut operations = vec![CenterModalContent::new(self.content_width, take, self.background.clone()).into()];
-        operations.extend(self.prefix.iter().cloned());
-        for (index, row) in visible_rows {
-            let mut row = row.clone();
-            if index == current_slide_index {
+
+        let mut rows = Vec::new();
+        if let Some(query) = &state.query {
+            rows.push(self.search_row(query));
+        }
+        for (position, slide) in state.matches.iter().enumerate().skip(skip).take(list_height) {
+            let mut row = self.rows[*slide].clone();
+            if position == state.cursor {
                 row = row.with_style(self.selection_style);
             }
-            let operation = RenderOperation::RenderText { line: row.build(), alignment: Default::default() };
-            operations.extend([operation, RenderOperation::RenderLineBreak]);
+            rows.push(row);
+        }
+        if state.matches.is_empty() {
+            rows.push(self.text_row("no matches"));
+        }
+        let missing_rows = list_height.saturating_sub(state.matches.len().max(1));
+        rows.extend(iter::repeat_with(|| self.text_row("")).take(missing_rows));
+
+        let mut operations =
+            vec![CenterModalContent::new(self.content_width, list_height + extra_rows, self.background.clone()).into()];
+        operations.extend(self.prefix.iter().cloned());
+        let into_operations = |row: ContentRow| {
+            [
+                RenderOperation::RenderText { line: row.build(), alignment: Default::default() },
+                RenderOperation::RenderLineBreak,
+            ]
+        };
+        operations.extend(rows.into_iter().flat_map(into_operations));
+        if preview_height > 0 {
+            let preview = state.matches.get(state.cursor).map(|slide| self.previews[*slide].as_slice()).unwrap_or(&[]);
+            operations.extend(Border::Separator.render_line(self.content_width));
+            let lines = preview.iter().map(String::as_str).chain(iter::repeat("")).take(preview_height);
+            operations.extend(lines.map(|line| self.text_row(line)).flat_map(into_operations));
         }
         operations.extend(self.suffix.iter().cloned());
         operations
@@ -123,6 +344,11 @@ impl KeyBindingsModalBuilder {
             Self::build_line("Execute code", &config.execute_code),
             Self::build_line("Reload", &config.reload),
             Self::build_line("Toggle slide index", &config.toggle_slide_index),
+            Self::build_line("Pause/resume recording", &config.toggle_playback),
+            Self::build_line("Seek recording forward", &config.seek_playback_forward),
+            Self::build_line("Seek recording backward", &config.seek_playback_backward),
+            Self::build_line("Speed up recording", &config.speed_up_playback),
+            Self::build_line("Slow down recording", &config.slow_down_playback),
             Self::build_line("Close modal", &config.close_modal),
             Self::build_line("Exit", &config.exit),
         ]);
@@ -322,3 +548,125 @@ impl From<CenterModalContent> for RenderOperation {
         Self::RenderDynamic(Rc::new(op))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn make_state() -> SlideIndexState {
+        let titles = ["Introduction", "Architecture", "Closing thoughts"];
+        SlideIndexState::new(titles.into_iter().map(String::from).collect())
+    }
+
+    #[rstest]
+    #[case::exact("intro", true)]
+    #[case::subsequence("itdn", true)]
+    #[case::case_insensitive("INTRO", true)]
+    #[case::empty("", true)]
+    #[case::out_of_order("ni", true)]
+    #[case::wrong_order("oni", false)]
+    #[case::missing("x", false)]
+    fn fuzzy_matching(#[case] query: &str, #[case] matches: bool) {
+        assert_eq!(fuzzy_score(query, "Introduction").is_some(), matches);
+    }
+
+    #[rstest]
+    #[case::consecutive("arch", "Architecture", "a rich cherry")]
+    #[case::word_start("th", "Closing thoughts", "Something")]
+    fn fuzzy_ranking(#[case] query: &str, #[case] better: &str, #[case] worse: &str) {
+        let better = fuzzy_score(query, better).expect("no match");
+        let worse = fuzzy_score(query, worse).expect("no match");
+        assert!(better > worse, "{better} <= {worse}");
+    }
+
+    #[test]
+    fn navigation() {
+        let state = make_state();
+        state.open(1);
+        assert_eq!(state.selected_slide(), Some(1));
+
+        state.select_next();
+        state.select_next();
+        assert_eq!(state.selected_slide(), Some(2));
+
+        state.select_previous();
+        state.select_previous();
+        state.select_previous();
+        assert_eq!(state.selected_slide(), Some(0));
+    }
+
+    #[test]
+    fn search() {
+        let state = make_state();
+        state.open(0);
+        state.start_search();
+        assert!(state.is_searching());
+
+        state.push_search_char('c');
+        state.push_search_char('t');
+        assert_eq!(state.inner.borrow().matches, &[0, 2, 1]);
+        assert_eq!(state.selected_slide(), Some(0));
+
+        state.push_search_char('z');
+        assert_eq!(state.selected_slide(), None);
+
+        state.pop_search_char();
+        state.select_next();
+        assert_eq!(state.selected_slide(), Some(2));
+    }
+
+    #[test]
+    fn cancel_search() {
+        let state = make_state();
+        state.open(0);
+        state.start_search();
+        state.push_search_char('c');
+        state.push_search_char('l');
+        state.cancel_search();
+        assert!(!state.is_searching());
+        assert_eq!(state.inner.borrow().matches, &[0, 1, 2]);
+        assert_eq!(state.selected_slide(), Some(2));
+    }
+
+    #[test]
+    fn empty_search_closes() {
+        let state = make_state();
+        state.open(0);
+        state.start_search();
+        state.push_search_char('a');
+        state.pop_search_char();
+        assert!(state.is_searching());
+
+        state.pop_search_char();
+        assert!(!state.is_searching());
+    }
+
+    #[test]
+    fn previews() {
+        let text = |text: &str| RenderOperation::RenderText {
+            line: WeightedLine::from(vec![Text::from(text)]),
+            alignment: Default::default(),
+        };
+        let operations = [
+            text("Intro"),
+            RenderOperation::RenderLineBreak,
+            text("  "),
+            text("first"),
+            text("second  "),
+            text("third"),
+        ];
+        let mut builder = IndexBuilder::default();
+        builder.add_slide(Line::from("Intro"), operations.iter());
+        builder.add_slide(Line::from("Empty"), iter::empty());
+        assert_eq!(builder.previews, &[vec!["first", "second", "third"], vec![]]);
+    }
+
+    #[rstest]
+    #[case::fits("hello", 5, "hello")]
+    #[case::cut("hello world", 6, "hello…")]
+    #[case::wide("日本語", 4, "日…")]
+    fn truncation(#[case] text: &str, #[case] width: usize, #[case] expected: &str) {
+        assert_eq!(truncate(text, width), expected);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{
+    markdown::{elements::Text, text_style::TextStyle},
+    render::{
+        engine::{RenderEngine, RenderEngineOptions},
+        operation::{AsRenderOperations, RenderOperation},
+        properties::WindowSize,
+    },
+    terminal::virt::{ImageBehavior, StyledChar, TerminalGrid, TerminalRowIterator, VirtualTerminal},
+    theme::{Alignment, BorderCharacters, Margin, SpeakerNotesLayout, SpeakerNotesStyle},
+};
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
+const PREVIEW_LABEL: &str = " Next ";
+const END_OF_PRESENTATION: &str = "End of presentation";
+
+/// The presenter view shown in the speaker notes window.
+///
+/// This shows the notes for the current slide next to a preview of whatever comes after it, along
+/// with a status bar that contains the slide being presented and the time.
+#[derive(Debug)]
+pub(crate) struct PresenterView {
+    /// The operations that render the current slide's notes.
+    pub(crate) notes: Vec<RenderOperation>,
+
+    /// The operations that render the next slide or chunk, if there's one.
+    pub(crate) next: Option<Vec<RenderOperation>>,
+
+    /// The current slide, 1 based.
+    pub(crate) slide: usize,
+
+    pub(crate) total_slides: usize,
+
+    /// How long the presentation has been going on for.
+    pub(crate) elapsed: Duration,
+
+    /// The wall clock time.
+    pub(crate) clock: String,
+
+    pub(crate) style: SpeakerNotesStyle,
+}
+
+impl PresenterView {
+    fn draw_status_bar(&self, canvas: &mut Canvas) {
+        let style = self.style.status_bar_style;
+        let columns = canvas.columns();
+        canvas.fill(Rect { row: 0, column: 0, rows: 1, columns }, style);
+
+        let slide = format!("Slide {}/{}", self.slide, self.total_slides);
+        canvas.write(0, 1, &slide, style.bold());
+
+        let seconds = self.elapsed.as_secs();
+        let elapsed = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+        canvas.write(0, columns.saturating_sub(elapsed.width() as u16) / 2, &elapsed, style);
+
+        let clock_column = columns.saturating_sub(self.clock.width() as u16 + 1);
+        canvas.write(0, clock_column, &self.clock, style);
+    }
+
+    /// Split the area below the status bar into the notes and the preview, including its border.
+    fn split(&self, dimensions: &WindowSize) -> (Rect, Rect) {
+        let body = Rect { row: 2, column: 0, rows: dimensions.rows.saturating_sub(2), columns: dimensions.columns };
+        let size = self.style.preview_size as u32;
+        // The preview keeps the window's aspect ratio so it looks like a smaller version of it.
+        let aspect_rows =
+            |columns: u16| (columns as u32 * dimensions.rows as u32 / dimensions.columns.max(1) as u32) as u16;
+        let aspect_columns =
+            |rows: u16| (rows as u32 * dimensions.columns as u32 / dimensions.rows.max(1) as u32) as u16;
+        match self.style.layout {
+            SpeakerNotesLayout::SideBySide => {
+                let columns = (body.columns as u32 * size / 100) as u16;
+                let rows = (aspect_rows(columns.saturating_sub(2)) + 2).min(body.rows);
+                let preview = Rect { row: body.row, column: body.columns - columns, rows, columns };
+                let notes =
+                    Rect { row: body.row, column: 1, rows: body.rows, columns: preview.column.saturating_sub(2) };
+                (notes, preview)
+            }
+            SpeakerNotesLayout::Stacked => {
+                let rows = (body.rows as u32 * size / 100) as u16;
+                let columns = (aspect_columns(rows.saturating_sub(2)) + 2).min(body.columns);
+                let preview =
+                    Rect { row: body.row + body.rows - rows, column: (body.columns - columns) / 2, rows, columns };
+                let notes = Rect {
+                    row: body.row,
+                    column: 1,
+                    rows: body.rows.saturating_sub(rows + 1),
+                    columns: body.columns.saturating_sub(2),
+                };
+                (notes, preview)
+            }
+        }
+    }
+
+    fn draw_preview(&self, canvas: &mut Canvas, area: Rect, dimensions: &WindowSize) {
+        let style = self.style.preview_style;
+        let inner = match &self.style.preview_border {
+            Some(border) => {
+                canvas.draw_border(area, border, style);
+                canvas.write(area.row, area.column + 2, PREVIEW_LABEL, style);
+                area.shrink(1)
+            }
+            None => {
+                canvas.write(area.row, area.column, PREVIEW_LABEL.trim(), style);
+                Rect { row: area.row + 1, rows: area.rows.saturating_sub(1), ..area }
+            }
+        };
+        match &self.next {
+            Some(operations) => {
+                if let Some(grid) = render_grid(operations, inner.window_size(dimensions)) {
+                    canvas.paste(inner, &grid);
+                }
+            }
+            None => {
+                let column = inner.column + inner.columns.saturating_sub(END_OF_PRESENTATION.width() as u16) / 2;
+                canvas.write(inner.row + inner.rows / 2, column, END_OF_PRESENTATION, style);
+            }
+        }
+    }
+}
+
+impl AsRenderOperations for PresenterView {
+    fn as_render_operations(&self, dimensions: &WindowSize) -> Vec<RenderOperation> {
+        let mut canvas = Canvas::new(dimensions);
+        self.draw_status_bar(&mut canvas);
+
+        let (notes, preview) = self.split(dimensions);
+        if let Some(grid) = render_grid(&self.notes, notes.window_size(dimensions)) {
+            canvas.paste(notes, &grid);
+        }
+        self.draw_preview(&mut canvas, preview, dimensions);
+
+        let mut operations = vec![RenderOperation::SetColors(self.style.style.colors), RenderOperation::ClearScreen];
+        for (index, row) in canvas.rows.iter().enumerate() {
+            let line: Vec<Text> = TerminalRowIterator::new(row).collect();
+            operations.extend([
+                RenderOperation::JumpToRow { index: index as u16 },
+                RenderOperation::RenderText {
+                    line: line.into(),
+                    alignment: Alignment::Left { margin: Margin::Fixed(0) },
+                },
+            ]);
+        }
+        operations
+    }
+}
+
+/// Render operations into a terminal of the given size.
+fn render_grid(operations: &[RenderOperation], dimensions: WindowSize) -> Option<TerminalGrid> {
+    if dimensions.rows == 0 || dimensions.columns == 0 {
+        return None;
+    }
+    let mut terminal = VirtualTerminal::new(dimensions, ImageBehavior::PrintAscii);
+    let engine = RenderEngine::new(&mut terminal, dimensions, RenderEngineOptions::default());
+    engine.render(operations.iter()).ok()?;
+    Some(terminal.into_contents())
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rect {
+    row: u16,
+    column: u16,
+    rows: u16,
+    columns: u16,
+}
+
+impl Rect {
+    /// Get the size of a window as big as this area, within a window of the given dimensions.
+    fn window_size(&self, dimensions: &WindowSize) -> WindowSize {
+        WindowSize {
+            rows: self.rows,
+            columns: self.columns,
+            height: (dimensions.height as u32 * self.rows as u32 / dimensions.rows.max(1) as u32) as u16,
+            width: (dimensions.width as u32 * self.columns as u32 / dimensions.columns.max(1) as u32) as u16,
+        }
+    }
+
+    fn shrink(self, amount: u16) -> Self {
+        Self {
+            row: self.row + amount,
+            column: self.column + amount,
+            rows: self.rows.saturating_sub(amount * 2),
+            columns: self.columns.saturating_sub(amount * 2),
+        }
+    }
+}
+
+/// The cells that make up the entire presenter view.
+struct Canvas {
+    rows: Vec<Vec<StyledChar>>,
+}
+
+impl Canvas {
+    fn new(dimensions: &WindowSize) -> Self {
+        Self { rows: vec![vec![StyledChar::default(); dimensions.columns as usize]; dimensions.rows as usize] }
+    }
+
+    fn columns(&self) -> u16 {
+        self.rows.first().map(Vec::len).unwrap_or_default() as u16
+    }
+
+    fn set(&mut self, row: u16, column: u16, character: char, style: TextStyle) {
+        if let Some(cell) = self.rows.get_mut(row as usize).and_then(|row| row.get_mut(column as usize)) {
+            *cell = StyledChar { character, style };
+        }
+    }
+
+    fn write(&mut self, row: u16, column: u16, text: &str, style: TextStyle) {
+        for (index, character) in text.chars().enumerate() {
+            self.set(row, column + index as u16, character, style);
+        }
+    }
+
+    fn fill(&mut self, area: Rect, style: TextStyle) {
+        for row in area.row..area.row + area.rows {
+            for column in area.column..area.column + area.columns {
+                self.set(row, column, ' ', style);
+            }
+        }
+    }
+
+    fn draw_border(&mut self, area: Rect, border: &BorderCharacters, style: TextStyle) {
+        if area.rows < 2 || area.columns < 2 {
+            return;
+        }
+        let (bottom, right) = (area.row + area.rows - 1, area.column + area.columns - 1);
+        for column in area.column + 1..right {
+            self.set(area.row, column, border.horizontal, style);
+            self.set(bottom, column, border.horizontal, style);
+        }
+        for row in area.row + 1..bottom {
+            self.set(row, area.column, border.vertical, style);
+            self.set(row, right, border.vertical, style);
+        }
+        self.set(area.row, area.column, border.top_left, style);
+        self.set(area.row, right, border.top_right, style);
+        self.set(bottom, area.column, border.bottom_left, style);
+        self.set(bottom, right, border.bottom_right, style);
+    }
+
+    /// Copy a rendered grid into the given area.
+    fn paste(&mut self, area: Rect, grid: &TerminalGrid) {
+        for (y, row) in grid.rows.iter().take(area.rows as usize).enumerate() {
+            for (x, cell) in row.iter().take(area.columns as usize).enumerate() {
+                let mut style = cell.style;
+                // Everything is drawn at its regular size so it fits in the area.
+                style.size = 1;
+                if style.colors.background.is_none() {
+                    style.colors.background = grid.background_color;
+                }
+                self.set(area.row + y as u16, area.column + x as u16, cell.character, style);
+            }
+        }
+    }
+}
+
+/// Get the current local time as hours and minutes.
+pub(crate) fn wall_clock() -> String {
+    #[cfg(unix)]
+    let (hours, minutes) = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut time: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&now, &mut time);
+        (time.tm_hour as u64, time.tm_min as u64)
+    };
+    #[cfg(not(unix))]
+    let (hours, minutes) = {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        (now.as_secs() / 3600 % 24, now.as_secs() / 60 % 60)
+    };
+    format!("{hours:02}:{minutes:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::raw;
+
+    fn render(view: &PresenterView) -> Vec<String> {
+        let dimensions = WindowSize { rows: 20, columns: 60, height: 200, width: 300 };
+        let operations = view.as_render_operations(&dimensions);
+        let grid = render_grid(&operations, dimensions).expect("render failed");
+        grid.rows.iter().map(|row| row.iter().map(|c| c.character).collect()).collect()
+    }
+
+    fn view(next: Option<&str>, layout: raw::SpeakerNotesLayout) -> PresenterView {
+        let text = |text: &str| RenderOperation::RenderText { line: text.into(), alignment: Default::default() };
+        let style = SpeakerNotesStyle { layout, ..Default::default() };
+        PresenterView {
+            notes: vec![text("remember the demo")],
+            next: next.map(|next| vec![text(next)]),
+            slide: 2,
+            total_slides: 5,
+            elapsed: Duration::from_secs(3725),
+            clock: "14:05".into(),
+            style,
+        }
+    }
+
+    #[test]
+    fn status_bar() {
+        let rows = render(&view(None, Default::default()));
+        let status_bar = &rows[0];
+        assert!(status_bar.starts_with(" Slide 2/5"));
+        assert!(status_bar.contains("01:02:05"));
+        assert!(status_bar.ends_with("14:05 "));
+    }
+
+    #[test]
+    fn side_by_side() {
+        let rows = render(&view(Some("next slide"), raw::SpeakerNotesLayout::SideBySide));
+        let notes_row = rows.iter().find(|row| row.contains("remember the demo")).expect("no notes");
+        let preview_row = rows.iter().find(|row| row.contains("next slide")).expect("no preview");
+        assert!(notes_row.find("remember").unwrap() < 30);
+        assert!(preview_row.find("next slide").unwrap() > 30);
+        assert!(rows.iter().any(|row| row.contains(PREVIEW_LABEL)));
+    }
+
+    #[test]
+    fn stacked() {
+        let rows = render(&view(Some("next slide"), raw::SpeakerNotesLayout::Stacked));
+        let notes_row = rows.iter().position(|row| row.contains("remember the demo")).expect("no notes");
+        let preview_row = rows.iter().position(|row| row.contains("next slide")).expect("no preview");
+        assert!(notes_row < preview_row);
+    }
+
+    #[test]
+    fn end_of_presentation() {
+        let rows = render(&view(None, Default::default()));
+        assert!(rows.iter().any(|row| row.contains(END_OF_PRESENTATION)));
+    }
+}